@@ -0,0 +1,12 @@
+-- Track presigned uploads until they are attached to a portfolio item
+CREATE TABLE IF NOT EXISTS upload_sessions (
+    object_key TEXT PRIMARY KEY,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    content_type VARCHAR(100) NOT NULL,
+    attached_at TIMESTAMP WITH TIME ZONE, -- NULL until a portfolio item uses the object
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
+
+-- The orphan sweeper only ever looks at unattached sessions
+CREATE INDEX IF NOT EXISTS idx_upload_sessions_unattached ON upload_sessions(created_at) WHERE attached_at IS NULL;
+CREATE INDEX IF NOT EXISTS idx_upload_sessions_user_id ON upload_sessions(user_id);
@@ -0,0 +1,11 @@
+-- Outbox of stored objects to delete, written in the same transaction that drops the reference
+CREATE TABLE IF NOT EXISTS storage_deletions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    object_url TEXT NOT NULL,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    last_error TEXT,
+    next_attempt_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_storage_deletions_next_attempt_at ON storage_deletions(next_attempt_at);
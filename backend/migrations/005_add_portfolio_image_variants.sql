@@ -0,0 +1,4 @@
+-- Resized WebP variants generated on upload; image_url holds the full-size variant.
+-- NULL for items created before processing existed, clients fall back to image_url.
+ALTER TABLE portfolio_items ADD COLUMN IF NOT EXISTS thumbnail_url TEXT;
+ALTER TABLE portfolio_items ADD COLUMN IF NOT EXISTS medium_url TEXT;
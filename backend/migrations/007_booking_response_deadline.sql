@@ -0,0 +1,23 @@
+-- MUAs must answer a booking request before its response deadline, otherwise it expires
+ALTER TABLE bookings ADD COLUMN IF NOT EXISTS response_deadline TIMESTAMP WITH TIME ZONE;
+ALTER TABLE bookings ADD COLUMN IF NOT EXISTS cancellation_reason TEXT;
+
+-- Existing pending requests get the default 24h window, but never past the event itself
+UPDATE bookings
+SET response_deadline = LEAST(created_at + INTERVAL '24 hours', event_date)
+WHERE status = 'pending' AND response_deadline IS NULL;
+
+CREATE INDEX IF NOT EXISTS idx_bookings_pending_response_deadline ON bookings(response_deadline) WHERE status = 'pending';
+
+-- Audit trail of every status change, including ones made by background sweeps (changed_by NULL)
+CREATE TABLE IF NOT EXISTS booking_status_history (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    booking_id UUID NOT NULL REFERENCES bookings(id) ON DELETE CASCADE,
+    from_status booking_status,
+    to_status booking_status NOT NULL,
+    changed_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    reason TEXT,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_booking_status_history_booking_id ON booking_status_history(booking_id);
@@ -0,0 +1,41 @@
+-- Confirmed bookings are completed automatically once the event is over, which opens reviews
+ALTER TABLE bookings ADD COLUMN IF NOT EXISTS completed_at TIMESTAMP WITH TIME ZONE;
+ALTER TABLE bookings ADD COLUMN IF NOT EXISTS review_window_closes_at TIMESTAMP WITH TIME ZONE;
+
+-- Bookings completed before this migration keep a window counted from their last update
+UPDATE bookings
+SET completed_at = updated_at,
+    review_window_closes_at = updated_at + INTERVAL '14 days'
+WHERE status = 'completed' AND completed_at IS NULL;
+
+CREATE INDEX IF NOT EXISTS idx_bookings_confirmed_event_date ON bookings(event_date) WHERE status = 'confirmed';
+
+-- One review per party per booking. Reviews could be repeated before, so keep each
+-- party's first and refresh the ratings of anyone who had duplicates
+CREATE TEMP TABLE duplicate_reviews AS
+SELECT id, reviewee_id
+FROM (
+    SELECT id, reviewee_id,
+           ROW_NUMBER() OVER (PARTITION BY booking_id, reviewer_id ORDER BY created_at NULLS LAST, id) AS position
+    FROM reviews
+    WHERE booking_id IS NOT NULL
+) ranked
+WHERE position > 1;
+
+DELETE FROM reviews WHERE id IN (SELECT id FROM duplicate_reviews);
+
+UPDATE mua_profiles mp
+SET average_rating = stats.average_rating,
+    total_reviews = stats.total_reviews,
+    updated_at = NOW()
+FROM (
+    SELECT reviewee_id, ROUND(AVG(rating)::numeric, 2) AS average_rating, COUNT(id)::int AS total_reviews
+    FROM reviews
+    GROUP BY reviewee_id
+) stats
+WHERE stats.reviewee_id = mp.user_id
+  AND mp.user_id IN (SELECT reviewee_id FROM duplicate_reviews);
+
+DROP TABLE duplicate_reviews;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_reviews_booking_reviewer ON reviews(booking_id, reviewer_id);
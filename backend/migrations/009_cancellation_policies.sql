@@ -0,0 +1,8 @@
+-- Tiered refund rules per MUA; NULL means the default policy
+ALTER TABLE mua_profiles ADD COLUMN IF NOT EXISTS cancellation_policy JSONB;
+
+-- The policy in effect when the booking was made, and what is owed back if it's cancelled
+ALTER TABLE bookings ADD COLUMN IF NOT EXISTS cancellation_policy JSONB;
+ALTER TABLE bookings ADD COLUMN IF NOT EXISTS refund_amount DECIMAL(10, 2);
+ALTER TABLE bookings ADD COLUMN IF NOT EXISTS refund_reference TEXT;
+ALTER TABLE bookings ADD COLUMN IF NOT EXISTS refunded_at TIMESTAMP WITH TIME ZONE;
@@ -0,0 +1,23 @@
+CREATE TYPE reschedule_status AS ENUM ('pending', 'accepted', 'declined', 'withdrawn');
+
+-- A proposal from one party to move a booking; the other party accepts or declines it
+CREATE TABLE IF NOT EXISTS reschedule_requests (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    booking_id UUID NOT NULL REFERENCES bookings(id) ON DELETE CASCADE,
+    requested_by UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    proposed_event_date TIMESTAMP WITH TIME ZONE NOT NULL,
+    proposed_duration_hours INTEGER NOT NULL CHECK (proposed_duration_hours > 0),
+    previous_event_date TIMESTAMP WITH TIME ZONE NOT NULL,
+    previous_duration_hours INTEGER NOT NULL,
+    reason TEXT,
+    status reschedule_status NOT NULL DEFAULT 'pending',
+    responded_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    responded_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_reschedule_requests_booking_id ON reschedule_requests(booking_id);
+
+-- At most one open proposal per booking
+CREATE UNIQUE INDEX IF NOT EXISTS idx_reschedule_requests_one_pending
+    ON reschedule_requests(booking_id) WHERE status = 'pending';
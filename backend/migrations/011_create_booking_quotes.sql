@@ -0,0 +1,20 @@
+CREATE TYPE quote_status AS ENUM ('open', 'accepted', 'countered', 'closed');
+
+-- Price negotiation on a pending booking: the MUA quotes, then each side accepts or counters in turn
+CREATE TABLE IF NOT EXISTS booking_quotes (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    booking_id UUID NOT NULL REFERENCES bookings(id) ON DELETE CASCADE,
+    proposed_by UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    price DECIMAL(10, 2) NOT NULL CHECK (price > 0),
+    deposit_amount DECIMAL(10, 2) CHECK (deposit_amount >= 0),
+    notes TEXT,
+    status quote_status NOT NULL DEFAULT 'open',
+    responded_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_booking_quotes_booking_id ON booking_quotes(booking_id);
+
+-- Only the latest offer can be answered
+CREATE UNIQUE INDEX IF NOT EXISTS idx_booking_quotes_one_open
+    ON booking_quotes(booking_id) WHERE status = 'open';
@@ -0,0 +1,21 @@
+-- What a booking is made of: one row per service with how many people get it.
+-- bookings.price and bookings.duration_hours are derived from these when the booking is made.
+CREATE TABLE IF NOT EXISTS booking_line_items (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    booking_id UUID NOT NULL REFERENCES bookings(id) ON DELETE CASCADE,
+    service_type VARCHAR(100) NOT NULL,
+    person_count INTEGER NOT NULL CHECK (person_count > 0),
+    unit_price DECIMAL(10, 2) NOT NULL CHECK (unit_price >= 0),
+    minutes_per_person INTEGER NOT NULL CHECK (minutes_per_person > 0),
+    position INTEGER NOT NULL DEFAULT 0, -- order the customer listed the services in
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_booking_line_items_booking_id ON booking_line_items(booking_id);
+
+-- Existing bookings become a single line item for one person
+INSERT INTO booking_line_items (booking_id, service_type, person_count, unit_price, minutes_per_person, created_at)
+SELECT b.id, LEFT(b.service_type, 100), 1, b.price, b.duration_hours * 60, b.created_at
+FROM bookings b
+WHERE b.duration_hours > 0
+  AND NOT EXISTS (SELECT 1 FROM booking_line_items li WHERE li.booking_id = b.id);
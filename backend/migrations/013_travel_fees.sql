@@ -0,0 +1,10 @@
+-- How far a MUA travels and what it costs; a fee of 0 means travel is included
+ALTER TABLE mua_profiles ADD COLUMN IF NOT EXISTS travel_free_radius_km DOUBLE PRECISION NOT NULL DEFAULT 0;
+ALTER TABLE mua_profiles ADD COLUMN IF NOT EXISTS travel_fee_per_km DECIMAL(10, 2) NOT NULL DEFAULT 0;
+ALTER TABLE mua_profiles ADD COLUMN IF NOT EXISTS travel_max_distance_km DOUBLE PRECISION; -- NULL means no limit
+
+-- Where the event takes place and the travel fee charged for it, which is included in price
+ALTER TABLE bookings ADD COLUMN IF NOT EXISTS event_latitude DOUBLE PRECISION;
+ALTER TABLE bookings ADD COLUMN IF NOT EXISTS event_longitude DOUBLE PRECISION;
+ALTER TABLE bookings ADD COLUMN IF NOT EXISTS travel_distance_km DOUBLE PRECISION;
+ALTER TABLE bookings ADD COLUMN IF NOT EXISTS travel_fee DECIMAL(10, 2) NOT NULL DEFAULT 0;
@@ -0,0 +1,5 @@
+-- Time a MUA keeps free between bookings: preparation plus travel, where the travel
+-- part can grow with the distance between the two events' coordinates
+ALTER TABLE mua_profiles ADD COLUMN IF NOT EXISTS prep_buffer_minutes INTEGER NOT NULL DEFAULT 0 CHECK (prep_buffer_minutes >= 0);
+ALTER TABLE mua_profiles ADD COLUMN IF NOT EXISTS travel_buffer_minutes INTEGER NOT NULL DEFAULT 0 CHECK (travel_buffer_minutes >= 0);
+ALTER TABLE mua_profiles ADD COLUMN IF NOT EXISTS travel_buffer_minutes_per_km DOUBLE PRECISION NOT NULL DEFAULT 0 CHECK (travel_buffer_minutes_per_km >= 0);
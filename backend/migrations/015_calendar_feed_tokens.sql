@@ -0,0 +1,4 @@
+-- Secret for a MUA's subscribable ICS feed; created on first use and replaced when rotated
+ALTER TABLE mua_profiles ADD COLUMN IF NOT EXISTS calendar_feed_token VARCHAR(64);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_mua_profiles_calendar_feed_token ON mua_profiles(calendar_feed_token);
@@ -0,0 +1,29 @@
+-- Calendars a MUA keeps elsewhere; source_url is NULL for a one-off file upload
+CREATE TABLE IF NOT EXISTS external_calendars (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    mua_id UUID NOT NULL REFERENCES mua_profiles(id) ON DELETE CASCADE,
+    name VARCHAR(255) NOT NULL,
+    source_url TEXT,
+    last_synced_at TIMESTAMP WITH TIME ZONE,
+    last_error TEXT,
+    last_attempted_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_external_calendars_mua_id ON external_calendars(mua_id);
+
+-- Events imported from those calendars; the MUA can't be booked while one is running.
+-- Replaced wholesale each time the calendar is imported.
+CREATE TABLE IF NOT EXISTS busy_blocks (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    mua_id UUID NOT NULL REFERENCES mua_profiles(id) ON DELETE CASCADE,
+    external_calendar_id UUID NOT NULL REFERENCES external_calendars(id) ON DELETE CASCADE,
+    uid TEXT,
+    summary TEXT,
+    starts_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    ends_at TIMESTAMP WITH TIME ZONE NOT NULL,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    CHECK (ends_at > starts_at)
+);
+
+CREATE INDEX IF NOT EXISTS idx_busy_blocks_mua_time ON busy_blocks(mua_id, starts_at, ends_at);
@@ -0,0 +1,34 @@
+-- IANA zone a MUA works in; availability slots are wall-clock times in this zone.
+-- Existing profiles get the zone their location falls in, so the backfill only runs
+-- when the column is first added and never overwrites a zone the MUA picked.
+DO $$
+BEGIN
+    IF NOT EXISTS (
+        SELECT 1 FROM information_schema.columns
+        WHERE table_name = 'mua_profiles' AND column_name = 'timezone'
+    ) THEN
+        ALTER TABLE mua_profiles ADD COLUMN timezone VARCHAR(64) NOT NULL DEFAULT 'Asia/Jakarta';
+
+        -- Eastern Indonesia (WIT): Papua and Maluku
+        UPDATE mua_profiles SET timezone = 'Asia/Jayapura'
+        WHERE location ~* '(papua|jayapura|sorong|merauke|manokwari|timika|maluku|ambon|ternate|tual)';
+
+        -- Central Indonesia (WITA): Bali, Nusa Tenggara, Sulawesi and east, south and north Kalimantan
+        UPDATE mua_profiles SET timezone = 'Asia/Makassar'
+        WHERE timezone = 'Asia/Jakarta'
+          AND location ~* '(bali|denpasar|badung|gianyar|ubud|kuta|seminyak|nusa tenggara|ntb|ntt|lombok|mataram|kupang|labuan bajo|sulawesi|makassar|manado|palu|kendari|gorontalo|kalimantan (timur|selatan|utara)|balikpapan|samarinda|banjarmasin|banjarbaru|tarakan|bontang)';
+    END IF;
+END $$;
+
+-- A one-off slot is a day on the MUA's calendar, not an instant. Existing rows were
+-- stored as midnight UTC of the intended day, so that day is kept.
+DO $$
+BEGIN
+    IF EXISTS (
+        SELECT 1 FROM information_schema.columns
+        WHERE table_name = 'availability_slots' AND column_name = 'specific_date' AND data_type <> 'date'
+    ) THEN
+        ALTER TABLE availability_slots
+            ALTER COLUMN specific_date TYPE DATE USING (specific_date AT TIME ZONE 'UTC')::date;
+    END IF;
+END $$;
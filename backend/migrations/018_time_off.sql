@@ -0,0 +1,13 @@
+-- Whole days a MUA can't be booked, such as Lebaran holidays or sick leave.
+-- Dates are inclusive and read in the MUA's timezone; they override any availability slots.
+CREATE TABLE IF NOT EXISTS time_off (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    mua_id UUID NOT NULL REFERENCES mua_profiles(id) ON DELETE CASCADE,
+    start_date DATE NOT NULL,
+    end_date DATE NOT NULL,
+    reason TEXT,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    CHECK (end_date >= start_date)
+);
+
+CREATE INDEX IF NOT EXISTS idx_time_off_mua_dates ON time_off(mua_id, start_date, end_date);
@@ -0,0 +1,12 @@
+-- MUAs a customer saved. event_date is the day they're looking for, if any; they're told
+-- once when the MUA opens up on it, and openings_notified_at resets when the date changes.
+CREATE TABLE IF NOT EXISTS favorites (
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    mua_id UUID NOT NULL REFERENCES mua_profiles(id) ON DELETE CASCADE,
+    event_date DATE,
+    openings_notified_at TIMESTAMP WITH TIME ZONE,
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    PRIMARY KEY (user_id, mua_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_favorites_mua_event_date ON favorites(mua_id, event_date) WHERE event_date IS NOT NULL;
@@ -0,0 +1,76 @@
+-- Full-text search over MUAs. The document spans the MUA's name, specialization, bio and
+-- portfolio, so it's kept on mua_profiles by triggers rather than as a generated column.
+-- The 'simple' config doesn't stem, which suits Indonesian better than any bundled dictionary,
+-- and unaccent lets "cafe" find "café".
+CREATE EXTENSION IF NOT EXISTS unaccent;
+
+-- unaccent() is only STABLE because its dictionary can change; pinning it makes it indexable
+CREATE OR REPLACE FUNCTION immutable_unaccent(value TEXT) RETURNS TEXT
+LANGUAGE sql IMMUTABLE PARALLEL SAFE STRICT AS $$
+    SELECT public.unaccent('public.unaccent'::regdictionary, value)
+$$;
+
+ALTER TABLE mua_profiles ADD COLUMN IF NOT EXISTS search_document TSVECTOR;
+
+-- Name and specialization weigh most, then bio, then portfolio titles and descriptions
+CREATE OR REPLACE FUNCTION mua_search_document(p_mua_id UUID, p_user_id UUID, p_bio TEXT, p_specialization TEXT[])
+RETURNS TSVECTOR LANGUAGE sql STABLE AS $$
+    SELECT setweight(to_tsvector('simple', immutable_unaccent(COALESCE((SELECT full_name FROM users WHERE id = p_user_id), ''))), 'A')
+        || setweight(to_tsvector('simple', immutable_unaccent(COALESCE(array_to_string(p_specialization, ' '), ''))), 'A')
+        || setweight(to_tsvector('simple', immutable_unaccent(COALESCE(p_bio, ''))), 'B')
+        || setweight(to_tsvector('simple', immutable_unaccent(COALESCE(
+               (SELECT string_agg(title, ' ') FROM portfolio_items WHERE mua_id = p_mua_id), ''))), 'C')
+        || setweight(to_tsvector('simple', immutable_unaccent(COALESCE(
+               (SELECT string_agg(description, ' ') FROM portfolio_items WHERE mua_id = p_mua_id), ''))), 'D')
+$$;
+
+CREATE OR REPLACE FUNCTION mua_profiles_search_document() RETURNS TRIGGER LANGUAGE plpgsql AS $$
+BEGIN
+    NEW.search_document := mua_search_document(NEW.id, NEW.user_id, NEW.bio, NEW.specialization);
+    RETURN NEW;
+END;
+$$;
+
+DROP TRIGGER IF EXISTS mua_profiles_search_document ON mua_profiles;
+CREATE TRIGGER mua_profiles_search_document
+    BEFORE INSERT OR UPDATE OF user_id, bio, specialization ON mua_profiles
+    FOR EACH ROW EXECUTE FUNCTION mua_profiles_search_document();
+
+CREATE OR REPLACE FUNCTION users_refresh_mua_search_document() RETURNS TRIGGER LANGUAGE plpgsql AS $$
+BEGIN
+    UPDATE mua_profiles
+    SET search_document = mua_search_document(id, user_id, bio, specialization)
+    WHERE user_id = NEW.id;
+    RETURN NULL;
+END;
+$$;
+
+DROP TRIGGER IF EXISTS users_refresh_mua_search_document ON users;
+CREATE TRIGGER users_refresh_mua_search_document
+    AFTER UPDATE OF full_name ON users
+    FOR EACH ROW EXECUTE FUNCTION users_refresh_mua_search_document();
+
+CREATE OR REPLACE FUNCTION portfolio_items_refresh_mua_search_document() RETURNS TRIGGER LANGUAGE plpgsql AS $$
+BEGIN
+    IF TG_OP <> 'INSERT' THEN
+        UPDATE mua_profiles
+        SET search_document = mua_search_document(id, user_id, bio, specialization)
+        WHERE id = OLD.mua_id;
+    END IF;
+    IF TG_OP <> 'DELETE' AND (TG_OP = 'INSERT' OR NEW.mua_id <> OLD.mua_id) THEN
+        UPDATE mua_profiles
+        SET search_document = mua_search_document(id, user_id, bio, specialization)
+        WHERE id = NEW.mua_id;
+    END IF;
+    RETURN NULL;
+END;
+$$;
+
+DROP TRIGGER IF EXISTS portfolio_items_refresh_mua_search_document ON portfolio_items;
+CREATE TRIGGER portfolio_items_refresh_mua_search_document
+    AFTER INSERT OR UPDATE OF mua_id, title, description OR DELETE ON portfolio_items
+    FOR EACH ROW EXECUTE FUNCTION portfolio_items_refresh_mua_search_document();
+
+UPDATE mua_profiles SET search_document = mua_search_document(id, user_id, bio, specialization);
+
+CREATE INDEX IF NOT EXISTS idx_mua_profiles_search_document ON mua_profiles USING GIN (search_document);
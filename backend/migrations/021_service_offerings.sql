@@ -0,0 +1,16 @@
+-- What a MUA offers and for how much, per person. Categories are free text such as
+-- "bridal" or "graduation" and are compared case-insensitively; search uses the cheapest
+-- offering as the MUA's "starting from" price.
+CREATE TABLE IF NOT EXISTS service_offerings (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    mua_id UUID NOT NULL REFERENCES mua_profiles(id) ON DELETE CASCADE,
+    category VARCHAR(100) NOT NULL,
+    name VARCHAR(255) NOT NULL,
+    description TEXT,
+    price DECIMAL(10, 2) NOT NULL CHECK (price >= 0),
+    duration_minutes INTEGER NOT NULL CHECK (duration_minutes > 0),
+    created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
+    updated_at TIMESTAMP WITH TIME ZONE DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_service_offerings_mua_category ON service_offerings(mua_id, lower(category), price);
@@ -0,0 +1,5 @@
+-- Messages are paged newest first by (created_at, id), so created_at can't be missing
+UPDATE messages SET created_at = NOW() WHERE created_at IS NULL;
+ALTER TABLE messages ALTER COLUMN created_at SET NOT NULL;
+
+CREATE INDEX IF NOT EXISTS idx_messages_booking_created ON messages(booking_id, created_at DESC, id DESC);
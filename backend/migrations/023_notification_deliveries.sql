@@ -0,0 +1,9 @@
+-- Which channel has reached which recipient for a notification job, so a retry after a
+-- partial failure only resends what didn't go out. Recipients are keyed by email address.
+CREATE TABLE IF NOT EXISTS notification_deliveries (
+    job_id UUID NOT NULL REFERENCES jobs(id) ON DELETE CASCADE,
+    channel VARCHAR(50) NOT NULL,
+    recipient VARCHAR(255) NOT NULL,
+    delivered_at TIMESTAMP WITH TIME ZONE NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (job_id, channel, recipient)
+);
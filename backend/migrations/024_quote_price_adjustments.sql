@@ -0,0 +1,10 @@
+-- Part of bookings.price that isn't the line items or the travel fee: what an accepted
+-- quote added (or took off, when negative) so the breakdown still adds up to the price
+ALTER TABLE bookings ADD COLUMN IF NOT EXISTS price_adjustment DECIMAL(10, 2) NOT NULL DEFAULT 0;
+
+-- Bookings confirmed through a quote before this column existed
+UPDATE bookings b
+SET price_adjustment = b.price - b.travel_fee - COALESCE(
+    (SELECT SUM(li.person_count * li.unit_price) FROM booking_line_items li WHERE li.booking_id = b.id), 0
+)
+WHERE EXISTS (SELECT 1 FROM booking_quotes q WHERE q.booking_id = b.id AND q.status = 'accepted');
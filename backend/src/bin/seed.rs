@@ -0,0 +1,652 @@
+use anyhow::Result;
+use chrono::{Datelike, Duration, NaiveDate, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use sqlx::{types::{BigDecimal, Json}, PgPool, Postgres, Transaction};
+use std::env;
+use uuid::Uuid;
+
+// The seed is a binary of its own, so it shares these model files rather than copying them
+#[allow(dead_code)]
+#[path = "../models/booking_status.rs"]
+mod booking_status;
+#[allow(dead_code)]
+#[path = "../models/cancellation.rs"]
+mod cancellation;
+#[allow(dead_code)]
+#[path = "../models/travel.rs"]
+mod travel;
+
+use booking_status::BookingStatus;
+use cancellation::{CancellationPolicy, CancellationTier};
+use travel::TravelPricing;
+
+// Every seeded account uses this email domain so a re-run can wipe the previous data set
+const SEED_EMAIL_DOMAIN: &str = "seed.muaconnect.dev";
+const SEED_PASSWORD: &str = "password123";
+// Bookings are generated around this date unless --anchor says otherwise, so a seed always gives the same rows
+const DEFAULT_ANCHOR: &str = "2026-01-15";
+
+// (city, latitude, longitude, IANA timezone)
+const CITIES: &[(&str, f64, f64, &str)] = &[
+    ("Jakarta", -6.2088, 106.8456, "Asia/Jakarta"),
+    ("Surabaya", -7.2575, 112.7521, "Asia/Jakarta"),
+    ("Bandung", -6.9175, 107.6191, "Asia/Jakarta"),
+    ("Medan", 3.5952, 98.6722, "Asia/Jakarta"),
+    ("Semarang", -6.9667, 110.4167, "Asia/Jakarta"),
+    ("Yogyakarta", -7.7956, 110.3695, "Asia/Jakarta"),
+    ("Palembang", -2.9761, 104.7754, "Asia/Jakarta"),
+    ("Denpasar", -8.6705, 115.2126, "Asia/Makassar"),
+    ("Makassar", -5.1477, 119.4327, "Asia/Makassar"),
+    ("Balikpapan", -1.2379, 116.8529, "Asia/Makassar"),
+    ("Manado", 1.4748, 124.8421, "Asia/Makassar"),
+    ("Jayapura", -2.5337, 140.7181, "Asia/Jayapura"),
+];
+
+const SPECIALIZATIONS: &[&str] = &[
+    "Bridal", "Wedding", "Party", "Graduation", "Photoshoot", "Editorial",
+    "Traditional", "Hijab", "SFX", "Natural",
+];
+
+// (service, base price in IDR, duration in hours)
+const SERVICES: &[(&str, i64, i32)] = &[
+    ("Bridal Makeup", 2_500_000, 4),
+    ("Engagement Makeup", 1_500_000, 3),
+    ("Party Makeup", 800_000, 2),
+    ("Graduation Makeup", 600_000, 2),
+    ("Photoshoot Makeup", 1_200_000, 3),
+    ("Traditional Wedding Makeup", 3_000_000, 5),
+];
+
+const FIRST_NAMES: &[&str] = &[
+    "Ayu", "Dewi", "Putri", "Rina", "Sari", "Indah", "Nadia", "Citra", "Maya", "Fitri",
+    "Intan", "Wulan", "Anisa", "Larasati", "Kartika", "Bunga", "Melati", "Ratna",
+];
+
+const LAST_NAMES: &[&str] = &[
+    "Pratiwi", "Santoso", "Wijaya", "Lestari", "Kusuma", "Hidayat", "Saputra", "Nugroho",
+    "Rahmawati", "Siregar", "Simanjuntak", "Halim", "Wibowo", "Purnama", "Setiawan",
+];
+
+const REVIEW_COMMENTS: &[&str] = &[
+    "Hasilnya cantik dan tahan lama, sangat direkomendasikan!",
+    "Tepat waktu dan sangat profesional.",
+    "Makeup flawless, semua tamu memuji.",
+    "Ramah dan mendengarkan keinginan saya.",
+    "Bagus, tapi datang sedikit terlambat.",
+];
+
+const STATUSES: &[BookingStatus] = &[
+    BookingStatus::Pending,
+    BookingStatus::Confirmed,
+    BookingStatus::Cancelled,
+    BookingStatus::Completed,
+    BookingStatus::NoShow,
+];
+
+// Extra people a customer can add to their booking: (service, price per person in IDR, minutes each)
+const EXTRA_PEOPLE_SERVICES: &[(&str, i64, i32)] = &[
+    ("Makeup Keluarga", 400_000, 45),
+    ("Makeup Bridesmaid", 500_000, 60),
+    ("Hair Do", 250_000, 30),
+];
+
+const CANCELLATION_REASONS: &[&str] = &[
+    "Jadwal acara berubah",
+    "Acara dibatalkan",
+    "Sudah menemukan MUA lain",
+];
+
+// Matches the defaults the server uses for BOOKING_RESPONSE_HOURS, BOOKING_COMPLETION_GRACE_HOURS and REVIEW_WINDOW_DAYS
+const RESPONSE_HOURS: i64 = 24;
+const COMPLETION_GRACE_HOURS: i64 = 24;
+const REVIEW_WINDOW_DAYS: i64 = 14;
+
+const EARTH_RADIUS_KM: f64 = 6371.0;
+
+struct SeedConfig {
+    seed: u64,
+    customers: usize,
+    muas: usize,
+    anchor: NaiveDate,
+}
+
+struct SeededCustomer {
+    id: Uuid,
+}
+
+struct SeededMua {
+    profile_id: Uuid,
+    user_id: Uuid,
+    city: usize,
+    latitude: f64,
+    longitude: f64,
+    travel_pricing: TravelPricing,
+    cancellation_policy: CancellationPolicy,
+}
+
+fn print_usage() {
+    println!("Usage: cargo run --bin seed -- [--seed N] [--customers N] [--muas N] [--anchor YYYY-MM-DD]");
+    println!();
+    println!("  --seed       RNG seed, the same value always produces the same data set (default 42)");
+    println!("  --customers  number of customer accounts to create (default 20)");
+    println!("  --muas       number of MUA accounts to create (default 10)");
+    println!("  --anchor     date that past/upcoming bookings are generated around (default {}),", DEFAULT_ANCHOR);
+    println!("               pass today's date to get bookings that are upcoming right now");
+    println!();
+    println!("All seeded accounts use *@{} with password '{}'.", SEED_EMAIL_DOMAIN, SEED_PASSWORD);
+    println!("Data from a previous run is removed before seeding.");
+}
+
+fn parse_args() -> Result<SeedConfig> {
+    let mut config = SeedConfig {
+        seed: 42,
+        customers: 20,
+        muas: 10,
+        anchor: NaiveDate::parse_from_str(DEFAULT_ANCHOR, "%Y-%m-%d")?,
+    };
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or_else(|| anyhow::anyhow!("Missing value for {}", arg));
+        match arg.as_str() {
+            "--seed" => config.seed = value()?.parse()?,
+            "--customers" => config.customers = value()?.parse()?,
+            "--muas" => config.muas = value()?.parse()?,
+            "--anchor" => config.anchor = NaiveDate::parse_from_str(&value()?, "%Y-%m-%d")?,
+            "--help" | "-h" => {
+                print_usage();
+                std::process::exit(0);
+            }
+            _ => return Err(anyhow::anyhow!("Unknown argument: {}", arg)),
+        }
+    }
+
+    if config.customers == 0 || config.muas == 0 {
+        return Err(anyhow::anyhow!("--customers and --muas must be at least 1"));
+    }
+
+    Ok(config)
+}
+
+fn next_uuid(rng: &mut ChaCha8Rng) -> Uuid {
+    uuid::Builder::from_random_bytes(rng.gen()).into_uuid()
+}
+
+fn pick<'a>(rng: &mut ChaCha8Rng, items: &'a [&'a str]) -> &'a str {
+    items.choose(rng).copied().unwrap_or_default()
+}
+
+fn phone_number(rng: &mut ChaCha8Rng) -> String {
+    format!("+628{}{:08}", rng.gen_range(11..=99), rng.gen_range(0..100_000_000))
+}
+
+/// Great-circle distance in km, the same measure bookings are charged travel by
+fn distance_km(from_latitude: f64, from_longitude: f64, to_latitude: f64, to_longitude: f64) -> f64 {
+    let delta_latitude = (to_latitude - from_latitude).to_radians();
+    let delta_longitude = (to_longitude - from_longitude).to_radians();
+    let a = (delta_latitude / 2.0).sin().powi(2)
+        + from_latitude.to_radians().cos() * to_latitude.to_radians().cos() * (delta_longitude / 2.0).sin().powi(2);
+
+    2.0 * EARTH_RADIUS_KM * a.sqrt().asin()
+}
+
+/// Half the MUAs keep the default policy, the rest are stricter or more lenient
+fn cancellation_policy(rng: &mut ChaCha8Rng) -> Option<CancellationPolicy> {
+    let tiers = match rng.gen_range(0..4) {
+        0 => vec![
+            CancellationTier { min_hours_before: 14 * 24, refund_percent: 100 },
+            CancellationTier { min_hours_before: 7 * 24, refund_percent: 25 },
+        ],
+        1 => vec![
+            CancellationTier { min_hours_before: 48, refund_percent: 100 },
+            CancellationTier { min_hours_before: 24, refund_percent: 50 },
+        ],
+        _ => return None,
+    };
+
+    Some(CancellationPolicy { tiers })
+}
+
+/// Most MUAs charge for travel past a free radius, some only work close to home
+fn travel_pricing(rng: &mut ChaCha8Rng) -> TravelPricing {
+    TravelPricing {
+        free_radius_km: f64::from(rng.gen_range(1..=3) * 5),
+        fee_per_km: BigDecimal::from(*[0, 2_500, 3_000, 5_000].choose(rng).unwrap_or(&0)),
+        max_distance_km: if rng.gen_bool(0.3) { Some(40.0) } else { None },
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    dotenvy::dotenv().ok();
+
+    let config = parse_args()?;
+
+    let database_url = env::var("DATABASE_URL")
+        .expect("DATABASE_URL must be set");
+
+    let pool = PgPool::connect(&database_url).await?;
+    let mut rng = ChaCha8Rng::seed_from_u64(config.seed);
+
+    println!(
+        "Seeding {} customers and {} MUAs (seed {}, anchor {})...",
+        config.customers, config.muas, config.seed, config.anchor
+    );
+
+    let mut tx = pool.begin().await?;
+
+    let removed = sqlx::query("DELETE FROM users WHERE email LIKE $1")
+        .bind(format!("%@{}", SEED_EMAIL_DOMAIN))
+        .execute(&mut tx)
+        .await?;
+    if removed.rows_affected() > 0 {
+        println!("Removed {} previously seeded users", removed.rows_affected());
+    }
+
+    // bcrypt salts are random by default, derive one from the seed so the hash is reproducible
+    let password_hash = bcrypt::hash_with_salt(SEED_PASSWORD, bcrypt::DEFAULT_COST, rng.gen())?
+        .format_for_version(bcrypt::Version::TwoB);
+
+    let customers = seed_customers(&mut tx, &mut rng, &config, &password_hash).await?;
+    let muas = seed_muas(&mut tx, &mut rng, &config, &password_hash).await?;
+    let booking_count = seed_bookings(&mut tx, &mut rng, &config, &customers, &muas).await?;
+
+    // Keep the denormalized rating columns consistent with the reviews we just inserted
+    sqlx::query(
+        r#"
+        UPDATE mua_profiles mp
+        SET average_rating = stats.average_rating,
+            total_reviews = stats.total_reviews
+        FROM (
+            SELECT mp.id,
+                   ROUND(AVG(r.rating)::numeric, 2) AS average_rating,
+                   COUNT(r.id)::int AS total_reviews
+            FROM mua_profiles mp
+            JOIN users u ON u.id = mp.user_id
+            LEFT JOIN reviews r ON r.reviewee_id = mp.user_id
+            WHERE u.email LIKE $1
+            GROUP BY mp.id
+        ) stats
+        WHERE mp.id = stats.id
+        "#
+    )
+    .bind(format!("%@{}", SEED_EMAIL_DOMAIN))
+    .execute(&mut tx)
+    .await?;
+
+    tx.commit().await?;
+
+    println!("Created {} customers, {} MUAs and {} bookings", customers.len(), muas.len(), booking_count);
+    println!("Log in with any *@{} account using password '{}'", SEED_EMAIL_DOMAIN, SEED_PASSWORD);
+    Ok(())
+}
+
+async fn seed_customers(
+    tx: &mut Transaction<'_, Postgres>,
+    rng: &mut ChaCha8Rng,
+    config: &SeedConfig,
+    password_hash: &str,
+) -> Result<Vec<SeededCustomer>> {
+    let mut customers = Vec::with_capacity(config.customers);
+
+    for i in 0..config.customers {
+        let id = next_uuid(rng);
+        let first = pick(rng, FIRST_NAMES);
+        let last = pick(rng, LAST_NAMES);
+        let phone = phone_number(rng);
+
+        sqlx::query(
+            r#"
+            INSERT INTO users (id, email, password_hash, user_type, full_name, phone_number, is_verified, created_at, updated_at)
+            VALUES ($1, $2, $3, 'customer', $4, $5, true, NOW(), NOW())
+            "#
+        )
+        .bind(id)
+        .bind(format!("customer{}.{}@{}", i + 1, first.to_lowercase(), SEED_EMAIL_DOMAIN))
+        .bind(password_hash)
+        .bind(format!("{} {}", first, last))
+        .bind(phone)
+        .execute(&mut *tx)
+        .await?;
+
+        customers.push(SeededCustomer { id });
+    }
+
+    Ok(customers)
+}
+
+async fn seed_muas(
+    tx: &mut Transaction<'_, Postgres>,
+    rng: &mut ChaCha8Rng,
+    config: &SeedConfig,
+    password_hash: &str,
+) -> Result<Vec<SeededMua>> {
+    let mut muas = Vec::with_capacity(config.muas);
+
+    for i in 0..config.muas {
+        let user_id = next_uuid(rng);
+        let profile_id = next_uuid(rng);
+        let first = pick(rng, FIRST_NAMES);
+        let last = pick(rng, LAST_NAMES);
+        let phone = phone_number(rng);
+        // Spread MUAs over every city before doubling up
+        let city = i % CITIES.len();
+        let (city_name, lat, lng, timezone) = CITIES[city];
+        let latitude = lat + rng.gen_range(-0.05..0.05);
+        let longitude = lng + rng.gen_range(-0.05..0.05);
+        let experience_years: i32 = rng.gen_range(1..=15);
+        let own_policy = cancellation_policy(rng);
+        let travel_pricing = travel_pricing(rng);
+
+        let specialization_count = rng.gen_range(1..=3);
+        let mut specialization: Vec<String> = SPECIALIZATIONS
+            .choose_multiple(rng, specialization_count)
+            .map(|s| s.to_string())
+            .collect();
+        specialization.sort();
+
+        let bio = format!(
+            "Makeup artist berbasis di {} dengan pengalaman {} tahun. Spesialis {}.",
+            city_name,
+            experience_years,
+            specialization.join(", ")
+        );
+
+        sqlx::query(
+            r#"
+            INSERT INTO users (id, email, password_hash, user_type, full_name, phone_number, profile_picture_url, is_verified, created_at, updated_at)
+            VALUES ($1, $2, $3, 'mua', $4, $5, $6, true, NOW(), NOW())
+            "#
+        )
+        .bind(user_id)
+        .bind(format!("mua{}.{}@{}", i + 1, first.to_lowercase(), SEED_EMAIL_DOMAIN))
+        .bind(password_hash)
+        .bind(format!("{} {} MUA", first, last))
+        .bind(phone)
+        .bind(format!("https://picsum.photos/seed/{}/400/400", user_id.simple()))
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO mua_profiles (
+                id, user_id, bio, experience_years, specialization, location, latitude, longitude, timezone,
+                cancellation_policy, travel_free_radius_km, travel_fee_per_km, travel_max_distance_km,
+                is_available, total_reviews, created_at, updated_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, true, 0, NOW(), NOW())
+            "#
+        )
+        .bind(profile_id)
+        .bind(user_id)
+        .bind(bio)
+        .bind(experience_years)
+        .bind(&specialization)
+        .bind(city_name)
+        .bind(latitude)
+        .bind(longitude)
+        .bind(timezone)
+        .bind(own_policy.as_ref().map(Json))
+        .bind(travel_pricing.free_radius_km)
+        .bind(&travel_pricing.fee_per_km)
+        .bind(travel_pricing.max_distance_km)
+        .execute(&mut *tx)
+        .await?;
+
+        for _ in 0..rng.gen_range(3..=6) {
+            let item_id = next_uuid(rng);
+            let (service, _, _) = SERVICES[rng.gen_range(0..SERVICES.len())];
+
+            sqlx::query(
+                r#"
+                INSERT INTO portfolio_items (id, mua_id, title, description, image_url, thumbnail_url, medium_url, service_type, created_at)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, NOW())
+                "#
+            )
+            .bind(item_id)
+            .bind(profile_id)
+            .bind(format!("{} di {}", service, city_name))
+            .bind(format!("Hasil {} untuk klien di {}", service.to_lowercase(), city_name))
+            .bind(format!("https://picsum.photos/seed/{}/1600/2000", item_id.simple()))
+            .bind(format!("https://picsum.photos/seed/{}/256/320", item_id.simple()))
+            .bind(format!("https://picsum.photos/seed/{}/768/960", item_id.simple()))
+            .bind(service)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        // Recurring weekly schedule, most MUAs take one or two days off
+        let mut days: Vec<i32> = (0..7).collect();
+        days.shuffle(rng);
+        days.truncate(rng.gen_range(5..=6));
+        days.sort();
+
+        let start_hour = rng.gen_range(5..=8);
+        let end_hour = rng.gen_range(17..=21);
+
+        for day in days {
+            sqlx::query(
+                r#"
+                INSERT INTO availability_slots (mua_id, start_time, end_time, day_of_week, is_available, recurring, created_at, updated_at)
+                VALUES ($1, $2, $3, $4, true, true, NOW(), NOW())
+                "#
+            )
+            .bind(profile_id)
+            .bind(NaiveTime::from_hms_opt(start_hour, 0, 0))
+            .bind(NaiveTime::from_hms_opt(end_hour, 0, 0))
+            .bind(day)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        muas.push(SeededMua {
+            profile_id,
+            user_id,
+            city,
+            latitude,
+            longitude,
+            travel_pricing,
+            // Bookings keep a copy of the policy they were made under, the default if the MUA has none
+            cancellation_policy: own_policy.unwrap_or_default(),
+        });
+    }
+
+    Ok(muas)
+}
+
+async fn seed_bookings(
+    tx: &mut Transaction<'_, Postgres>,
+    rng: &mut ChaCha8Rng,
+    config: &SeedConfig,
+    customers: &[SeededCustomer],
+    muas: &[SeededMua],
+) -> Result<usize> {
+    let mut booking_index = 0;
+
+    for mua in muas {
+        let (city_name, _, _, timezone) = CITIES[mua.city];
+        let timezone: Tz = timezone.parse()
+            .map_err(|_| anyhow::anyhow!("Invalid timezone {}", timezone))?;
+
+        for _ in 0..rng.gen_range(3..=8) {
+            // Walk through every status first so each one is represented even in tiny data sets
+            let status = if booking_index < STATUSES.len() {
+                STATUSES[booking_index]
+            } else {
+                *STATUSES.choose(rng).unwrap_or(&BookingStatus::Pending)
+            };
+            booking_index += 1;
+
+            let customer = &customers[rng.gen_range(0..customers.len())];
+            let (service, base_price, service_hours) = SERVICES[rng.gen_range(0..SERVICES.len())];
+
+            let day_offset = match status {
+                BookingStatus::Pending | BookingStatus::Confirmed => rng.gen_range(1..=60),
+                BookingStatus::Cancelled => rng.gen_range(-30..=30),
+                BookingStatus::Completed | BookingStatus::NoShow => -rng.gen_range(1..=90),
+            };
+            let event_day = config.anchor + Duration::days(day_offset);
+            let local_start = event_day
+                .and_hms_opt(rng.gen_range(6..=15), if rng.gen_bool(0.5) { 0 } else { 30 }, 0)
+                .ok_or_else(|| anyhow::anyhow!("Invalid event time"))?;
+            let event_date = timezone
+                .from_local_datetime(&local_start)
+                .single()
+                .ok_or_else(|| anyhow::anyhow!("Ambiguous event time"))?
+                .with_timezone(&Utc);
+
+            // The main service for the customer, sometimes with extra people; prices are
+            // rounded to the nearest 50k so they look like real quotes
+            let service_price = (base_price + rng.gen_range(-4..=8) * 50_000).max(300_000);
+            let mut line_items = vec![(service, 1, service_price, service_hours * 60)];
+            if rng.gen_bool(0.3) {
+                let (extra, price, minutes) = EXTRA_PEOPLE_SERVICES[rng.gen_range(0..EXTRA_PEOPLE_SERVICES.len())];
+                line_items.push((extra, rng.gen_range(1..=3), price, minutes));
+            }
+            let services_price: i64 = line_items.iter().map(|(_, people, price, _)| i64::from(*people) * price).sum();
+            let total_minutes: i32 = line_items.iter().map(|(_, people, _, minutes)| people * minutes).sum();
+            // Slots are booked in whole hours, the way the booking service rounds them
+            let duration_hours = (total_minutes + 59) / 60;
+            let service_type = line_items.iter().map(|(name, _, _, _)| *name).collect::<Vec<_>>().join(", ");
+
+            // Somewhere around the MUA's base, close enough for MUAs with a travel limit
+            let event_latitude = mua.latitude + rng.gen_range(-0.2..0.2);
+            let event_longitude = mua.longitude + rng.gen_range(-0.2..0.2);
+            let travel_distance_km = distance_km(mua.latitude, mua.longitude, event_latitude, event_longitude);
+            let travel_fee = mua.travel_pricing.fee_for(travel_distance_km)?;
+
+            let price = BigDecimal::from(services_price) + &travel_fee;
+            let deposit = (&price * BigDecimal::from(3) / BigDecimal::from(10)).round(0);
+            let final_payment_paid = status == BookingStatus::Completed;
+            let mut deposit_paid = matches!(status, BookingStatus::Confirmed | BookingStatus::Completed | BookingStatus::NoShow);
+            let mut created_at = event_date - Duration::days(rng.gen_range(3..=45));
+            let mut updated_at = created_at;
+
+            let mut response_deadline = None;
+            let (mut completed_at, mut review_window_closes_at) = (None, None);
+            let (mut cancellation_reason, mut refund_amount, mut refunded_at) = (None, None, None);
+            match status {
+                // Asked for shortly before the anchor, so the MUA still has time to answer
+                BookingStatus::Pending => {
+                    let anchor_noon = Utc.from_utc_datetime(&config.anchor.and_hms_opt(12, 0, 0).unwrap_or_default());
+                    created_at = anchor_noon - Duration::hours(rng.gen_range(1..=20));
+                    updated_at = created_at;
+                    response_deadline = Some((created_at + Duration::hours(RESPONSE_HOURS)).min(event_date));
+                }
+                BookingStatus::Completed => {
+                    let completed = event_date + Duration::hours(i64::from(duration_hours) + COMPLETION_GRACE_HOURS);
+                    completed_at = Some(completed);
+                    review_window_closes_at = Some(completed + Duration::days(REVIEW_WINDOW_DAYS));
+                    updated_at = completed;
+                }
+                // Cancelled by the customer, refunded by the booking's policy if the deposit was paid
+                BookingStatus::Cancelled => {
+                    let hours_before = rng.gen_range(12..=20 * 24);
+                    let cancelled_at = event_date - Duration::hours(hours_before);
+                    created_at = cancelled_at - Duration::days(rng.gen_range(1..=20));
+                    updated_at = cancelled_at;
+                    deposit_paid = rng.gen_bool(0.5);
+
+                    let amount_paid = if deposit_paid { deposit.clone() } else { BigDecimal::default() };
+                    let refund = mua.cancellation_policy.refund_amount(&amount_paid, hours_before);
+                    refunded_at = (refund > BigDecimal::default()).then(|| cancelled_at + Duration::hours(1));
+                    refund_amount = Some(refund);
+                    cancellation_reason = Some(pick(rng, CANCELLATION_REASONS));
+                }
+                BookingStatus::Confirmed | BookingStatus::NoShow => {}
+            }
+
+            let booking_id = next_uuid(rng);
+            sqlx::query(
+                r#"
+                INSERT INTO bookings (
+                    id, customer_id, mua_id, service_type, description, event_date,
+                    event_location, duration_hours, price, status,
+                    deposit_amount, deposit_paid, final_payment_paid,
+                    response_deadline, completed_at, review_window_closes_at,
+                    cancellation_policy, cancellation_reason, refund_amount, refund_reference, refunded_at,
+                    event_latitude, event_longitude, travel_distance_km, travel_fee,
+                    created_at, updated_at
+                )
+                VALUES (
+                    $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16,
+                    $17, $18, $19, $20, $21, $22, $23, $24, $25, $26, $27
+                )
+                "#
+            )
+            .bind(booking_id)
+            .bind(customer.id)
+            .bind(mua.profile_id)
+            .bind(&service_type)
+            .bind(format!("{} pada hari {}", service, event_day.weekday()))
+            .bind(event_date)
+            .bind(format!("{}, {}", pick(rng, &["Hotel Mulia", "Gedung Serbaguna", "Rumah Klien", "Studio Foto", "Ballroom Hotel"]), city_name))
+            .bind(duration_hours)
+            .bind(&price)
+            .bind(status)
+            .bind(&deposit)
+            .bind(deposit_paid)
+            .bind(final_payment_paid)
+            .bind(response_deadline)
+            .bind(completed_at)
+            .bind(review_window_closes_at)
+            .bind(Json(&mua.cancellation_policy))
+            .bind(cancellation_reason)
+            .bind(&refund_amount)
+            .bind(refunded_at.map(|_| format!("seed-refund-{}", booking_id.simple())))
+            .bind(refunded_at)
+            .bind(event_latitude)
+            .bind(event_longitude)
+            .bind(travel_distance_km)
+            .bind(&travel_fee)
+            .bind(created_at)
+            .bind(updated_at)
+            .execute(&mut *tx)
+            .await?;
+
+            for (position, (item_service, person_count, unit_price, minutes_per_person)) in line_items.iter().enumerate() {
+                sqlx::query(
+                    r#"
+                    INSERT INTO booking_line_items (booking_id, service_type, person_count, unit_price, minutes_per_person, position, created_at)
+                    VALUES ($1, $2, $3, $4, $5, $6, $7)
+                    "#
+                )
+                .bind(booking_id)
+                .bind(item_service)
+                .bind(person_count)
+                .bind(BigDecimal::from(*unit_price))
+                .bind(minutes_per_person)
+                .bind(position as i32)
+                .bind(created_at)
+                .execute(&mut *tx)
+                .await?;
+            }
+
+            // Most completed bookings are reviewed within their review window
+            if let Some(completed_at) = completed_at.filter(|_| rng.gen_bool(0.8)) {
+                let rating: i32 = *[3, 4, 4, 5, 5, 5].choose(rng).unwrap_or(&5);
+
+                sqlx::query(
+                    r#"
+                    INSERT INTO reviews (id, booking_id, reviewer_id, reviewee_id, rating, comment, created_at)
+                    VALUES ($1, $2, $3, $4, $5, $6, $7)
+                    "#
+                )
+                .bind(next_uuid(rng))
+                .bind(booking_id)
+                .bind(customer.id)
+                .bind(mua.user_id)
+                .bind(rating)
+                .bind(pick(rng, REVIEW_COMMENTS))
+                .bind(completed_at + Duration::hours(rng.gen_range(1..=7 * 24)))
+                .execute(&mut *tx)
+                .await?;
+            }
+        }
+    }
+
+    Ok(booking_index)
+}
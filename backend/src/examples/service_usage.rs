@@ -77,6 +77,12 @@ pub async fn example_custom_repositories(pool: &PgPool) -> anyhow::Result<()> {
         longitude: None,
         radius: None,
         date: None,
+        q: None,
+        min_price: None,
+        max_price: None,
+        service_category: None,
+        min_experience_years: None,
+        sort: None,
     };
 
     let mua_results = container.mua_service.search_muas(pool, search_params).await?;
@@ -105,6 +111,12 @@ mod test_examples {
             longitude: None,
             radius: None,
             date: None,
+            q: None,
+            min_price: None,
+            max_price: None,
+            service_category: None,
+            min_experience_years: None,
+            sort: None,
         };
 
         // This will work without a database because it uses mock services
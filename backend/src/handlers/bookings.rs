@@ -1,7 +1,7 @@
 use actix_web::{web, HttpResponse, Responder, HttpRequest};
 use serde_json::json;
-use crate::models::{CreateBookingRequest, UpdateBookingStatusRequest};
-use crate::services::booking_service;
+use crate::models::{BookingListQuery, CreateBookingRequest, CreateQuoteRequest, CreateRescheduleRequest, PageQuery, UpdateBookingStatusRequest};
+use crate::services::{booking_service, message_service, quote_service, reschedule_service};
 
 pub async fn create_booking(
     pool: web::Data<sqlx::PgPool>,
@@ -17,6 +17,8 @@ pub async fn create_booking(
         Err(e) => {
             let status = if e.to_string().contains("Unauthorized") {
                 actix_web::http::StatusCode::UNAUTHORIZED
+            } else if e.to_string().starts_with("Time slot unavailable") {
+                actix_web::http::StatusCode::CONFLICT
             } else {
                 actix_web::http::StatusCode::BAD_REQUEST
             };
@@ -27,19 +29,81 @@ pub async fn create_booking(
     }
 }
 
+/// The caller's bookings a page at a time, narrowed by `status`, `from`/`to`, `when` and `service_type`;
+/// pass `next_cursor` back as `cursor`, with the same filters and `sort`, for the next page
 pub async fn get_bookings(
     pool: web::Data<sqlx::PgPool>,
     req: HttpRequest,
+    query: web::Query<BookingListQuery>,
 ) -> impl Responder {
     let auth_header = req.headers().get("authorization")
         .and_then(|h| h.to_str().ok())
         .map(|s| s.to_string());
 
-    match booking_service::get_user_bookings(&pool, auth_header).await {
+    match booking_service::get_user_bookings(&pool, auth_header, query.into_inner()).await {
         Ok(bookings) => HttpResponse::Ok().json(bookings),
         Err(e) => {
             let status = if e.to_string().contains("Unauthorized") {
                 actix_web::http::StatusCode::UNAUTHORIZED
+            } else if e.to_string().starts_with("Invalid") {
+                actix_web::http::StatusCode::BAD_REQUEST
+            } else {
+                actix_web::http::StatusCode::INTERNAL_SERVER_ERROR
+            };
+            HttpResponse::build(status).json(json!({
+                "error": e.to_string()
+            }))
+        }
+    }
+}
+
+/// One booking for either of its participants, with who it's with and where it stands
+pub async fn get_booking(
+    pool: web::Data<sqlx::PgPool>,
+    req: HttpRequest,
+    path: web::Path<uuid::Uuid>,
+) -> impl Responder {
+    let auth_header = req.headers().get("authorization")
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.to_string());
+
+    match booking_service::get_booking_detail(&pool, auth_header, path.into_inner()).await {
+        Ok(booking) => HttpResponse::Ok().json(booking),
+        Err(e) => {
+            let status = if e.to_string().contains("Unauthorized") {
+                actix_web::http::StatusCode::UNAUTHORIZED
+            } else if e.to_string().contains("not found") {
+                actix_web::http::StatusCode::NOT_FOUND
+            } else {
+                actix_web::http::StatusCode::INTERNAL_SERVER_ERROR
+            };
+            HttpResponse::build(status).json(json!({
+                "error": e.to_string()
+            }))
+        }
+    }
+}
+
+/// The booking's chat, newest first; pass `next_cursor` back as `cursor` for older messages
+pub async fn get_messages(
+    pool: web::Data<sqlx::PgPool>,
+    req: HttpRequest,
+    path: web::Path<uuid::Uuid>,
+    query: web::Query<PageQuery>,
+) -> impl Responder {
+    let auth_header = req.headers().get("authorization")
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.to_string());
+
+    match message_service::get_messages(&pool, auth_header, path.into_inner(), query.into_inner()).await {
+        Ok(messages) => HttpResponse::Ok().json(messages),
+        Err(e) => {
+            let status = if e.to_string().contains("Unauthorized") {
+                actix_web::http::StatusCode::UNAUTHORIZED
+            } else if e.to_string().contains("not found") {
+                actix_web::http::StatusCode::NOT_FOUND
+            } else if e.to_string().starts_with("Invalid") {
+                actix_web::http::StatusCode::BAD_REQUEST
             } else {
                 actix_web::http::StatusCode::INTERNAL_SERVER_ERROR
             };
@@ -67,6 +131,9 @@ pub async fn update_booking_status(
             let status = match e.to_string().as_str() {
                 "Unauthorized" => actix_web::http::StatusCode::UNAUTHORIZED,
                 "Booking not found" => actix_web::http::StatusCode::NOT_FOUND,
+                message if message.starts_with("Forbidden") => actix_web::http::StatusCode::FORBIDDEN,
+                message if message.starts_with("Time slot unavailable") => actix_web::http::StatusCode::CONFLICT,
+                message if message.starts_with("Booking has changed") => actix_web::http::StatusCode::CONFLICT,
                 _ => actix_web::http::StatusCode::BAD_REQUEST,
             };
             HttpResponse::build(status).json(json!({
@@ -74,4 +141,133 @@ pub async fn update_booking_status(
             }))
         }
     }
-}
\ No newline at end of file
+}
+
+/// Shared by the reschedule and quote endpoints, which fail in the same ways
+fn negotiation_error_response(e: anyhow::Error) -> HttpResponse {
+    let message = e.to_string();
+    let status = if message.starts_with("Unauthorized") {
+        actix_web::http::StatusCode::UNAUTHORIZED
+    } else if message.contains("not found") {
+        actix_web::http::StatusCode::NOT_FOUND
+    } else if message.contains("already") || message.contains("unavailable") || message.contains("has changed") || message.contains("still waiting") {
+        actix_web::http::StatusCode::CONFLICT
+    } else {
+        actix_web::http::StatusCode::BAD_REQUEST
+    };
+    HttpResponse::build(status).json(json!({
+        "error": message
+    }))
+}
+
+pub async fn create_reschedule_request(
+    pool: web::Data<sqlx::PgPool>,
+    req: HttpRequest,
+    path: web::Path<uuid::Uuid>,
+    request_data: web::Json<CreateRescheduleRequest>,
+) -> impl Responder {
+    let auth_header = req.headers().get("authorization")
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.to_string());
+
+    match reschedule_service::create_reschedule_request(&pool, auth_header, path.into_inner(), request_data.into_inner()).await {
+        Ok(reschedule) => HttpResponse::Created().json(reschedule),
+        Err(e) => negotiation_error_response(e),
+    }
+}
+
+pub async fn get_reschedule_requests(
+    pool: web::Data<sqlx::PgPool>,
+    req: HttpRequest,
+    path: web::Path<uuid::Uuid>,
+    query: web::Query<PageQuery>,
+) -> impl Responder {
+    let auth_header = req.headers().get("authorization")
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.to_string());
+
+    match reschedule_service::get_reschedule_requests(&pool, auth_header, path.into_inner(), query.into_inner()).await {
+        Ok(requests) => HttpResponse::Ok().json(requests),
+        Err(e) => negotiation_error_response(e),
+    }
+}
+
+pub async fn accept_reschedule_request(
+    pool: web::Data<sqlx::PgPool>,
+    req: HttpRequest,
+    path: web::Path<(uuid::Uuid, uuid::Uuid)>,
+) -> impl Responder {
+    let (booking_id, reschedule_request_id) = path.into_inner();
+    let auth_header = req.headers().get("authorization")
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.to_string());
+
+    match reschedule_service::accept_reschedule_request(&pool, auth_header, booking_id, reschedule_request_id).await {
+        Ok(reschedule) => HttpResponse::Ok().json(reschedule),
+        Err(e) => negotiation_error_response(e),
+    }
+}
+
+pub async fn decline_reschedule_request(
+    pool: web::Data<sqlx::PgPool>,
+    req: HttpRequest,
+    path: web::Path<(uuid::Uuid, uuid::Uuid)>,
+) -> impl Responder {
+    let (booking_id, reschedule_request_id) = path.into_inner();
+    let auth_header = req.headers().get("authorization")
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.to_string());
+
+    match reschedule_service::decline_reschedule_request(&pool, auth_header, booking_id, reschedule_request_id).await {
+        Ok(reschedule) => HttpResponse::Ok().json(reschedule),
+        Err(e) => negotiation_error_response(e),
+    }
+}
+
+pub async fn create_quote(
+    pool: web::Data<sqlx::PgPool>,
+    req: HttpRequest,
+    path: web::Path<uuid::Uuid>,
+    quote_data: web::Json<CreateQuoteRequest>,
+) -> impl Responder {
+    let auth_header = req.headers().get("authorization")
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.to_string());
+
+    match quote_service::create_quote(&pool, auth_header, path.into_inner(), quote_data.into_inner()).await {
+        Ok(quote) => HttpResponse::Created().json(quote),
+        Err(e) => negotiation_error_response(e),
+    }
+}
+
+pub async fn get_quotes(
+    pool: web::Data<sqlx::PgPool>,
+    req: HttpRequest,
+    path: web::Path<uuid::Uuid>,
+    query: web::Query<PageQuery>,
+) -> impl Responder {
+    let auth_header = req.headers().get("authorization")
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.to_string());
+
+    match quote_service::get_quotes(&pool, auth_header, path.into_inner(), query.into_inner()).await {
+        Ok(quotes) => HttpResponse::Ok().json(quotes),
+        Err(e) => negotiation_error_response(e),
+    }
+}
+
+pub async fn accept_quote(
+    pool: web::Data<sqlx::PgPool>,
+    req: HttpRequest,
+    path: web::Path<(uuid::Uuid, uuid::Uuid)>,
+) -> impl Responder {
+    let (booking_id, quote_id) = path.into_inner();
+    let auth_header = req.headers().get("authorization")
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.to_string());
+
+    match quote_service::accept_quote(&pool, auth_header, booking_id, quote_id).await {
+        Ok(quote) => HttpResponse::Ok().json(quote),
+        Err(e) => negotiation_error_response(e),
+    }
+}
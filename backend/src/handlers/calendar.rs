@@ -0,0 +1,197 @@
+use actix_web::{web, HttpResponse, Responder, HttpRequest};
+use serde::Deserialize;
+use serde_json::json;
+use crate::models::{AddExternalCalendarRequest, PageQuery, UploadExternalCalendarQuery};
+use crate::services::calendar_feed;
+use crate::services::calendar_import;
+use crate::services::user_service;
+
+#[derive(Debug, Deserialize)]
+pub struct CalendarFeedQuery {
+    #[serde(default)]
+    pub include_pending: bool,
+}
+
+/// The ICS feed calendar apps subscribe to; the token in the path is the only credential
+pub async fn get_calendar_feed_ics(
+    pool: web::Data<sqlx::PgPool>,
+    path: web::Path<String>,
+    query: web::Query<CalendarFeedQuery>,
+) -> impl Responder {
+    let token = path.into_inner();
+
+    match calendar_feed::render_calendar_feed(&pool, &token, query.include_pending).await {
+        Ok(calendar) => HttpResponse::Ok()
+            .content_type("text/calendar; charset=utf-8")
+            .insert_header(("Cache-Control", "no-cache"))
+            .body(calendar),
+        Err(e) => {
+            let status = if e.to_string().contains("not found") {
+                actix_web::http::StatusCode::NOT_FOUND
+            } else {
+                actix_web::http::StatusCode::INTERNAL_SERVER_ERROR
+            };
+            HttpResponse::build(status).json(json!({
+                "error": e.to_string()
+            }))
+        }
+    }
+}
+
+pub async fn get_calendar_feed(
+    pool: web::Data<sqlx::PgPool>,
+    req: HttpRequest,
+) -> impl Responder {
+    let auth_header = req.headers().get("authorization")
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.to_string());
+
+    let result = match user_service::get_user_id_from_auth_header(auth_header) {
+        Ok(user_id) => calendar_feed::get_calendar_feed(&pool, user_id).await,
+        Err(e) => Err(e),
+    };
+
+    calendar_feed_response(result)
+}
+
+pub async fn rotate_calendar_feed(
+    pool: web::Data<sqlx::PgPool>,
+    req: HttpRequest,
+) -> impl Responder {
+    let auth_header = req.headers().get("authorization")
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.to_string());
+
+    let result = match user_service::get_user_id_from_auth_header(auth_header) {
+        Ok(user_id) => calendar_feed::rotate_calendar_feed(&pool, user_id).await,
+        Err(e) => Err(e),
+    };
+
+    calendar_feed_response(result)
+}
+
+fn calendar_feed_response(result: anyhow::Result<calendar_feed::CalendarFeed>) -> HttpResponse {
+    match result {
+        Ok(feed) => HttpResponse::Ok().json(feed),
+        Err(e) => {
+            let status = if e.to_string().contains("Unauthorized") {
+                actix_web::http::StatusCode::UNAUTHORIZED
+            } else if e.to_string().contains("not found") {
+                actix_web::http::StatusCode::NOT_FOUND
+            } else {
+                actix_web::http::StatusCode::INTERNAL_SERVER_ERROR
+            };
+            HttpResponse::build(status).json(json!({
+                "error": e.to_string()
+            }))
+        }
+    }
+}
+
+pub async fn get_external_calendars(
+    pool: web::Data<sqlx::PgPool>,
+    req: HttpRequest,
+    query: web::Query<PageQuery>,
+) -> impl Responder {
+    let auth_header = req.headers().get("authorization")
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.to_string());
+
+    match user_service::get_user_id_from_auth_header(auth_header) {
+        Ok(user_id) => match calendar_import::get_external_calendars(&pool, user_id, query.into_inner()).await {
+            Ok(calendars) => HttpResponse::Ok().json(calendars),
+            Err(e) => external_calendar_error_response(e),
+        },
+        Err(e) => external_calendar_error_response(e),
+    }
+}
+
+pub async fn add_external_calendar(
+    pool: web::Data<sqlx::PgPool>,
+    req: HttpRequest,
+    calendar_data: web::Json<AddExternalCalendarRequest>,
+) -> impl Responder {
+    let auth_header = req.headers().get("authorization")
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.to_string());
+
+    match user_service::get_user_id_from_auth_header(auth_header) {
+        Ok(user_id) => match calendar_import::add_external_calendar(&pool, user_id, calendar_data.into_inner()).await {
+            Ok(calendar) => HttpResponse::Created().json(calendar),
+            Err(e) => external_calendar_error_response(e),
+        },
+        Err(e) => external_calendar_error_response(e),
+    }
+}
+
+/// Takes the raw .ics file as the request body
+pub async fn upload_external_calendar(
+    pool: web::Data<sqlx::PgPool>,
+    req: HttpRequest,
+    query: web::Query<UploadExternalCalendarQuery>,
+    body: web::Bytes,
+) -> impl Responder {
+    let auth_header = req.headers().get("authorization")
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.to_string());
+
+    match user_service::get_user_id_from_auth_header(auth_header) {
+        Ok(user_id) => match calendar_import::upload_external_calendar(&pool, user_id, query.into_inner().name, &body).await {
+            Ok(calendar) => HttpResponse::Created().json(calendar),
+            Err(e) => external_calendar_error_response(e),
+        },
+        Err(e) => external_calendar_error_response(e),
+    }
+}
+
+pub async fn sync_external_calendar(
+    pool: web::Data<sqlx::PgPool>,
+    req: HttpRequest,
+    calendar_id: web::Path<uuid::Uuid>,
+) -> impl Responder {
+    let auth_header = req.headers().get("authorization")
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.to_string());
+
+    match user_service::get_user_id_from_auth_header(auth_header) {
+        Ok(user_id) => match calendar_import::sync_external_calendar(&pool, user_id, calendar_id.into_inner()).await {
+            Ok(calendar) => HttpResponse::Ok().json(calendar),
+            Err(e) => external_calendar_error_response(e),
+        },
+        Err(e) => external_calendar_error_response(e),
+    }
+}
+
+pub async fn delete_external_calendar(
+    pool: web::Data<sqlx::PgPool>,
+    req: HttpRequest,
+    calendar_id: web::Path<uuid::Uuid>,
+) -> impl Responder {
+    let auth_header = req.headers().get("authorization")
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.to_string());
+
+    match user_service::get_user_id_from_auth_header(auth_header) {
+        Ok(user_id) => match calendar_import::delete_external_calendar(&pool, user_id, calendar_id.into_inner()).await {
+            Ok(()) => HttpResponse::NoContent().finish(),
+            Err(e) => external_calendar_error_response(e),
+        },
+        Err(e) => external_calendar_error_response(e),
+    }
+}
+
+fn external_calendar_error_response(e: anyhow::Error) -> HttpResponse {
+    let status = if e.to_string().contains("Unauthorized") {
+        actix_web::http::StatusCode::UNAUTHORIZED
+    } else if e.to_string().contains("not found") {
+        actix_web::http::StatusCode::NOT_FOUND
+    } else if e.to_string().starts_with("Invalid") {
+        actix_web::http::StatusCode::BAD_REQUEST
+    } else {
+        // The calendar server couldn't be reached or sent something unusable
+        actix_web::http::StatusCode::BAD_GATEWAY
+    };
+    HttpResponse::build(status).json(json!({
+        "error": e.to_string()
+    }))
+}
@@ -1,8 +1,8 @@
 use actix_web::{web, HttpResponse, Responder, HttpRequest};
 use serde_json::json;
 use crate::models::dashboard::{DashboardResponse, UpdateAvailabilityRequest};
-use crate::models::{UpdateMuaAvailabilityRequest, PortfolioItem, CreatePortfolioRequest, UpdatePortfolioRequest};
-use crate::models::availability::CreateAvailabilityRequest;
+use crate::models::{UpdateMuaAvailabilityRequest, PageNumberQuery, PortfolioItem, CreatePortfolioRequest, UpdatePortfolioRequest, UpdateCancellationPolicyRequest, UpdateTravelPricingRequest, UpdateTimezoneRequest};
+use crate::models::availability::{CreateAvailabilityRequest, UpdateBookingBuffersRequest, UpdateWeeklyScheduleRequest};
 use crate::services::dashboard_service;
 use crate::services::user_service;
 use crate::services::booking_service;
@@ -67,9 +67,294 @@ pub async fn update_availability(
     }
 }
 
+pub async fn get_cancellation_policy(
+    pool: web::Data<sqlx::PgPool>,
+    req: HttpRequest,
+) -> impl Responder {
+    let auth_header = req.headers().get("authorization")
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.to_string());
+
+    let user_repository = crate::repository::UserRepositoryImpl::new();
+    let mua_repository = crate::repository::MuaRepositoryImpl::new();
+    let booking_repository = crate::repository::BookingRepositoryImpl::new();
+    let dashboard_service = crate::services::dashboard_service::DashboardServiceImpl::new(
+        Box::new(user_repository),
+        Box::new(mua_repository),
+        Box::new(booking_repository)
+    );
+
+    match dashboard_service.get_cancellation_policy(&pool, auth_header).await {
+        Ok(policy) => HttpResponse::Ok().json(policy),
+        Err(e) => {
+            let status = if e.to_string().contains("Unauthorized") {
+                actix_web::http::StatusCode::UNAUTHORIZED
+            } else if e.to_string().contains("not found") {
+                actix_web::http::StatusCode::NOT_FOUND
+            } else {
+                actix_web::http::StatusCode::INTERNAL_SERVER_ERROR
+            };
+            HttpResponse::build(status).json(json!({
+                "error": e.to_string()
+            }))
+        }
+    }
+}
+
+pub async fn update_cancellation_policy(
+    pool: web::Data<sqlx::PgPool>,
+    req: HttpRequest,
+    policy_data: web::Json<UpdateCancellationPolicyRequest>,
+) -> impl Responder {
+    let auth_header = req.headers().get("authorization")
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.to_string());
+
+    let user_repository = crate::repository::UserRepositoryImpl::new();
+    let mua_repository = crate::repository::MuaRepositoryImpl::new();
+    let booking_repository = crate::repository::BookingRepositoryImpl::new();
+    let dashboard_service = crate::services::dashboard_service::DashboardServiceImpl::new(
+        Box::new(user_repository),
+        Box::new(mua_repository),
+        Box::new(booking_repository)
+    );
+
+    match dashboard_service.update_cancellation_policy(&pool, auth_header, policy_data.into_inner()).await {
+        Ok(policy) => HttpResponse::Ok().json(policy),
+        Err(e) => {
+            let status = if e.to_string().contains("Unauthorized") {
+                actix_web::http::StatusCode::UNAUTHORIZED
+            } else if e.to_string().contains("not found") {
+                actix_web::http::StatusCode::NOT_FOUND
+            } else if e.to_string().contains("Invalid") {
+                actix_web::http::StatusCode::BAD_REQUEST
+            } else {
+                actix_web::http::StatusCode::INTERNAL_SERVER_ERROR
+            };
+            HttpResponse::build(status).json(json!({
+                "error": e.to_string()
+            }))
+        }
+    }
+}
+
+pub async fn get_travel_pricing(
+    pool: web::Data<sqlx::PgPool>,
+    req: HttpRequest,
+) -> impl Responder {
+    let auth_header = req.headers().get("authorization")
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.to_string());
+
+    let user_repository = crate::repository::UserRepositoryImpl::new();
+    let mua_repository = crate::repository::MuaRepositoryImpl::new();
+    let booking_repository = crate::repository::BookingRepositoryImpl::new();
+    let dashboard_service = crate::services::dashboard_service::DashboardServiceImpl::new(
+        Box::new(user_repository),
+        Box::new(mua_repository),
+        Box::new(booking_repository)
+    );
+
+    match dashboard_service.get_travel_pricing(&pool, auth_header).await {
+        Ok(pricing) => HttpResponse::Ok().json(pricing),
+        Err(e) => {
+            let status = if e.to_string().contains("Unauthorized") {
+                actix_web::http::StatusCode::UNAUTHORIZED
+            } else if e.to_string().contains("not found") {
+                actix_web::http::StatusCode::NOT_FOUND
+            } else {
+                actix_web::http::StatusCode::INTERNAL_SERVER_ERROR
+            };
+            HttpResponse::build(status).json(json!({
+                "error": e.to_string()
+            }))
+        }
+    }
+}
+
+pub async fn update_travel_pricing(
+    pool: web::Data<sqlx::PgPool>,
+    req: HttpRequest,
+    pricing_data: web::Json<UpdateTravelPricingRequest>,
+) -> impl Responder {
+    let auth_header = req.headers().get("authorization")
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.to_string());
+
+    let user_repository = crate::repository::UserRepositoryImpl::new();
+    let mua_repository = crate::repository::MuaRepositoryImpl::new();
+    let booking_repository = crate::repository::BookingRepositoryImpl::new();
+    let dashboard_service = crate::services::dashboard_service::DashboardServiceImpl::new(
+        Box::new(user_repository),
+        Box::new(mua_repository),
+        Box::new(booking_repository)
+    );
+
+    match dashboard_service.update_travel_pricing(&pool, auth_header, pricing_data.into_inner()).await {
+        Ok(pricing) => HttpResponse::Ok().json(pricing),
+        Err(e) => {
+            let status = if e.to_string().contains("Unauthorized") {
+                actix_web::http::StatusCode::UNAUTHORIZED
+            } else if e.to_string().contains("not found") {
+                actix_web::http::StatusCode::NOT_FOUND
+            } else if e.to_string().contains("Invalid") {
+                actix_web::http::StatusCode::BAD_REQUEST
+            } else {
+                actix_web::http::StatusCode::INTERNAL_SERVER_ERROR
+            };
+            HttpResponse::build(status).json(json!({
+                "error": e.to_string()
+            }))
+        }
+    }
+}
+
+pub async fn get_timezone(
+    pool: web::Data<sqlx::PgPool>,
+    req: HttpRequest,
+) -> impl Responder {
+    let auth_header = req.headers().get("authorization")
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.to_string());
+
+    let user_repository = crate::repository::UserRepositoryImpl::new();
+    let mua_repository = crate::repository::MuaRepositoryImpl::new();
+    let booking_repository = crate::repository::BookingRepositoryImpl::new();
+    let dashboard_service = crate::services::dashboard_service::DashboardServiceImpl::new(
+        Box::new(user_repository),
+        Box::new(mua_repository),
+        Box::new(booking_repository)
+    );
+
+    match dashboard_service.get_timezone(&pool, auth_header).await {
+        Ok(timezone) => HttpResponse::Ok().json(timezone),
+        Err(e) => {
+            let status = if e.to_string().contains("Unauthorized") {
+                actix_web::http::StatusCode::UNAUTHORIZED
+            } else if e.to_string().contains("not found") {
+                actix_web::http::StatusCode::NOT_FOUND
+            } else {
+                actix_web::http::StatusCode::INTERNAL_SERVER_ERROR
+            };
+            HttpResponse::build(status).json(json!({
+                "error": e.to_string()
+            }))
+        }
+    }
+}
+
+pub async fn update_timezone(
+    pool: web::Data<sqlx::PgPool>,
+    req: HttpRequest,
+    timezone_data: web::Json<UpdateTimezoneRequest>,
+) -> impl Responder {
+    let auth_header = req.headers().get("authorization")
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.to_string());
+
+    let user_repository = crate::repository::UserRepositoryImpl::new();
+    let mua_repository = crate::repository::MuaRepositoryImpl::new();
+    let booking_repository = crate::repository::BookingRepositoryImpl::new();
+    let dashboard_service = crate::services::dashboard_service::DashboardServiceImpl::new(
+        Box::new(user_repository),
+        Box::new(mua_repository),
+        Box::new(booking_repository)
+    );
+
+    match dashboard_service.update_timezone(&pool, auth_header, timezone_data.into_inner()).await {
+        Ok(timezone) => HttpResponse::Ok().json(timezone),
+        Err(e) => {
+            let status = if e.to_string().contains("Unauthorized") {
+                actix_web::http::StatusCode::UNAUTHORIZED
+            } else if e.to_string().contains("not found") {
+                actix_web::http::StatusCode::NOT_FOUND
+            } else if e.to_string().contains("Invalid") {
+                actix_web::http::StatusCode::BAD_REQUEST
+            } else {
+                actix_web::http::StatusCode::INTERNAL_SERVER_ERROR
+            };
+            HttpResponse::build(status).json(json!({
+                "error": e.to_string()
+            }))
+        }
+    }
+}
+
+pub async fn get_booking_buffers(
+    pool: web::Data<sqlx::PgPool>,
+    req: HttpRequest,
+) -> impl Responder {
+    let auth_header = req.headers().get("authorization")
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.to_string());
+
+    let user_repository = crate::repository::UserRepositoryImpl::new();
+    let mua_repository = crate::repository::MuaRepositoryImpl::new();
+    let booking_repository = crate::repository::BookingRepositoryImpl::new();
+    let dashboard_service = crate::services::dashboard_service::DashboardServiceImpl::new(
+        Box::new(user_repository),
+        Box::new(mua_repository),
+        Box::new(booking_repository)
+    );
+
+    match dashboard_service.get_booking_buffers(&pool, auth_header).await {
+        Ok(buffers) => HttpResponse::Ok().json(buffers),
+        Err(e) => {
+            let status = if e.to_string().contains("Unauthorized") {
+                actix_web::http::StatusCode::UNAUTHORIZED
+            } else if e.to_string().contains("not found") {
+                actix_web::http::StatusCode::NOT_FOUND
+            } else {
+                actix_web::http::StatusCode::INTERNAL_SERVER_ERROR
+            };
+            HttpResponse::build(status).json(json!({
+                "error": e.to_string()
+            }))
+        }
+    }
+}
+
+pub async fn update_booking_buffers(
+    pool: web::Data<sqlx::PgPool>,
+    req: HttpRequest,
+    buffers_data: web::Json<UpdateBookingBuffersRequest>,
+) -> impl Responder {
+    let auth_header = req.headers().get("authorization")
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.to_string());
+
+    let user_repository = crate::repository::UserRepositoryImpl::new();
+    let mua_repository = crate::repository::MuaRepositoryImpl::new();
+    let booking_repository = crate::repository::BookingRepositoryImpl::new();
+    let dashboard_service = crate::services::dashboard_service::DashboardServiceImpl::new(
+        Box::new(user_repository),
+        Box::new(mua_repository),
+        Box::new(booking_repository)
+    );
+
+    match dashboard_service.update_booking_buffers(&pool, auth_header, buffers_data.into_inner()).await {
+        Ok(buffers) => HttpResponse::Ok().json(buffers),
+        Err(e) => {
+            let status = if e.to_string().contains("Unauthorized") {
+                actix_web::http::StatusCode::UNAUTHORIZED
+            } else if e.to_string().contains("not found") {
+                actix_web::http::StatusCode::NOT_FOUND
+            } else if e.to_string().contains("Invalid") {
+                actix_web::http::StatusCode::BAD_REQUEST
+            } else {
+                actix_web::http::StatusCode::INTERNAL_SERVER_ERROR
+            };
+            HttpResponse::build(status).json(json!({
+                "error": e.to_string()
+            }))
+        }
+    }
+}
+
 pub async fn get_portfolio_items(
     pool: web::Data<sqlx::PgPool>,
     req: HttpRequest,
+    query: web::Query<PageNumberQuery>,
 ) -> impl Responder {
     let auth_header = req.headers().get("authorization")
         .and_then(|h| h.to_str().ok())
@@ -84,7 +369,7 @@ pub async fn get_portfolio_items(
         Box::new(booking_repository)
     );
 
-    match dashboard_service.get_portfolio_items(&pool, auth_header).await {
+    match dashboard_service.get_portfolio_items(&pool, auth_header, query.into_inner()).await {
         Ok(items) => HttpResponse::Ok().json(items),
         Err(e) => {
             let status = if e.to_string().contains("Unauthorized") {
@@ -276,6 +561,77 @@ pub async fn create_availability_slot(
     }
 }
 
+pub async fn get_weekly_schedule(
+    pool: web::Data<sqlx::PgPool>,
+    req: HttpRequest,
+) -> impl Responder {
+    let auth_header = req.headers().get("authorization")
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.to_string());
+
+    let user_repository = crate::repository::UserRepositoryImpl::new();
+    let mua_repository = crate::repository::MuaRepositoryImpl::new();
+    let booking_repository = crate::repository::BookingRepositoryImpl::new();
+    let dashboard_service = crate::services::dashboard_service::DashboardServiceImpl::new(
+        Box::new(user_repository),
+        Box::new(mua_repository),
+        Box::new(booking_repository)
+    );
+
+    match dashboard_service.get_weekly_schedule(&pool, auth_header).await {
+        Ok(schedule) => HttpResponse::Ok().json(schedule),
+        Err(e) => {
+            let status = if e.to_string().contains("Unauthorized") {
+                actix_web::http::StatusCode::UNAUTHORIZED
+            } else if e.to_string().contains("not found") {
+                actix_web::http::StatusCode::NOT_FOUND
+            } else {
+                actix_web::http::StatusCode::INTERNAL_SERVER_ERROR
+            };
+            HttpResponse::build(status).json(json!({
+                "error": e.to_string()
+            }))
+        }
+    }
+}
+
+pub async fn replace_weekly_schedule(
+    pool: web::Data<sqlx::PgPool>,
+    req: HttpRequest,
+    schedule_data: web::Json<UpdateWeeklyScheduleRequest>,
+) -> impl Responder {
+    let auth_header = req.headers().get("authorization")
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.to_string());
+
+    let user_repository = crate::repository::UserRepositoryImpl::new();
+    let mua_repository = crate::repository::MuaRepositoryImpl::new();
+    let booking_repository = crate::repository::BookingRepositoryImpl::new();
+    let dashboard_service = crate::services::dashboard_service::DashboardServiceImpl::new(
+        Box::new(user_repository),
+        Box::new(mua_repository),
+        Box::new(booking_repository)
+    );
+
+    match dashboard_service.replace_weekly_schedule(&pool, auth_header, schedule_data.into_inner()).await {
+        Ok(schedule) => HttpResponse::Ok().json(schedule),
+        Err(e) => {
+            let status = if e.to_string().contains("Unauthorized") {
+                actix_web::http::StatusCode::UNAUTHORIZED
+            } else if e.to_string().contains("not found") {
+                actix_web::http::StatusCode::NOT_FOUND
+            } else if e.to_string().contains("Invalid") {
+                actix_web::http::StatusCode::BAD_REQUEST
+            } else {
+                actix_web::http::StatusCode::INTERNAL_SERVER_ERROR
+            };
+            HttpResponse::build(status).json(json!({
+                "error": e.to_string()
+            }))
+        }
+    }
+}
+
 pub async fn update_availability_slot(
     _pool: web::Data<sqlx::PgPool>,
     _req: HttpRequest,
@@ -369,6 +725,9 @@ pub async fn update_booking_status_calendar(
                 }));
             }
         },
+        reason: status_data.get("reason")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string()),
     };
 
     match booking_service::update_booking_status(&pool, auth_header, booking_id.into_inner(), status_request).await {
@@ -376,10 +735,14 @@ pub async fn update_booking_status_calendar(
         Err(e) => {
             let status = if e.to_string().contains("Unauthorized") {
                 actix_web::http::StatusCode::UNAUTHORIZED
+            } else if e.to_string().starts_with("Forbidden") {
+                actix_web::http::StatusCode::FORBIDDEN
             } else if e.to_string().contains("not found") {
                 actix_web::http::StatusCode::NOT_FOUND
             } else if e.to_string().contains("Invalid status") {
                 actix_web::http::StatusCode::BAD_REQUEST
+            } else if e.to_string().starts_with("Time slot unavailable") || e.to_string().starts_with("Booking has changed") {
+                actix_web::http::StatusCode::CONFLICT
             } else {
                 actix_web::http::StatusCode::INTERNAL_SERVER_ERROR
             };
@@ -0,0 +1,77 @@
+use actix_web::{web, HttpResponse, Responder, HttpRequest};
+use serde_json::json;
+use crate::models::{PageQuery, SaveFavoriteRequest};
+use crate::services::favorite_service;
+use crate::services::user_service;
+
+/// The body is optional; an `event_date` asks to be told when the MUA has openings that day
+pub async fn save_favorite(
+    pool: web::Data<sqlx::PgPool>,
+    req: HttpRequest,
+    mua_id: web::Path<uuid::Uuid>,
+    favorite_data: Option<web::Json<SaveFavoriteRequest>>,
+) -> impl Responder {
+    let auth_header = req.headers().get("authorization")
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.to_string());
+    let favorite_data = favorite_data.map(|data| data.into_inner()).unwrap_or_default();
+
+    match user_service::get_user_id_from_auth_header(auth_header) {
+        Ok(user_id) => match favorite_service::save_favorite(&pool, user_id, mua_id.into_inner(), favorite_data).await {
+            Ok(favorite) => HttpResponse::Ok().json(favorite),
+            Err(e) => favorite_error_response(e),
+        },
+        Err(e) => favorite_error_response(e),
+    }
+}
+
+pub async fn remove_favorite(
+    pool: web::Data<sqlx::PgPool>,
+    req: HttpRequest,
+    mua_id: web::Path<uuid::Uuid>,
+) -> impl Responder {
+    let auth_header = req.headers().get("authorization")
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.to_string());
+
+    match user_service::get_user_id_from_auth_header(auth_header) {
+        Ok(user_id) => match favorite_service::remove_favorite(&pool, user_id, mua_id.into_inner()).await {
+            Ok(()) => HttpResponse::NoContent().finish(),
+            Err(e) => favorite_error_response(e),
+        },
+        Err(e) => favorite_error_response(e),
+    }
+}
+
+pub async fn get_favorites(
+    pool: web::Data<sqlx::PgPool>,
+    req: HttpRequest,
+    query: web::Query<PageQuery>,
+) -> impl Responder {
+    let auth_header = req.headers().get("authorization")
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.to_string());
+
+    match user_service::get_user_id_from_auth_header(auth_header) {
+        Ok(user_id) => match favorite_service::get_favorites(&pool, user_id, query.into_inner()).await {
+            Ok(favorites) => HttpResponse::Ok().json(favorites),
+            Err(e) => favorite_error_response(e),
+        },
+        Err(e) => favorite_error_response(e),
+    }
+}
+
+fn favorite_error_response(e: anyhow::Error) -> HttpResponse {
+    let status = if e.to_string().contains("Unauthorized") {
+        actix_web::http::StatusCode::UNAUTHORIZED
+    } else if e.to_string().contains("not found") {
+        actix_web::http::StatusCode::NOT_FOUND
+    } else if e.to_string().starts_with("Invalid") {
+        actix_web::http::StatusCode::BAD_REQUEST
+    } else {
+        actix_web::http::StatusCode::INTERNAL_SERVER_ERROR
+    };
+    HttpResponse::build(status).json(json!({
+        "error": e.to_string()
+    }))
+}
@@ -2,4 +2,10 @@ pub mod auth;
 pub mod users;
 pub mod muas;
 pub mod bookings;
-pub mod dashboard;
\ No newline at end of file
+pub mod reviews;
+pub mod dashboard;
+pub mod storage;
+pub mod calendar;
+pub mod time_off;
+pub mod favorites;
+pub mod offerings;
\ No newline at end of file
@@ -1,16 +1,29 @@
 use actix_web::{web, HttpResponse, Responder, HttpRequest};
 use serde_json::json;
-use crate::models::{SearchMuasRequest, MuaProfileResponse, CreateMuaProfileRequest};
+use std::time::Duration;
+use crate::models::{SearchMuasRequest, MuaProfileResponse, CreateMuaProfileRequest, BookableSlotsQuery, Page, PageNumberQuery};
 use crate::services::mua_service;
+use crate::services::availability_service;
 use crate::services::user_service;
-use crate::services::s3_service;
+use crate::services::object_storage;
+use crate::services::upload_session;
+use crate::services::favorite_service;
 
+/// Signed-in searchers also get `is_favorite` on each result
 pub async fn get_muas(
     pool: web::Data<sqlx::PgPool>,
+    req: HttpRequest,
     query: web::Query<SearchMuasRequest>,
 ) -> impl Responder {
+    let user_id = optional_user_id(&req);
+
     match mua_service::search_muas(&pool, query.into_inner()).await {
-        Ok(muas) => HttpResponse::Ok().json(muas),
+        Ok(mut page) => match favorite_service::mark_favorites(&pool, user_id, &mut page.items).await {
+            Ok(()) => HttpResponse::Ok().json(page),
+            Err(e) => HttpResponse::InternalServerError().json(json!({
+                "error": e.to_string()
+            }))
+        },
         Err(e) => HttpResponse::InternalServerError().json(json!({
             "error": e.to_string()
         }))
@@ -19,18 +32,72 @@ pub async fn get_muas(
 
 pub async fn get_mua_by_id(
     pool: web::Data<sqlx::PgPool>,
+    req: HttpRequest,
     path: web::Path<uuid::Uuid>,
 ) -> impl Responder {
     let mua_id = path.into_inner();
+    let user_id = optional_user_id(&req);
 
     match mua_service::get_mua_by_id(&pool, mua_id).await {
-        Ok(mua) => HttpResponse::Ok().json(mua),
+        Ok(mua) => {
+            let mut muas = [mua];
+            match favorite_service::mark_favorites(&pool, user_id, &mut muas).await {
+                Ok(()) => {
+                    let [mua] = muas;
+                    HttpResponse::Ok().json(mua)
+                }
+                Err(e) => HttpResponse::InternalServerError().json(json!({
+                    "error": e.to_string()
+                }))
+            }
+        }
         Err(_) => HttpResponse::NotFound().json(json!({
             "error": "MUA not found"
         }))
     }
 }
 
+/// Public endpoints work without a token; a valid one just personalises the response
+fn optional_user_id(req: &HttpRequest) -> Option<uuid::Uuid> {
+    let auth_header = req.headers().get("authorization")
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.to_string());
+
+    user_service::get_user_id_from_auth_header(auth_header).ok()
+}
+
+pub async fn get_bookable_slots(
+    pool: web::Data<sqlx::PgPool>,
+    path: web::Path<uuid::Uuid>,
+    query: web::Query<BookableSlotsQuery>,
+) -> impl Responder {
+    let mua_id = path.into_inner();
+    let query = query.into_inner();
+
+    let date = match chrono::NaiveDate::parse_from_str(&query.date, "%Y-%m-%d") {
+        Ok(date) => date,
+        Err(_) => return HttpResponse::BadRequest().json(json!({
+            "error": "Invalid date format, expected YYYY-MM-DD"
+        })),
+    };
+
+    match availability_service::bookable_slots(&pool, mua_id, date, query.duration_hours, query.latitude.zip(query.longitude)).await {
+        Ok(slots) => HttpResponse::Ok().json(slots),
+        Err(e) => {
+            let status = if e.to_string().contains("not found") {
+                actix_web::http::StatusCode::NOT_FOUND
+            } else if e.to_string().starts_with("Invalid") {
+                actix_web::http::StatusCode::BAD_REQUEST
+            } else {
+                actix_web::http::StatusCode::INTERNAL_SERVER_ERROR
+            };
+            HttpResponse::build(status).json(json!({
+                "error": e.to_string()
+            }))
+        }
+    }
+}
+
 pub async fn create_profile(
     pool: web::Data<sqlx::PgPool>,
     req: HttpRequest,
@@ -90,17 +157,34 @@ pub async fn create_portfolio(
 pub async fn get_mua_portfolio(
     pool: web::Data<sqlx::PgPool>,
     path: web::Path<uuid::Uuid>,
+    query: web::Query<PageNumberQuery>,
 ) -> impl Responder {
     let mua_id = path.into_inner();
+    let page = query.page();
+    let limit = query.limit();
+
+    let total_count = match sqlx::query!(
+        "SELECT COUNT(*) as count FROM portfolio_items WHERE mua_id = $1",
+        mua_id
+    )
+    .fetch_one(&**pool)
+    .await
+    {
+        Ok(count) => count.count.unwrap_or(0),
+        Err(_) => 0,
+    };
 
     match sqlx::query!(
         r#"
-        SELECT id, title, description, image_url, service_type, created_at
+        SELECT id, title, description, image_url, thumbnail_url, medium_url, service_type, created_at
         FROM portfolio_items
         WHERE mua_id = $1
-        ORDER BY created_at DESC
+        ORDER BY created_at DESC, id DESC
+        LIMIT $2 OFFSET $3
         "#,
-        mua_id
+        mua_id,
+        limit,
+        query.offset()
     )
     .fetch_all(&**pool)
     .await
@@ -112,12 +196,14 @@ pub async fn get_mua_portfolio(
                     "title": item.title,
                     "description": item.description,
                     "image_url": item.image_url,
+                    "thumbnail_url": item.thumbnail_url,
+                    "medium_url": item.medium_url,
                     "service_type": item.service_type,
                     "created_at": item.created_at
                 })
             }).collect();
 
-            HttpResponse::Ok().json(portfolio_items)
+            HttpResponse::Ok().json(Page::numbered(portfolio_items, total_count, page, limit))
         }
         Err(e) => {
             HttpResponse::InternalServerError().json(json!({
@@ -127,16 +213,11 @@ pub async fn get_mua_portfolio(
     }
 }
 
-#[derive(serde::Deserialize)]
-pub struct GetPortfolioQuery {
-    page: Option<i32>,
-    limit: Option<i32>,
-}
 
 pub async fn get_current_mua_portfolio(
     pool: web::Data<sqlx::PgPool>,
     req: HttpRequest,
-    query: web::Query<GetPortfolioQuery>,
+    query: web::Query<PageNumberQuery>,
 ) -> impl Responder {
     let auth_header = req.headers().get("authorization")
         .and_then(|h| h.to_str().ok())
@@ -153,9 +234,9 @@ pub async fn get_current_mua_portfolio(
     };
 
     // Get pagination parameters
-    let page = query.page.unwrap_or(1).max(1);
-    let limit = query.limit.unwrap_or(10).max(1).min(100);
-    let offset = (page - 1) * limit;
+    let page = query.page();
+    let limit = query.limit();
+    let offset = query.offset();
 
     // First get MUA profile for this user
     let mua_id = match sqlx::query!(
@@ -188,15 +269,15 @@ pub async fn get_current_mua_portfolio(
     // Get portfolio items with pagination
     match sqlx::query!(
         r#"
-        SELECT id, title, description, image_url, service_type, created_at
+        SELECT id, title, description, image_url, thumbnail_url, medium_url, service_type, created_at
         FROM portfolio_items
         WHERE mua_id = $1
-        ORDER BY created_at DESC
+        ORDER BY created_at DESC, id DESC
         LIMIT $2 OFFSET $3
         "#,
         mua_id,
-        limit as i32,
-        offset as i32
+        limit,
+        offset
     )
     .fetch_all(&**pool)
     .await
@@ -208,24 +289,14 @@ pub async fn get_current_mua_portfolio(
                     "title": item.title,
                     "description": item.description,
                     "image_url": item.image_url,
+                    "thumbnail_url": item.thumbnail_url,
+                    "medium_url": item.medium_url,
                     "service_type": item.service_type,
                     "created_at": item.created_at
                 })
             }).collect();
 
-            let total_pages = (total_count as f64 / limit as f64).ceil() as i32;
-
-            HttpResponse::Ok().json(json!({
-                "data": portfolio_items,
-                "pagination": {
-                    "current_page": page,
-                    "per_page": limit,
-                    "total_items": total_count,
-                    "total_pages": total_pages,
-                    "has_next_page": page < total_pages,
-                    "has_prev_page": page > 1
-                }
-            }))
+            HttpResponse::Ok().json(Page::numbered(portfolio_items, total_count, page, limit))
         }
         Err(e) => {
             HttpResponse::InternalServerError().json(json!({
@@ -237,7 +308,6 @@ pub async fn get_current_mua_portfolio(
 
 #[derive(serde::Deserialize)]
 pub struct PresignedUrlRequest {
-    file_name: String,
     content_type: String,
     folder: Option<String>,
 }
@@ -250,6 +320,7 @@ pub struct PresignedUrlResponse {
 }
 
 pub async fn get_presigned_upload_url(
+    pool: web::Data<sqlx::PgPool>,
     req: HttpRequest,
     body: web::Json<PresignedUrlRequest>,
 ) -> impl Responder {
@@ -258,16 +329,14 @@ pub async fn get_presigned_upload_url(
         .map(|s| s.to_string());
 
     // Validate authentication
-    match user_service::get_user_id_from_auth_header(auth_header) {
-        Ok(_) => {
-            // User is authenticated, proceed with presigned URL generation
-        }
+    let user_id = match user_service::get_user_id_from_auth_header(auth_header) {
+        Ok(user_id) => user_id,
         Err(e) => {
             return HttpResponse::Unauthorized().json(json!({
                 "error": e.to_string()
             }));
         }
-    }
+    };
 
     // Validate content type (only allow images)
     if !body.content_type.starts_with("image/") {
@@ -279,24 +348,38 @@ pub async fn get_presigned_upload_url(
     // Use provided folder or default to "uploads"
     let folder = body.folder.as_deref().unwrap_or("uploads");
 
-    // Initialize S3 service
-    let s3_service = match s3_service::S3Service::new().await {
-        Ok(service) => service,
+    // Initialize storage backend
+    let storage = match object_storage::from_env().await {
+        Ok(storage) => storage,
         Err(e) => {
-            eprintln!("Failed to initialize S3 service: {}", e);
+            eprintln!("Failed to initialize storage backend: {}", e);
             return HttpResponse::InternalServerError().json(json!({
                 "error": "Failed to initialize upload service"
             }));
         }
     };
 
+    // Uploads live under the owner's own folder so they can't be attached by someone else
+    let key = match object_storage::generate_object_key(&format!("{}/{}", folder, user_id), &body.content_type) {
+        Ok(key) => key,
+        Err(e) => {
+            return HttpResponse::BadRequest().json(json!({
+                "error": e.to_string()
+            }));
+        }
+    };
+
     // Generate presigned URL
-    match s3_service.get_presigned_upload_url(
-        &body.file_name,
-        &body.content_type,
-        folder,
-    ).await {
-        Ok((presigned_url, public_url)) => {
+    match storage.presign_upload(&key, &body.content_type, Duration::from_secs(3600)).await {
+        Ok(presigned_url) => {
+            if let Err(e) = upload_session::create_upload_session(&pool, user_id, &key, &body.content_type).await {
+                eprintln!("Failed to record upload session: {}", e);
+                return HttpResponse::InternalServerError().json(json!({
+                    "error": "Failed to generate upload URL"
+                }));
+            }
+
+            let public_url = storage.public_url(&key);
             println!("DEBUG: Returning presigned URL: {}", presigned_url);
             println!("DEBUG: Returning public URL: {}", public_url);
             HttpResponse::Ok().json(PresignedUrlResponse {
@@ -312,39 +395,4 @@ pub async fn get_presigned_upload_url(
             }))
         }
     }
-}
-
-pub async fn debug_presigned_url() -> impl Responder {
-    // Debug endpoint to test S3 service without authentication
-    match s3_service::S3Service::new().await {
-        Ok(service) => {
-            match service.get_presigned_upload_url(
-                "test.jpg",
-                "image/jpeg",
-                "portfolio"
-            ).await {
-                Ok((presigned_url, public_url)) => {
-                    println!("DEBUG: Presigned URL: {}", presigned_url);
-                    println!("DEBUG: Public URL: {}", public_url);
-                    HttpResponse::Ok().json(serde_json::json!({
-                        "presigned_url": presigned_url,
-                        "public_url": public_url,
-                        "message": "Debug URLs generated successfully"
-                    }))
-                }
-                Err(e) => {
-                    eprintln!("DEBUG: Failed to generate presigned URL: {}", e);
-                    HttpResponse::InternalServerError().json(serde_json::json!({
-                        "error": format!("Failed to generate presigned URL: {}", e)
-                    }))
-                }
-            }
-        }
-        Err(e) => {
-            eprintln!("DEBUG: Failed to initialize S3 service: {}", e);
-            HttpResponse::InternalServerError().json(serde_json::json!({
-                "error": format!("Failed to initialize S3 service: {}", e)
-            }))
-        }
-    }
 }
\ No newline at end of file
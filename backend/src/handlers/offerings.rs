@@ -0,0 +1,105 @@
+use actix_web::{web, HttpResponse, Responder, HttpRequest};
+use serde_json::json;
+use crate::models::{PageNumberQuery, SaveServiceOfferingRequest};
+use crate::services::offering_service;
+use crate::services::user_service;
+
+/// Public list of what a MUA offers
+pub async fn get_mua_offerings(
+    pool: web::Data<sqlx::PgPool>,
+    mua_id: web::Path<uuid::Uuid>,
+    query: web::Query<PageNumberQuery>,
+) -> impl Responder {
+    match offering_service::offerings_for_mua(&pool, mua_id.into_inner(), query.into_inner()).await {
+        Ok(offerings) => HttpResponse::Ok().json(offerings),
+        Err(e) => offering_error_response(e),
+    }
+}
+
+pub async fn get_offerings(
+    pool: web::Data<sqlx::PgPool>,
+    req: HttpRequest,
+    query: web::Query<PageNumberQuery>,
+) -> impl Responder {
+    let auth_header = req.headers().get("authorization")
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.to_string());
+
+    match user_service::get_user_id_from_auth_header(auth_header) {
+        Ok(user_id) => match offering_service::get_offerings(&pool, user_id, query.into_inner()).await {
+            Ok(offerings) => HttpResponse::Ok().json(offerings),
+            Err(e) => offering_error_response(e),
+        },
+        Err(e) => offering_error_response(e),
+    }
+}
+
+pub async fn create_offering(
+    pool: web::Data<sqlx::PgPool>,
+    req: HttpRequest,
+    offering_data: web::Json<SaveServiceOfferingRequest>,
+) -> impl Responder {
+    let auth_header = req.headers().get("authorization")
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.to_string());
+
+    match user_service::get_user_id_from_auth_header(auth_header) {
+        Ok(user_id) => match offering_service::create_offering(&pool, user_id, offering_data.into_inner()).await {
+            Ok(offering) => HttpResponse::Created().json(offering),
+            Err(e) => offering_error_response(e),
+        },
+        Err(e) => offering_error_response(e),
+    }
+}
+
+pub async fn update_offering(
+    pool: web::Data<sqlx::PgPool>,
+    req: HttpRequest,
+    offering_id: web::Path<uuid::Uuid>,
+    offering_data: web::Json<SaveServiceOfferingRequest>,
+) -> impl Responder {
+    let auth_header = req.headers().get("authorization")
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.to_string());
+
+    match user_service::get_user_id_from_auth_header(auth_header) {
+        Ok(user_id) => match offering_service::update_offering(&pool, user_id, offering_id.into_inner(), offering_data.into_inner()).await {
+            Ok(offering) => HttpResponse::Ok().json(offering),
+            Err(e) => offering_error_response(e),
+        },
+        Err(e) => offering_error_response(e),
+    }
+}
+
+pub async fn delete_offering(
+    pool: web::Data<sqlx::PgPool>,
+    req: HttpRequest,
+    offering_id: web::Path<uuid::Uuid>,
+) -> impl Responder {
+    let auth_header = req.headers().get("authorization")
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.to_string());
+
+    match user_service::get_user_id_from_auth_header(auth_header) {
+        Ok(user_id) => match offering_service::delete_offering(&pool, user_id, offering_id.into_inner()).await {
+            Ok(()) => HttpResponse::NoContent().finish(),
+            Err(e) => offering_error_response(e),
+        },
+        Err(e) => offering_error_response(e),
+    }
+}
+
+fn offering_error_response(e: anyhow::Error) -> HttpResponse {
+    let status = if e.to_string().contains("Unauthorized") {
+        actix_web::http::StatusCode::UNAUTHORIZED
+    } else if e.to_string().contains("not found") {
+        actix_web::http::StatusCode::NOT_FOUND
+    } else if e.to_string().starts_with("Invalid") {
+        actix_web::http::StatusCode::BAD_REQUEST
+    } else {
+        actix_web::http::StatusCode::INTERNAL_SERVER_ERROR
+    };
+    HttpResponse::build(status).json(json!({
+        "error": e.to_string()
+    }))
+}
@@ -0,0 +1,29 @@
+use actix_web::{web, HttpResponse, Responder, HttpRequest};
+use serde_json::json;
+use crate::models::CreateReviewRequest;
+use crate::services::review_service;
+
+pub async fn create_review(
+    pool: web::Data<sqlx::PgPool>,
+    req: HttpRequest,
+    review_data: web::Json<CreateReviewRequest>,
+) -> impl Responder {
+    let auth_header = req.headers().get("authorization")
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.to_string());
+
+    match review_service::create_review(&pool, auth_header, review_data.into_inner()).await {
+        Ok(review) => HttpResponse::Created().json(review),
+        Err(e) => {
+            let status = match e.to_string().as_str() {
+                "Unauthorized" => actix_web::http::StatusCode::UNAUTHORIZED,
+                "Booking not found" => actix_web::http::StatusCode::NOT_FOUND,
+                "You have already reviewed this booking" => actix_web::http::StatusCode::CONFLICT,
+                _ => actix_web::http::StatusCode::BAD_REQUEST,
+            };
+            HttpResponse::build(status).json(json!({
+                "error": e.to_string()
+            }))
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::services::local_storage::LocalStorage;
+use crate::services::object_storage::ObjectStorage;
+
+#[derive(Deserialize)]
+pub struct UploadQuery {
+    pub expires: i64,
+    pub content_type: String,
+    pub signature: String,
+}
+
+/// Receives uploads for presigned URLs issued by the local storage backend
+pub async fn upload_object(
+    req: HttpRequest,
+    path: web::Path<String>,
+    query: web::Query<UploadQuery>,
+    body: web::Bytes,
+) -> impl Responder {
+    let key = path.into_inner();
+    let storage = match LocalStorage::from_env() {
+        Ok(storage) => storage,
+        Err(e) => {
+            eprintln!("Failed to initialize local storage: {}", e);
+            return HttpResponse::InternalServerError().json(json!({
+                "error": "Failed to initialize upload service"
+            }));
+        }
+    };
+
+    if let Err(e) = storage.verify_upload(&key, &query.content_type, query.expires, &query.signature) {
+        return HttpResponse::Forbidden().json(json!({
+            "error": e.to_string()
+        }));
+    }
+
+    // The signature covers the content type, so the client must send the same one
+    let content_type = req.headers()
+        .get("content-type")
+        .and_then(|h| h.to_str().ok())
+        .unwrap_or("");
+
+    if content_type != query.content_type {
+        return HttpResponse::BadRequest().json(json!({
+            "error": "Content-Type does not match the presigned URL"
+        }));
+    }
+
+    match storage.put_object(&key, body, content_type).await {
+        Ok(_) => HttpResponse::Ok().finish(),
+        Err(e) => {
+            eprintln!("Failed to store upload {}: {}", key, e);
+            HttpResponse::InternalServerError().json(json!({
+                "error": "Failed to store upload"
+            }))
+        }
+    }
+}
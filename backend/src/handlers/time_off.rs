@@ -0,0 +1,75 @@
+use actix_web::{web, HttpResponse, Responder, HttpRequest};
+use serde_json::json;
+use crate::models::{CreateTimeOffRequest, PageNumberQuery};
+use crate::services::time_off_service;
+use crate::services::user_service;
+
+pub async fn get_time_off(
+    pool: web::Data<sqlx::PgPool>,
+    req: HttpRequest,
+    query: web::Query<PageNumberQuery>,
+) -> impl Responder {
+    let auth_header = req.headers().get("authorization")
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.to_string());
+
+    match user_service::get_user_id_from_auth_header(auth_header) {
+        Ok(user_id) => match time_off_service::get_time_off(&pool, user_id, query.into_inner()).await {
+            Ok(time_off) => HttpResponse::Ok().json(time_off),
+            Err(e) => time_off_error_response(e),
+        },
+        Err(e) => time_off_error_response(e),
+    }
+}
+
+/// Responds with the new time off plus any confirmed bookings that fall inside it
+pub async fn create_time_off(
+    pool: web::Data<sqlx::PgPool>,
+    req: HttpRequest,
+    time_off_data: web::Json<CreateTimeOffRequest>,
+) -> impl Responder {
+    let auth_header = req.headers().get("authorization")
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.to_string());
+
+    match user_service::get_user_id_from_auth_header(auth_header) {
+        Ok(user_id) => match time_off_service::create_time_off(&pool, user_id, time_off_data.into_inner()).await {
+            Ok(created) => HttpResponse::Created().json(created),
+            Err(e) => time_off_error_response(e),
+        },
+        Err(e) => time_off_error_response(e),
+    }
+}
+
+pub async fn delete_time_off(
+    pool: web::Data<sqlx::PgPool>,
+    req: HttpRequest,
+    time_off_id: web::Path<uuid::Uuid>,
+) -> impl Responder {
+    let auth_header = req.headers().get("authorization")
+        .and_then(|h| h.to_str().ok())
+        .map(|s| s.to_string());
+
+    match user_service::get_user_id_from_auth_header(auth_header) {
+        Ok(user_id) => match time_off_service::delete_time_off(&pool, user_id, time_off_id.into_inner()).await {
+            Ok(()) => HttpResponse::NoContent().finish(),
+            Err(e) => time_off_error_response(e),
+        },
+        Err(e) => time_off_error_response(e),
+    }
+}
+
+fn time_off_error_response(e: anyhow::Error) -> HttpResponse {
+    let status = if e.to_string().contains("Unauthorized") {
+        actix_web::http::StatusCode::UNAUTHORIZED
+    } else if e.to_string().contains("not found") {
+        actix_web::http::StatusCode::NOT_FOUND
+    } else if e.to_string().starts_with("Invalid") {
+        actix_web::http::StatusCode::BAD_REQUEST
+    } else {
+        actix_web::http::StatusCode::INTERNAL_SERVER_ERROR
+    };
+    HttpResponse::build(status).json(json!({
+        "error": e.to_string()
+    }))
+}
@@ -41,6 +41,12 @@ pub async fn update_profile(
             let status = match e.to_string().as_str() {
                 "Unauthorized" => actix_web::http::StatusCode::UNAUTHORIZED,
                 "Invalid input" => actix_web::http::StatusCode::BAD_REQUEST,
+                // A profile picture that wasn't a finished upload of a real image
+                message if message.starts_with("Invalid")
+                    || message.contains("upload")
+                    || message.contains("Upload")
+                    || message.starts_with("Failed to read image")
+                    || message.starts_with("Failed to decode image") => actix_web::http::StatusCode::BAD_REQUEST,
                 _ => actix_web::http::StatusCode::INTERNAL_SERVER_ERROR,
             };
             HttpResponse::build(status).json(json!({
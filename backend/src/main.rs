@@ -1,4 +1,4 @@
-use actix_web::{web, App, HttpServer, middleware::Logger};
+use actix_web::{guard, web, App, HttpServer, middleware::Logger};
 use actix_cors::Cors;
 use dotenvy::dotenv;
 use sqlx::PgPool;
@@ -9,6 +9,11 @@ mod models;
 mod services;
 mod repository;
 mod utils;
+mod workers;
+
+use services::calendar_import::MAX_CALENDAR_SIZE;
+use services::object_storage::{self, StorageBackend, MAX_IMAGE_SIZE};
+use services::local_storage::LocalStorage;
 
 async fn run_migrations(pool: &PgPool) -> Result<(), sqlx::Error> {
     // Check if table already exists
@@ -34,7 +39,7 @@ async fn run_migrations(pool: &PgPool) -> Result<(), sqlx::Error> {
             start_time TIME NOT NULL,
             end_time TIME NOT NULL,
             day_of_week INTEGER CHECK (day_of_week >= 0 AND day_of_week <= 6),
-            specific_date TIMESTAMP WITH TIME ZONE,
+            specific_date DATE,
             is_available BOOLEAN NOT NULL DEFAULT true,
             recurring BOOLEAN NOT NULL DEFAULT false,
             created_at TIMESTAMP WITH TIME ZONE DEFAULT NOW(),
@@ -211,6 +216,24 @@ async fn main() -> std::io::Result<()> {
     // Run migrations
     run_migrations(&pool).await.expect("Failed to run migrations");
 
+    // Background jobs
+    workers::upload_sweeper::spawn(pool.clone());
+    workers::storage_deletions::spawn(pool.clone());
+    workers::job_runner::spawn(pool.clone());
+    workers::booking_sweeper::spawn(pool.clone());
+    workers::calendar_importer::spawn(pool.clone());
+
+    let storage_backend = object_storage::storage_backend();
+    let local_storage_root = if storage_backend == StorageBackend::Local {
+        // Refuse to start rather than sign uploads with a guessable secret
+        let local_storage = LocalStorage::from_env().expect("Invalid local storage configuration");
+        std::fs::create_dir_all(local_storage.root())?;
+        println!("📦 Using local storage at {}", local_storage.root().display());
+        Some(local_storage.root().to_path_buf())
+    } else {
+        None
+    };
+
     HttpServer::new(move || {
         let cors = Cors::default()
             .allowed_origin("http://localhost:5173")
@@ -220,10 +243,24 @@ async fn main() -> std::io::Result<()> {
             .allowed_headers(vec!["authorization", "content-type"])
             .max_age(3600);
 
-        App::new()
+        let mut app = App::new()
             .app_data(web::Data::new(pool.clone()))
             .wrap(cors)
-            .wrap(Logger::default())
+            .wrap(Logger::default());
+
+        // Local storage backend serves its own files and accepts presigned uploads
+        if let Some(local_storage_root) = &local_storage_root {
+            app = app
+                .service(
+                    web::resource("/storage/{key:.*}")
+                        .guard(guard::Put())
+                        .app_data(web::PayloadConfig::new(MAX_IMAGE_SIZE))
+                        .route(web::put().to(handlers::storage::upload_object))
+                )
+                .service(actix_files::Files::new("/storage", local_storage_root));
+        }
+
+        app
             .service(
                 web::scope("/api")
                     .service(
@@ -235,6 +272,7 @@ async fn main() -> std::io::Result<()> {
                         web::scope("/users")
                             .route("/profile", web::get().to(handlers::users::get_profile))
                             .route("/profile", web::put().to(handlers::users::update_profile))
+                            .route("/favorites", web::get().to(handlers::favorites::get_favorites))
                     )
                     .service(
                         web::scope("/muas")
@@ -246,25 +284,73 @@ async fn main() -> std::io::Result<()> {
                                     .route(web::post().to(handlers::muas::create_portfolio))
                             )
                             .route("/upload/presigned", web::post().to(handlers::muas::get_presigned_upload_url))
-                            .route("/debug/presigned", web::get().to(handlers::muas::debug_presigned_url))
                             .route("/{id}", web::get().to(handlers::muas::get_mua_by_id))
                             .route("/{id}/portfolio", web::get().to(handlers::muas::get_mua_portfolio))
+                            .route("/{id}/bookable-slots", web::get().to(handlers::muas::get_bookable_slots))
+                            .route("/{id}/services", web::get().to(handlers::offerings::get_mua_offerings))
+                            .route("/{id}/favorite", web::post().to(handlers::favorites::save_favorite))
+                            .route("/{id}/favorite", web::delete().to(handlers::favorites::remove_favorite))
                     )
                     .service(
                         web::scope("/bookings")
                             .route("", web::post().to(handlers::bookings::create_booking))
                             .route("", web::get().to(handlers::bookings::get_bookings))
+                            .route("/{id}", web::get().to(handlers::bookings::get_booking))
                             .route("/{id}/status", web::put().to(handlers::bookings::update_booking_status))
+                            .route("/{id}/messages", web::get().to(handlers::bookings::get_messages))
+                            .route("/{id}/reschedule-requests", web::post().to(handlers::bookings::create_reschedule_request))
+                            .route("/{id}/reschedule-requests", web::get().to(handlers::bookings::get_reschedule_requests))
+                            .route("/{id}/reschedule-requests/{request_id}/accept", web::post().to(handlers::bookings::accept_reschedule_request))
+                            .route("/{id}/reschedule-requests/{request_id}/decline", web::post().to(handlers::bookings::decline_reschedule_request))
+                            .route("/{id}/quotes", web::post().to(handlers::bookings::create_quote))
+                            .route("/{id}/quotes", web::get().to(handlers::bookings::get_quotes))
+                            .route("/{id}/quotes/{quote_id}/accept", web::post().to(handlers::bookings::accept_quote))
+                    )
+                    .service(
+                        web::scope("/calendar")
+                            .route("/{token}.ics", web::get().to(handlers::calendar::get_calendar_feed_ics))
+                    )
+                    .service(
+                        web::scope("/reviews")
+                            .route("", web::post().to(handlers::reviews::create_review))
                     )
                     .service(
                         web::scope("/dashboard")
                             .route("", web::get().to(handlers::dashboard::get_dashboard))
                             .route("/availability", web::put().to(handlers::dashboard::update_availability))
+                            .route("/cancellation-policy", web::get().to(handlers::dashboard::get_cancellation_policy))
+                            .route("/cancellation-policy", web::put().to(handlers::dashboard::update_cancellation_policy))
+                            .route("/travel-pricing", web::get().to(handlers::dashboard::get_travel_pricing))
+                            .route("/travel-pricing", web::put().to(handlers::dashboard::update_travel_pricing))
+                            .route("/timezone", web::get().to(handlers::dashboard::get_timezone))
+                            .route("/timezone", web::put().to(handlers::dashboard::update_timezone))
+                            .route("/buffers", web::get().to(handlers::dashboard::get_booking_buffers))
+                            .route("/buffers", web::put().to(handlers::dashboard::update_booking_buffers))
+                            .route("/calendar-feed", web::get().to(handlers::calendar::get_calendar_feed))
+                            .route("/calendar-feed/rotate", web::post().to(handlers::calendar::rotate_calendar_feed))
+                            .route("/external-calendars", web::get().to(handlers::calendar::get_external_calendars))
+                            .route("/external-calendars", web::post().to(handlers::calendar::add_external_calendar))
+                            .service(
+                                web::resource("/external-calendars/upload")
+                                    .app_data(web::PayloadConfig::new(MAX_CALENDAR_SIZE))
+                                    .route(web::post().to(handlers::calendar::upload_external_calendar))
+                            )
+                            .route("/external-calendars/{id}/sync", web::post().to(handlers::calendar::sync_external_calendar))
+                            .route("/external-calendars/{id}", web::delete().to(handlers::calendar::delete_external_calendar))
+                            .route("/time-off", web::get().to(handlers::time_off::get_time_off))
+                            .route("/time-off", web::post().to(handlers::time_off::create_time_off))
+                            .route("/time-off/{id}", web::delete().to(handlers::time_off::delete_time_off))
+                            .route("/services", web::get().to(handlers::offerings::get_offerings))
+                            .route("/services", web::post().to(handlers::offerings::create_offering))
+                            .route("/services/{id}", web::put().to(handlers::offerings::update_offering))
+                            .route("/services/{id}", web::delete().to(handlers::offerings::delete_offering))
                             // Availability Management endpoints
                             .route("/availability/slots", web::get().to(handlers::dashboard::get_availability_slots))
                             .route("/availability/slots", web::post().to(handlers::dashboard::create_availability_slot))
                             .route("/availability/slots/{id}", web::put().to(handlers::dashboard::update_availability_slot))
                             .route("/availability/slots/{id}", web::delete().to(handlers::dashboard::delete_availability_slot))
+                            .route("/availability/weekly", web::get().to(handlers::dashboard::get_weekly_schedule))
+                            .route("/availability/weekly", web::put().to(handlers::dashboard::replace_weekly_schedule))
                             .route("/calendar/bookings", web::get().to(handlers::dashboard::get_calendar_bookings))
                             .route("/bookings/{id}/status", web::put().to(handlers::dashboard::update_booking_status_calendar))
                     )
@@ -1,17 +1,19 @@
 use serde::{Deserialize, Serialize};
 use sqlx::{FromRow, Row, postgres::PgRow};
 use uuid::Uuid;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveTime, Utc};
 use anyhow::Result;
 
+use crate::models::booking::BookingLineItem;
+
 #[derive(Debug, Serialize, Deserialize, FromRow)]
 pub struct AvailabilitySlot {
     pub id: Uuid,
     pub mua_id: Uuid,
-    pub start_time: String, // HH:MM format
-    pub end_time: String,   // HH:MM format
+    pub start_time: NaiveTime, // Wall-clock time in the MUA's timezone
+    pub end_time: NaiveTime,
     pub day_of_week: Option<i32>, // 0-6, Sunday to Saturday, NULL for specific dates
-    pub specific_date: Option<DateTime<Utc>>, // For one-time availability
+    pub specific_date: Option<NaiveDate>, // For one-time availability, a day in the MUA's timezone
     pub is_available: bool,
     pub recurring: bool,
     pub created_at: DateTime<Utc>,
@@ -43,13 +45,184 @@ pub struct AvailabilityResponse {
     pub start_time: String,
     pub end_time: String,
     pub day_of_week: Option<i32>,
-    pub specific_date: Option<DateTime<Utc>>,
+    pub specific_date: Option<NaiveDate>,
+    /// IANA zone the times and date are in
+    pub timezone: String,
     pub is_available: bool,
     pub recurring: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+/// One stretch of available time, HH:MM on the MUA's wall clock
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimeRange {
+    pub start_time: String,
+    pub end_time: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WeeklyScheduleDay {
+    /// 0-6, Sunday to Saturday
+    pub day_of_week: i32,
+    pub ranges: Vec<TimeRange>,
+}
+
+/// A MUA's whole recurring week; days left out have no availability, but at least one day needs a range
+#[derive(Debug, Deserialize)]
+pub struct UpdateWeeklyScheduleRequest {
+    pub days: Vec<WeeklyScheduleDay>,
+}
+
+/// Every weekday, Sunday first, with its ranges sorted and touching ranges merged
+#[derive(Debug, Serialize)]
+pub struct WeeklySchedule {
+    pub timezone: String,
+    pub days: Vec<WeeklyScheduleDay>,
+}
+
+impl UpdateWeeklyScheduleRequest {
+    /// Parse and check the ranges, returned per weekday in order. Ranges on the
+    /// same day may touch, which merges them, but not overlap.
+    ///
+    /// An empty week is refused: with no slots at all the MUA would count as open
+    /// around the clock, so closing is done by turning availability off instead.
+    pub fn normalize(&self) -> Result<[Vec<(NaiveTime, NaiveTime)>; 7]> {
+        let mut week: [Vec<(NaiveTime, NaiveTime)>; 7] = Default::default();
+
+        for day in &self.days {
+            let ranges = usize::try_from(day.day_of_week).ok()
+                .and_then(|index| week.get_mut(index))
+                .ok_or_else(|| anyhow::anyhow!("Invalid schedule: day_of_week must be between 0 and 6"))?;
+
+            for range in &day.ranges {
+                let start = NaiveTime::parse_from_str(&range.start_time, "%H:%M")
+                    .map_err(|_| anyhow::anyhow!("Invalid schedule: start_time '{}' is not HH:MM", range.start_time))?;
+                let end = NaiveTime::parse_from_str(&range.end_time, "%H:%M")
+                    .map_err(|_| anyhow::anyhow!("Invalid schedule: end_time '{}' is not HH:MM", range.end_time))?;
+                if end <= start {
+                    return Err(anyhow::anyhow!(
+                        "Invalid schedule: {}-{} ends before it starts",
+                        range.start_time, range.end_time
+                    ));
+                }
+                ranges.push((start, end));
+            }
+        }
+
+        for (day_of_week, ranges) in week.iter_mut().enumerate() {
+            ranges.sort();
+
+            let mut merged: Vec<(NaiveTime, NaiveTime)> = Vec::with_capacity(ranges.len());
+            for &(start, end) in ranges.iter() {
+                match merged.last_mut() {
+                    Some(previous) if start < previous.1 => {
+                        return Err(anyhow::anyhow!(
+                            "Invalid schedule: overlapping ranges on day {} ({}-{} and {}-{})",
+                            day_of_week,
+                            previous.0.format("%H:%M"), previous.1.format("%H:%M"),
+                            start.format("%H:%M"), end.format("%H:%M")
+                        ));
+                    }
+                    Some(previous) if start == previous.1 => previous.1 = end,
+                    _ => merged.push((start, end)),
+                }
+            }
+            *ranges = merged;
+        }
+
+        if week.iter().all(|ranges| ranges.is_empty()) {
+            return Err(anyhow::anyhow!(
+                "Invalid schedule: add at least one range, or turn off availability to stop taking bookings"
+            ));
+        }
+
+        Ok(week)
+    }
+}
+
+impl WeeklySchedule {
+    pub fn new(timezone: String, week: &[Vec<(NaiveTime, NaiveTime)>; 7]) -> Self {
+        let days = week.iter().enumerate()
+            .map(|(day_of_week, ranges)| WeeklyScheduleDay {
+                day_of_week: day_of_week as i32,
+                ranges: ranges.iter()
+                    .map(|(start, end)| TimeRange {
+                        start_time: start.format("%H:%M").to_string(),
+                        end_time: end.format("%H:%M").to_string(),
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        Self { timezone, days }
+    }
+}
+
+/// Longest gap the buffers can demand between two bookings
+pub const MAX_BUFFER_MINUTES: i64 = 12 * 60;
+
+/// Time a MUA keeps free around each confirmed booking
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct BookingBuffers {
+    /// Setting up and packing away
+    pub prep_minutes: i32,
+    /// Getting from one event to the next
+    pub travel_minutes: i32,
+    /// Extra travel time per km between the two events, when both have coordinates
+    pub travel_minutes_per_km: f64,
+}
+
+impl BookingBuffers {
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if self.prep_minutes < 0 || self.travel_minutes < 0 {
+            return Err(anyhow::anyhow!("Invalid buffers: minutes cannot be negative"));
+        }
+        if i64::from(self.prep_minutes) + i64::from(self.travel_minutes) > MAX_BUFFER_MINUTES {
+            return Err(anyhow::anyhow!("Invalid buffers: at most {} minutes in total", MAX_BUFFER_MINUTES));
+        }
+        if !self.travel_minutes_per_km.is_finite() || !(0.0..=10.0).contains(&self.travel_minutes_per_km) {
+            return Err(anyhow::anyhow!("Invalid buffers: travel_minutes_per_km must be between 0 and 10"));
+        }
+
+        Ok(())
+    }
+
+    /// Minutes needed between two events `distance_km` apart (None if either has no coordinates),
+    /// capped at `MAX_BUFFER_MINUTES`
+    pub fn minutes_between(&self, distance_km: Option<f64>) -> i64 {
+        let distance_minutes = distance_km
+            .map(|distance_km| (distance_km * self.travel_minutes_per_km).ceil() as i64)
+            .unwrap_or(0);
+
+        (i64::from(self.prep_minutes) + i64::from(self.travel_minutes) + distance_minutes).min(MAX_BUFFER_MINUTES)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateBookingBuffersRequest {
+    pub prep_minutes: i32,
+    pub travel_minutes: i32,
+    #[serde(default)]
+    pub travel_minutes_per_km: f64,
+}
+
+/// Times carry the MUA's UTC offset
+#[derive(Debug, Serialize)]
+pub struct BookableSlot {
+    pub start_time: DateTime<FixedOffset>,
+    pub end_time: DateTime<FixedOffset>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BookableSlotsQuery {
+    /// YYYY-MM-DD, a day in the MUA's timezone
+    pub date: String,
+    pub duration_hours: i32,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+}
+
 // Simplified response for frontend TimeSlot interface
 #[derive(Debug, Serialize)]
 pub struct TimeSlotResponse {
@@ -60,21 +233,26 @@ pub struct TimeSlotResponse {
     pub recurring: bool,
     pub day_of_week: Option<i32>,
     pub specific_date: Option<String>,
+    pub timezone: String,
 }
 
-// Calendar booking response with customer info
+// Calendar booking response with customer info; times carry the MUA's UTC offset
 #[derive(Debug, Serialize)]
 pub struct CalendarBooking {
     pub id: Uuid,
     pub customer_name: String,
     pub customer_phone: Option<String>,
     pub service_type: String,
-    pub start_time: DateTime<Utc>,
-    pub end_time: DateTime<Utc>,
+    pub start_time: DateTime<FixedOffset>,
+    pub end_time: DateTime<FixedOffset>,
     pub status: String,
     pub location: Option<String>,
     pub notes: Option<String>,
     pub price: f64,
+    pub line_items: Vec<BookingLineItem>,
+    /// The booking plus the buffers the MUA keeps around it
+    pub blocked_from: DateTime<FixedOffset>,
+    pub blocked_until: DateTime<FixedOffset>,
 }
 
 impl TryFrom<PgRow> for AvailabilitySlot {
@@ -94,4 +272,87 @@ impl TryFrom<PgRow> for AvailabilitySlot {
             updated_at: row.try_get("updated_at")?,
         })
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(day_of_week: i32, ranges: &[(&str, &str)]) -> WeeklyScheduleDay {
+        WeeklyScheduleDay {
+            day_of_week,
+            ranges: ranges.iter()
+                .map(|(start, end)| TimeRange { start_time: start.to_string(), end_time: end.to_string() })
+                .collect(),
+        }
+    }
+
+    fn time(value: &str) -> NaiveTime {
+        NaiveTime::parse_from_str(value, "%H:%M").unwrap()
+    }
+
+    fn error(days: Vec<WeeklyScheduleDay>) -> String {
+        UpdateWeeklyScheduleRequest { days }.normalize().unwrap_err().to_string()
+    }
+
+    #[test]
+    fn sorts_and_merges_touching_ranges() {
+        let week = UpdateWeeklyScheduleRequest {
+            days: vec![
+                day(1, &[("13:00", "17:00"), ("09:00", "12:00"), ("12:00", "13:00")]),
+                day(3, &[("18:00", "21:00"), ("08:00", "10:00")]),
+                day(1, &[("19:00", "20:00")]),
+            ],
+        }
+        .normalize()
+        .unwrap();
+
+        assert_eq!(week[1], vec![(time("09:00"), time("17:00")), (time("19:00"), time("20:00"))]);
+        assert_eq!(week[3], vec![(time("08:00"), time("10:00")), (time("18:00"), time("21:00"))]);
+        assert!(week[0].is_empty() && week[2].is_empty() && week[6].is_empty());
+    }
+
+    #[test]
+    fn rejects_overlapping_ranges() {
+        let message = error(vec![day(2, &[("09:00", "12:00"), ("11:30", "14:00")])]);
+        assert!(message.starts_with("Invalid schedule: overlapping ranges on day 2"), "{}", message);
+
+        // Repeating a day doesn't sneak an overlap past the check
+        let message = error(vec![day(5, &[("09:00", "12:00")]), day(5, &[("10:00", "11:00")])]);
+        assert!(message.contains("overlapping"), "{}", message);
+    }
+
+    #[test]
+    fn rejects_bad_days_and_times() {
+        assert!(error(vec![day(7, &[("09:00", "12:00")])]).contains("day_of_week"));
+        assert!(error(vec![day(1, &[("9am", "12:00")])]).contains("start_time"));
+        assert!(error(vec![day(1, &[("12:00", "09:00")])]).contains("ends before it starts"));
+        assert!(error(vec![day(1, &[("12:00", "12:00")])]).contains("ends before it starts"));
+    }
+
+    #[test]
+    fn rejects_an_empty_week() {
+        assert!(error(vec![]).starts_with("Invalid schedule: add at least one range"));
+        assert!(error(vec![day(0, &[]), day(4, &[])]).starts_with("Invalid schedule: add at least one range"));
+    }
+
+    fn buffers(prep_minutes: i32, travel_minutes: i32, travel_minutes_per_km: f64) -> BookingBuffers {
+        BookingBuffers { prep_minutes, travel_minutes, travel_minutes_per_km }
+    }
+
+    #[test]
+    fn buffer_without_a_distance_is_prep_and_travel() {
+        assert_eq!(buffers(30, 45, 2.0).minutes_between(None), 75);
+        assert_eq!(BookingBuffers::default().minutes_between(None), 0);
+    }
+
+    #[test]
+    fn buffer_adds_travel_time_per_km_rounded_up() {
+        assert_eq!(buffers(30, 45, 2.0).minutes_between(Some(12.3)), 75 + 25);
+        assert_eq!(buffers(30, 45, 0.0).minutes_between(Some(12.3)), 75);
+    }
+
+    #[test]
+    fn buffer_is_capped() {
+        assert_eq!(buffers(300, 300, 10.0).minutes_between(Some(500.0)), MAX_BUFFER_MINUTES);
+    }
+}
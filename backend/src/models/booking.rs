@@ -1,11 +1,15 @@
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 use serde_with::DisplayFromStr;
-use sqlx::{FromRow, Row, postgres::PgRow, types::BigDecimal};
+use sqlx::{FromRow, Row, postgres::PgRow, types::{BigDecimal, Json}};
 use uuid::Uuid;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, FixedOffset, Utc};
+use chrono_tz::Tz;
 use anyhow::Result;
 use super::user::UserType;
+use super::cancellation::CancellationPolicy;
+// Still reachable as `models::booking::BookingStatus`, where it used to live
+pub use super::booking_status::BookingStatus;
 
 #[serde_as]
 #[derive(Debug, Serialize, Deserialize, FromRow)]
@@ -25,30 +29,188 @@ pub struct Booking {
     pub deposit_amount: Option<BigDecimal>,
     pub deposit_paid: bool,
     pub final_payment_paid: bool,
+    pub response_deadline: Option<DateTime<Utc>>,
+    pub cancellation_reason: Option<String>,
+    pub completed_at: Option<DateTime<Utc>>,
+    /// Both parties can leave a review until this time
+    pub review_window_closes_at: Option<DateTime<Utc>>,
+    /// The MUA's policy when the booking was made; None for bookings older than policies
+    pub cancellation_policy: Option<Json<CancellationPolicy>>,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub refund_amount: Option<BigDecimal>,
+    pub refund_reference: Option<String>,
+    pub refunded_at: Option<DateTime<Utc>>,
+    pub event_latitude: Option<f64>,
+    pub event_longitude: Option<f64>,
+    pub travel_distance_km: Option<f64>,
+    /// Part of `price`, charged for the MUA's travel to the event
+    #[serde_as(as = "DisplayFromStr")]
+    pub travel_fee: BigDecimal,
+    /// Part of `price`: what an accepted quote changed on top of the line items and travel fee
+    #[serde_as(as = "DisplayFromStr")]
+    pub price_adjustment: BigDecimal,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Serialize, Deserialize, sqlx::Type)]
-#[sqlx(type_name = "booking_status", rename_all = "lowercase")]
-pub enum BookingStatus {
-    Pending,
-    Confirmed,
-    Cancelled,
-    Completed,
-    NoShow,
+/// Whose bookings a list is for
+#[derive(Debug, Clone, Copy)]
+pub enum BookingParticipant {
+    Customer(Uuid),
+    Mua(Uuid),
+}
+
+/// Query string of `GET /api/bookings`
+#[derive(Debug, Default, Deserialize)]
+pub struct BookingListQuery {
+    pub cursor: Option<String>,
+    pub limit: Option<i64>,
+    /// Comma separated, e.g. `pending,confirmed`
+    pub status: Option<String>,
+    /// Event dates on the MUA's calendar, YYYY-MM-DD, both inclusive
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub when: Option<BookingTimeframe>,
+    pub service_type: Option<String>,
+    pub sort: Option<BookingSort>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BookingTimeframe {
+    /// The event hasn't started yet
+    Upcoming,
+    Past,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BookingSort {
+    /// Most recently requested first
+    Newest,
+    /// Soonest event first
+    EventDateAsc,
+    EventDateDesc,
+}
+
+impl BookingSort {
+    /// Names the order in cursors, as it is spelled in the `sort` parameter
+    pub fn key(&self) -> &'static str {
+        match self {
+            BookingSort::Newest => super::NEWEST_FIRST,
+            BookingSort::EventDateAsc => "event_date_asc",
+            BookingSort::EventDateDesc => "event_date_desc",
+        }
+    }
+}
+
+/// What a booking list is narrowed to; empty fields don't filter
+#[derive(Debug, Clone)]
+pub struct BookingFilter {
+    pub statuses: Vec<BookingStatus>,
+    pub event_from: Option<chrono::NaiveDate>,
+    pub event_to: Option<chrono::NaiveDate>,
+    pub timeframe: Option<BookingTimeframe>,
+    /// Matched case-insensitively against the booking's or any of its line items' service type
+    pub service_type: Option<String>,
+    pub sort: BookingSort,
+}
+
+impl BookingListQuery {
+    pub fn page_query(&self) -> super::PageQuery {
+        super::PageQuery { cursor: self.cursor.clone(), limit: self.limit }
+    }
+
+    /// Upcoming lists default to the soonest event first and past ones to the latest,
+    /// anything else to the newest request
+    pub fn filter(&self) -> Result<BookingFilter> {
+        let statuses = self.status.as_deref()
+            .unwrap_or("")
+            .split(',')
+            .map(str::trim)
+            .filter(|status| !status.is_empty())
+            .map(str::parse)
+            .collect::<Result<Vec<BookingStatus>>>()?;
+
+        let parse_date = |date: &Option<String>| -> Result<Option<chrono::NaiveDate>> {
+            date.as_deref()
+                .map(|date| chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                    .map_err(|_| anyhow::anyhow!("Invalid date format, expected YYYY-MM-DD")))
+                .transpose()
+        };
+        let event_from = parse_date(&self.from)?;
+        let event_to = parse_date(&self.to)?;
+        if let (Some(from), Some(to)) = (event_from, event_to) {
+            if from > to {
+                return Err(anyhow::anyhow!("Invalid date range: from is after to"));
+            }
+        }
+
+        let sort = self.sort.unwrap_or(match self.when {
+            Some(BookingTimeframe::Upcoming) => BookingSort::EventDateAsc,
+            Some(BookingTimeframe::Past) => BookingSort::EventDateDesc,
+            None => BookingSort::Newest,
+        });
+
+        Ok(BookingFilter {
+            statuses,
+            event_from,
+            event_to,
+            timeframe: self.when,
+            service_type: self.service_type.as_deref()
+                .map(str::trim)
+                .filter(|service_type| !service_type.is_empty())
+                .map(str::to_string),
+            sort,
+        })
+    }
 }
 
 #[derive(Debug, Deserialize)]
 pub struct CreateBookingRequest {
     pub mua_id: Uuid,
+    /// `service_type`, `duration_hours` and `price` can be left out when `line_items` are given,
+    /// they are derived from the items
+    #[serde(default)]
     pub service_type: String,
     pub description: Option<String>,
     pub event_date: String,
     pub event_location: String,
+    /// Where the event is, used to price the MUA's travel
+    pub event_latitude: Option<f64>,
+    pub event_longitude: Option<f64>,
+    #[serde(default)]
     pub duration_hours: i32,
+    #[serde(default)]
     pub price: String,
     pub deposit_amount: Option<String>,
+    #[serde(default)]
+    pub line_items: Vec<CreateBookingLineItem>,
+    /// RFC 3339; defaults to `BOOKING_RESPONSE_HOURS` after the request, capped at the event date
+    pub response_deadline: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CreateBookingLineItem {
+    pub service_type: String,
+    pub person_count: i32,
+    /// Price per person
+    pub unit_price: String,
+    pub minutes_per_person: i32,
+}
+
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct BookingLineItem {
+    pub id: Uuid,
+    pub booking_id: Uuid,
+    pub service_type: String,
+    pub person_count: i32,
+    #[serde_as(as = "DisplayFromStr")]
+    pub unit_price: BigDecimal,
+    pub minutes_per_person: i32,
+    pub position: i32,
+    pub created_at: DateTime<Utc>,
 }
 
 #[serde_as]
@@ -59,7 +221,10 @@ pub struct BookingResponse {
     pub mua_id: Uuid,
     pub service_type: String,
     pub description: Option<String>,
-    pub event_date: DateTime<Utc>,
+    /// On the MUA's wall clock, with its UTC offset
+    pub event_date: DateTime<FixedOffset>,
+    /// IANA zone of the MUA, which `event_date` is given in
+    pub timezone: String,
     pub event_location: String,
     pub duration_hours: i32,
     #[serde_as(as = "DisplayFromStr")]
@@ -69,13 +234,183 @@ pub struct BookingResponse {
     pub deposit_amount: Option<BigDecimal>,
     pub deposit_paid: bool,
     pub final_payment_paid: bool,
+    pub response_deadline: Option<DateTime<Utc>>,
+    pub cancellation_reason: Option<String>,
+    pub completed_at: Option<DateTime<Utc>>,
+    /// Both parties can leave a review until this time
+    pub review_window_closes_at: Option<DateTime<Utc>>,
+    pub cancellation_policy: CancellationPolicy,
+    /// Owed back to the customer once the booking is cancelled
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub refund_amount: Option<BigDecimal>,
+    pub refunded_at: Option<DateTime<Utc>>,
+    pub event_latitude: Option<f64>,
+    pub event_longitude: Option<f64>,
+    pub travel_distance_km: Option<f64>,
+    /// Part of `price`, along with the line items and `price_adjustment`
+    #[serde_as(as = "DisplayFromStr")]
+    pub travel_fee: BigDecimal,
+    /// Negotiated difference from the line items and travel fee; negative for a discount
+    #[serde_as(as = "DisplayFromStr")]
+    pub price_adjustment: BigDecimal,
+    pub line_items: Vec<BookingLineItem>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+/// One booking as seen by one of its participants
+#[derive(Debug, Serialize)]
+pub struct BookingDetail {
+    #[serde(flatten)]
+    pub booking: BookingResponse,
+    /// The other side of the booking
+    pub counterpart: BookingCounterpart,
+    pub payment: BookingPayment,
+    /// Oldest first
+    pub status_history: Vec<BookingStatusChange>,
+    pub reviews: BookingReviewState,
+}
+
+/// What one participant may see of the other
+#[derive(Debug, Serialize)]
+pub struct BookingCounterpart {
+    pub user_id: Uuid,
+    pub user_type: UserType,
+    /// Set when the counterpart is the MUA
+    pub mua_id: Option<Uuid>,
+    pub full_name: String,
+    pub profile_picture_url: Option<String>,
+    /// Only shared once the booking is confirmed
+    pub phone_number: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PaymentState {
+    Unpaid,
+    DepositPaid,
+    Paid,
+    /// Cancelled with money owed back that hasn't reached the customer yet
+    RefundPending,
+    Refunded,
+}
+
+#[serde_as]
+#[derive(Debug, Serialize)]
+pub struct BookingPayment {
+    pub state: PaymentState,
+    #[serde_as(as = "DisplayFromStr")]
+    pub amount_paid: BigDecimal,
+    /// Still to pay; nothing once the booking is cancelled
+    #[serde_as(as = "DisplayFromStr")]
+    pub balance_due: BigDecimal,
+}
+
+#[derive(Debug, Serialize, FromRow)]
+pub struct BookingStatusChange {
+    pub from_status: Option<BookingStatus>,
+    pub to_status: BookingStatus,
+    /// None for automatic transitions such as expiry
+    pub changed_by: Option<Uuid>,
+    pub reason: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BookingReviewState {
+    pub window_closes_at: Option<DateTime<Utc>>,
+    /// Whether the caller can still leave a review
+    pub can_review: bool,
+    pub my_review: Option<super::Review>,
+    pub counterpart_review: Option<super::Review>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct UpdateBookingStatusRequest {
     pub status: BookingStatus,
+    pub reason: Option<String>,
+}
+
+impl Booking {
+    pub fn event_end(&self) -> DateTime<Utc> {
+        self.event_date + chrono::Duration::hours(self.duration_hours as i64)
+    }
+
+    pub fn event_coordinates(&self) -> Option<(f64, f64)> {
+        self.event_latitude.zip(self.event_longitude)
+    }
+
+    /// What the customer has paid so far: the deposit, or the full price once the final payment is in
+    pub fn amount_paid(&self) -> BigDecimal {
+        if self.final_payment_paid {
+            self.price.clone()
+        } else if self.deposit_paid {
+            self.deposit_amount.clone().unwrap_or_default()
+        } else {
+            BigDecimal::default()
+        }
+    }
+
+    pub fn payment(&self) -> BookingPayment {
+        let amount_paid = self.amount_paid();
+        let refund_owed = self.refund_amount.as_ref().is_some_and(|amount| *amount > BigDecimal::default());
+
+        let state = if self.refunded_at.is_some() {
+            PaymentState::Refunded
+        } else if refund_owed {
+            PaymentState::RefundPending
+        } else if self.final_payment_paid {
+            PaymentState::Paid
+        } else if self.deposit_paid {
+            PaymentState::DepositPaid
+        } else {
+            PaymentState::Unpaid
+        };
+
+        let balance_due = if self.status == BookingStatus::Cancelled {
+            BigDecimal::default()
+        } else {
+            &self.price - &amount_paid
+        };
+
+        BookingPayment { state, amount_paid, balance_due }
+    }
+}
+
+impl BookingResponse {
+    pub fn with_line_items(booking: Booking, line_items: Vec<BookingLineItem>, timezone: Tz) -> Self {
+        Self {
+            id: booking.id,
+            customer_id: booking.customer_id,
+            mua_id: booking.mua_id,
+            service_type: booking.service_type,
+            description: booking.description,
+            event_date: booking.event_date.with_timezone(&timezone).fixed_offset(),
+            timezone: timezone.name().to_string(),
+            event_location: booking.event_location,
+            duration_hours: booking.duration_hours,
+            price: booking.price,
+            status: booking.status,
+            deposit_amount: booking.deposit_amount,
+            deposit_paid: booking.deposit_paid,
+            final_payment_paid: booking.final_payment_paid,
+            response_deadline: booking.response_deadline,
+            cancellation_reason: booking.cancellation_reason,
+            completed_at: booking.completed_at,
+            review_window_closes_at: booking.review_window_closes_at,
+            cancellation_policy: booking.cancellation_policy.map(|policy| policy.0).unwrap_or_default(),
+            refund_amount: booking.refund_amount,
+            refunded_at: booking.refunded_at,
+            event_latitude: booking.event_latitude,
+            event_longitude: booking.event_longitude,
+            travel_distance_km: booking.travel_distance_km,
+            travel_fee: booking.travel_fee,
+            price_adjustment: booking.price_adjustment,
+            line_items,
+            created_at: booking.created_at,
+            updated_at: booking.updated_at,
+        }
+    }
 }
 
 impl TryFrom<PgRow> for Booking {
@@ -96,6 +431,19 @@ impl TryFrom<PgRow> for Booking {
             deposit_amount: row.try_get("deposit_amount")?,
             deposit_paid: row.try_get("deposit_paid")?,
             final_payment_paid: row.try_get("final_payment_paid")?,
+            response_deadline: row.try_get("response_deadline")?,
+            cancellation_reason: row.try_get("cancellation_reason")?,
+            completed_at: row.try_get("completed_at")?,
+            review_window_closes_at: row.try_get("review_window_closes_at")?,
+            cancellation_policy: row.try_get("cancellation_policy")?,
+            refund_amount: row.try_get("refund_amount")?,
+            refund_reference: row.try_get("refund_reference")?,
+            refunded_at: row.try_get("refunded_at")?,
+            event_latitude: row.try_get("event_latitude")?,
+            event_longitude: row.try_get("event_longitude")?,
+            travel_distance_km: row.try_get("travel_distance_km")?,
+            travel_fee: row.try_get("travel_fee")?,
+            price_adjustment: row.try_get("price_adjustment")?,
             created_at: row.try_get("created_at")?,
             updated_at: row.try_get("updated_at")?,
         })
@@ -113,4 +461,53 @@ impl TryFrom<PgRow> for UserType {
             _ => Err(anyhow::anyhow!("Invalid user type: {}", user_type_str)),
         }
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+impl Booking {
+    /// A booking for unit tests; change the fields a test cares about
+    pub(crate) fn fixture(status: BookingStatus, event_date: DateTime<Utc>, duration_hours: i32) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            customer_id: Uuid::new_v4(),
+            mua_id: Uuid::new_v4(),
+            service_type: "Bridal".to_string(),
+            description: None,
+            event_date,
+            event_location: "Jakarta".to_string(),
+            duration_hours,
+            price: BigDecimal::from(1_000_000),
+            status,
+            deposit_amount: None,
+            deposit_paid: false,
+            final_payment_paid: false,
+            response_deadline: None,
+            cancellation_reason: None,
+            completed_at: None,
+            review_window_closes_at: None,
+            cancellation_policy: None,
+            refund_amount: None,
+            refund_reference: None,
+            refunded_at: None,
+            event_latitude: None,
+            event_longitude: None,
+            travel_distance_km: None,
+            travel_fee: BigDecimal::default(),
+            price_adjustment: BigDecimal::default(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sort_keys_match_the_sort_parameter() {
+        for sort in [BookingSort::Newest, BookingSort::EventDateAsc, BookingSort::EventDateDesc] {
+            let parsed: BookingSort = serde_json::from_value(serde_json::json!(sort.key())).unwrap();
+            assert_eq!(parsed, sort);
+        }
+    }
+}
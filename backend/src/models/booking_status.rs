@@ -0,0 +1,135 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "booking_status", rename_all = "lowercase")]
+pub enum BookingStatus {
+    Pending,
+    Confirmed,
+    Cancelled,
+    Completed,
+    #[sqlx(rename = "no_show")]
+    NoShow,
+}
+
+/// Every move a booking can make. Completed, cancelled and no-show are final: completing
+/// opens reviews and cancelling settles the refund, so neither can be undone.
+const ALLOWED_TRANSITIONS: [(BookingStatus, BookingStatus); 5] = [
+    (BookingStatus::Pending, BookingStatus::Confirmed),
+    (BookingStatus::Pending, BookingStatus::Cancelled),
+    (BookingStatus::Confirmed, BookingStatus::Completed),
+    (BookingStatus::Confirmed, BookingStatus::NoShow),
+    (BookingStatus::Confirmed, BookingStatus::Cancelled),
+];
+
+impl BookingStatus {
+    /// The database spelling, e.g. `no_show`
+    pub fn as_str(self) -> &'static str {
+        match self {
+            BookingStatus::Pending => "pending",
+            BookingStatus::Confirmed => "confirmed",
+            BookingStatus::Cancelled => "cancelled",
+            BookingStatus::Completed => "completed",
+            BookingStatus::NoShow => "no_show",
+        }
+    }
+
+    pub fn can_become(self, next: BookingStatus) -> bool {
+        ALLOWED_TRANSITIONS.contains(&(self, next))
+    }
+}
+
+/// Lets lists bind several statuses as one `booking_status[]`
+impl sqlx::postgres::PgHasArrayType for BookingStatus {
+    fn array_type_info() -> sqlx::postgres::PgTypeInfo {
+        sqlx::postgres::PgTypeInfo::with_name("_booking_status")
+    }
+}
+
+impl std::str::FromStr for BookingStatus {
+    type Err = anyhow::Error;
+
+    /// The database spelling, e.g. `no_show`
+    fn from_str(value: &str) -> Result<Self> {
+        match value {
+            "pending" => Ok(BookingStatus::Pending),
+            "confirmed" => Ok(BookingStatus::Confirmed),
+            "cancelled" => Ok(BookingStatus::Cancelled),
+            "completed" => Ok(BookingStatus::Completed),
+            "no_show" => Ok(BookingStatus::NoShow),
+            _ => Err(anyhow::anyhow!("Invalid status. Must be one of: pending, confirmed, cancelled, completed, no_show")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_forbidden(from: BookingStatus, to: BookingStatus) {
+        assert!(!from.can_become(to), "{:?} -> {:?} was allowed", from, to);
+    }
+
+    #[test]
+    fn pending_bookings_can_be_confirmed_or_cancelled() {
+        assert!(BookingStatus::Pending.can_become(BookingStatus::Confirmed));
+        assert!(BookingStatus::Pending.can_become(BookingStatus::Cancelled));
+    }
+
+    #[test]
+    fn confirmed_bookings_can_be_completed_marked_no_show_or_cancelled() {
+        assert!(BookingStatus::Confirmed.can_become(BookingStatus::Completed));
+        assert!(BookingStatus::Confirmed.can_become(BookingStatus::NoShow));
+        assert!(BookingStatus::Confirmed.can_become(BookingStatus::Cancelled));
+    }
+
+    #[test]
+    fn rejects_moving_a_confirmed_booking_back_to_pending() {
+        assert_forbidden(BookingStatus::Confirmed, BookingStatus::Pending);
+    }
+
+    #[test]
+    fn rejects_completing_a_pending_booking() {
+        assert_forbidden(BookingStatus::Pending, BookingStatus::Completed);
+    }
+
+    #[test]
+    fn rejects_marking_a_pending_booking_no_show() {
+        assert_forbidden(BookingStatus::Pending, BookingStatus::NoShow);
+    }
+
+    #[test]
+    fn rejects_reopening_a_cancelled_booking() {
+        for to in [BookingStatus::Pending, BookingStatus::Confirmed, BookingStatus::Completed, BookingStatus::NoShow] {
+            assert_forbidden(BookingStatus::Cancelled, to);
+        }
+    }
+
+    #[test]
+    fn rejects_reopening_a_completed_booking() {
+        for to in [BookingStatus::Pending, BookingStatus::Confirmed, BookingStatus::Cancelled, BookingStatus::NoShow] {
+            assert_forbidden(BookingStatus::Completed, to);
+        }
+    }
+
+    #[test]
+    fn rejects_reopening_a_no_show_booking() {
+        for to in [BookingStatus::Pending, BookingStatus::Confirmed, BookingStatus::Cancelled, BookingStatus::Completed] {
+            assert_forbidden(BookingStatus::NoShow, to);
+        }
+    }
+
+    #[test]
+    fn rejects_moving_a_booking_to_the_status_it_has() {
+        for status in [BookingStatus::Pending, BookingStatus::Confirmed, BookingStatus::Cancelled, BookingStatus::Completed, BookingStatus::NoShow] {
+            assert_forbidden(status, status);
+        }
+    }
+
+    #[test]
+    fn status_spelling_round_trips() {
+        for status in [BookingStatus::Pending, BookingStatus::Confirmed, BookingStatus::Cancelled, BookingStatus::Completed, BookingStatus::NoShow] {
+            assert_eq!(status.as_str().parse::<BookingStatus>().unwrap(), status);
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use serde::{Deserialize, Serialize};
+use sqlx::types::BigDecimal;
+
+/// Refund `refund_percent` of what was paid when cancelling at least `min_hours_before` the event
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CancellationTier {
+    pub min_hours_before: i64,
+    pub refund_percent: i32,
+}
+
+/// A MUA's tiered refund rules for customer cancellations.
+///
+/// A copy is stored on every booking when it's made, so later policy changes
+/// don't affect existing bookings. Cancelling later than every tier refunds nothing.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CancellationPolicy {
+    pub tiers: Vec<CancellationTier>,
+}
+
+impl Default for CancellationPolicy {
+    /// Full refund more than 7 days out, half at 3-7 days, nothing inside 72 hours
+    fn default() -> Self {
+        Self {
+            tiers: vec![
+                CancellationTier { min_hours_before: 7 * 24, refund_percent: 100 },
+                CancellationTier { min_hours_before: 72, refund_percent: 50 },
+            ],
+        }
+    }
+}
+
+impl CancellationPolicy {
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if self.tiers.len() > 10 {
+            return Err(anyhow::anyhow!("Invalid cancellation policy: at most 10 tiers are allowed"));
+        }
+
+        for tier in &self.tiers {
+            if tier.min_hours_before < 0 {
+                return Err(anyhow::anyhow!("Invalid cancellation policy: min_hours_before cannot be negative"));
+            }
+            if !(0..=100).contains(&tier.refund_percent) {
+                return Err(anyhow::anyhow!("Invalid cancellation policy: refund_percent must be between 0 and 100"));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Percentage refunded when cancelling `hours_before` the event; the most generous matching tier wins
+    pub fn refund_percent(&self, hours_before: i64) -> i32 {
+        self.tiers
+            .iter()
+            .filter(|tier| hours_before >= tier.min_hours_before)
+            .map(|tier| tier.refund_percent)
+            .max()
+            .unwrap_or(0)
+    }
+
+    pub fn refund_amount(&self, amount_paid: &BigDecimal, hours_before: i64) -> BigDecimal {
+        (amount_paid * BigDecimal::from(self.refund_percent(hours_before)) / BigDecimal::from(100)).round(2)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateCancellationPolicyRequest {
+    pub tiers: Vec<CancellationTier>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn default_policy_refunds_by_tier_boundaries() {
+        let policy = CancellationPolicy::default();
+
+        assert_eq!(policy.refund_percent(7 * 24 + 1), 100);
+        assert_eq!(policy.refund_percent(7 * 24), 100);
+        assert_eq!(policy.refund_percent(7 * 24 - 1), 50);
+        assert_eq!(policy.refund_percent(72), 50);
+        assert_eq!(policy.refund_percent(71), 0);
+        assert_eq!(policy.refund_percent(0), 0);
+    }
+
+    #[test]
+    fn cancelling_after_the_event_has_started_refunds_nothing() {
+        let policy = CancellationPolicy { tiers: vec![CancellationTier { min_hours_before: 0, refund_percent: 25 }] };
+
+        assert_eq!(policy.refund_percent(0), 25);
+        assert_eq!(policy.refund_percent(-1), 0);
+    }
+
+    #[test]
+    fn most_generous_matching_tier_wins_whatever_the_order() {
+        let policy = CancellationPolicy {
+            tiers: vec![
+                CancellationTier { min_hours_before: 24, refund_percent: 30 },
+                CancellationTier { min_hours_before: 48, refund_percent: 20 },
+                CancellationTier { min_hours_before: 12, refund_percent: 80 },
+            ],
+        };
+
+        assert_eq!(policy.refund_percent(100), 80);
+        assert_eq!(policy.refund_percent(11), 0);
+    }
+
+    #[test]
+    fn empty_policy_never_refunds() {
+        let policy = CancellationPolicy { tiers: vec![] };
+        assert_eq!(policy.refund_percent(10_000), 0);
+    }
+
+    #[test]
+    fn refund_amount_is_rounded_to_cents() {
+        let policy = CancellationPolicy::default();
+        let paid = BigDecimal::from_str("333333.33").unwrap();
+
+        assert_eq!(policy.refund_amount(&paid, 100), BigDecimal::from_str("166666.67").unwrap());
+        assert_eq!(policy.refund_amount(&paid, 200), paid);
+        assert_eq!(policy.refund_amount(&paid, 1), BigDecimal::from(0));
+    }
+
+    #[test]
+    fn rejects_out_of_range_tiers() {
+        let tier = |min_hours_before, refund_percent| CancellationPolicy { tiers: vec![CancellationTier { min_hours_before, refund_percent }] };
+
+        assert!(tier(-1, 50).validate().is_err());
+        assert!(tier(24, 101).validate().is_err());
+        assert!(tier(24, -1).validate().is_err());
+        assert!(tier(0, 0).validate().is_ok());
+        assert!(tier(24, 100).validate().is_ok());
+
+        let too_many = CancellationPolicy { tiers: (0..11).map(|hours| CancellationTier { min_hours_before: hours, refund_percent: 10 }).collect() };
+        assert!(too_many.validate().is_err());
+    }
+}
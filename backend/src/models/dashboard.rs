@@ -1,10 +1,11 @@
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 use serde_with::DisplayFromStr;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, FixedOffset};
 use uuid::Uuid;
 use sqlx::types::BigDecimal;
 use crate::models::portfolio::{PortfolioItem, CreatePortfolioRequest};
+use crate::models::booking::BookingLineItem;
 
 #[serde_as]
 #[derive(Debug, Serialize)]
@@ -25,10 +26,12 @@ pub struct RecentBooking {
     pub id: Uuid,
     pub customer_name: String,
     pub service_type: String,
-    pub event_date: DateTime<Utc>,
+    /// In the MUA's timezone
+    pub event_date: DateTime<FixedOffset>,
     pub status: String,
     #[serde_as(as = "DisplayFromStr")]
     pub price: BigDecimal,
+    pub line_items: Vec<BookingLineItem>,
 }
 
 #[serde_as]
@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct ExternalCalendar {
+    pub id: Uuid,
+    pub mua_id: Uuid,
+    pub name: String,
+    /// None for a calendar that was uploaded as a file
+    pub source_url: Option<String>,
+    pub last_synced_at: Option<DateTime<Utc>>,
+    /// Why the last import failed; cleared on the next successful one
+    pub last_error: Option<String>,
+    /// How many busy blocks the last import produced
+    pub busy_block_count: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AddExternalCalendarRequest {
+    pub url: String,
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UploadExternalCalendarQuery {
+    pub name: Option<String>,
+}
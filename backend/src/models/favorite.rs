@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use chrono::{DateTime, NaiveDate, Utc};
+
+use crate::models::MuaProfileResponse;
+
+#[derive(Debug, Default, Deserialize)]
+pub struct SaveFavoriteRequest {
+    /// YYYY-MM-DD; the customer is told when the MUA has openings that day
+    pub event_date: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Favorite {
+    pub mua_id: Uuid,
+    pub event_date: Option<NaiveDate>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FavoriteMua {
+    #[serde(flatten)]
+    pub mua: MuaProfileResponse,
+    pub event_date: Option<NaiveDate>,
+    pub saved_at: DateTime<Utc>,
+}
@@ -0,0 +1,16 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+
+/// A chat message between a booking's customer and MUA
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct Message {
+    pub id: Uuid,
+    pub booking_id: Option<Uuid>,
+    pub sender_id: Uuid,
+    pub receiver_id: Uuid,
+    pub content: String,
+    pub is_read: Option<bool>,
+    pub created_at: DateTime<Utc>,
+}
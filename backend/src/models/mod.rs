@@ -1,15 +1,37 @@
 pub mod user;
 pub mod mua;
 pub mod booking;
+pub mod booking_status;
 pub mod portfolio;
 pub mod review;
 pub mod dashboard;
 pub mod availability;
+pub mod cancellation;
+pub mod reschedule;
+pub mod quote;
+pub mod travel;
+pub mod external_calendar;
+pub mod time_off;
+pub mod favorite;
+pub mod offering;
+pub mod pagination;
+pub mod message;
 
 pub use user::*;
 pub use mua::*;
 pub use booking::*;
+pub use booking_status::*;
 pub use portfolio::*;
 pub use review::*;
 pub use dashboard::*;
-pub use availability::*;
\ No newline at end of file
+pub use availability::*;
+pub use cancellation::*;
+pub use reschedule::*;
+pub use quote::*;
+pub use travel::*;
+pub use external_calendar::*;
+pub use time_off::*;
+pub use favorite::*;
+pub use offering::*;
+pub use pagination::*;
+pub use message::*;
\ No newline at end of file
@@ -1,7 +1,11 @@
 use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use serde_with::DisplayFromStr;
+use sqlx::types::BigDecimal;
 use sqlx::FromRow;
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
 use crate::models::User;
 
 #[derive(Debug, Serialize, Deserialize, FromRow)]
@@ -17,6 +21,8 @@ pub struct MuaProfile {
     pub is_available: bool,
     pub average_rating: Option<f64>,
     pub total_reviews: Option<i32>,
+    /// IANA zone the MUA's availability is written in
+    pub timezone: String,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -33,8 +39,12 @@ pub struct CreateMuaProfileRequest {
     pub profile_picture_base64: Option<String>,
     #[serde(default)]
     pub profile_picture_url: Option<String>,
+    /// IANA zone name, defaults to Asia/Jakarta
+    #[serde(default)]
+    pub timezone: Option<String>,
 }
 
+#[serde_as]
 #[derive(Debug, Serialize)]
 pub struct MuaProfileResponse {
     pub id: Uuid,
@@ -48,11 +58,47 @@ pub struct MuaProfileResponse {
     pub is_available: bool,
     pub average_rating: Option<f64>,
     pub total_reviews: Option<i32>,
+    pub timezone: String,
+    /// Cheapest service the MUA offers, per person; MUAs without listed services fall back to
+    /// the cheapest they've delivered on completed bookings. Null when neither is known.
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub starting_price: Option<BigDecimal>,
     pub created_at: DateTime<Utc>,
+    /// Whether the signed-in user saved this MUA; left out for anonymous requests
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_favorite: Option<bool>,
+    /// Name, specialization, bio and portfolio snippets matching the search `q`, in that order,
+    /// HTML-escaped with the matched terms wrapped in `<mark>`; left out when there was no `q`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub highlights: Option<Vec<String>>,
+}
+
+/// A MUA's zone and its current offset from UTC
+#[derive(Debug, Serialize)]
+pub struct MuaTimezone {
+    pub timezone: String,
+    /// Like `+07:00`; can change with daylight saving in zones that have it
+    pub utc_offset: String,
+}
+
+impl MuaTimezone {
+    pub fn new(timezone: Tz) -> Self {
+        Self {
+            timezone: timezone.name().to_string(),
+            utc_offset: Utc::now().with_timezone(&timezone).format("%:z").to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateTimezoneRequest {
+    pub timezone: String,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct SearchMuasRequest {
+    /// Free text matched against name, specialization, bio and portfolio
+    pub q: Option<String>,
     pub location: Option<String>,
     pub latitude: Option<f64>,
     pub longitude: Option<f64>,
@@ -60,6 +106,24 @@ pub struct SearchMuasRequest {
     pub date: Option<String>,
     pub specialization: Option<String>,
     pub min_rating: Option<f64>,
+    /// Budget bounds on the starting price; MUAs without any known price are left out
+    pub min_price: Option<f64>,
+    pub max_price: Option<f64>,
+    /// Only MUAs offering this category, which `starting_price` is then given for
+    pub service_category: Option<String>,
+    pub min_experience_years: Option<i32>,
+    #[serde(default)]
+    pub sort: Option<MuaSearchSort>,
     pub page: Option<i32>,
     pub limit: Option<i32>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MuaSearchSort {
+    /// Best match for `q` first; the same as `rating` without one
+    Relevance,
+    Rating,
+    PriceAsc,
+    PriceDesc,
 }
\ No newline at end of file
@@ -0,0 +1,40 @@
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use serde_with::DisplayFromStr;
+use sqlx::types::BigDecimal;
+use sqlx::FromRow;
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+
+/// Longest category and name an offering can have, matching the columns
+pub const MAX_OFFERING_CATEGORY_LENGTH: usize = 100;
+pub const MAX_OFFERING_NAME_LENGTH: usize = 255;
+/// Prices are stored as DECIMAL(10, 2)
+pub const MAX_OFFERING_PRICE: i64 = 100_000_000;
+
+/// A service a MUA offers at a set price per person
+#[serde_as]
+#[derive(Debug, Serialize, FromRow)]
+pub struct ServiceOffering {
+    pub id: Uuid,
+    pub mua_id: Uuid,
+    /// Free text such as `bridal`, matched case-insensitively by search
+    pub category: String,
+    pub name: String,
+    pub description: Option<String>,
+    #[serde_as(as = "DisplayFromStr")]
+    pub price: BigDecimal,
+    pub duration_minutes: i32,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Used to create an offering and to replace one
+#[derive(Debug, Deserialize)]
+pub struct SaveServiceOfferingRequest {
+    pub category: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub price: String,
+    pub duration_minutes: i32,
+}
@@ -0,0 +1,246 @@
+use anyhow::Result;
+use base64::{Engine as _, engine::general_purpose};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Postgres, QueryBuilder};
+use uuid::Uuid;
+
+/// Items per page when the client doesn't ask for a number
+pub const DEFAULT_PAGE_LIMIT: i64 = 20;
+/// Most items a client can ask for in one page
+pub const MAX_PAGE_LIMIT: i64 = 100;
+/// Sort key of lists ordered by when their items were created, latest first
+pub const NEWEST_FIRST: &str = "newest";
+/// Sort key of lists ordered by when their items were created, earliest first
+pub const OLDEST_FIRST: &str = "oldest";
+
+/// One page of a list endpoint.
+///
+/// Lists paged by number fill in `page`; lists paged by keyset fill in `next_cursor`
+/// instead, which is passed back as `cursor` to get the following page.
+///
+/// The dashboard calendar (`/dashboard/calendar/bookings`, `/dashboard/availability/slots`)
+/// still returns plain lists: it covers a requested date range or the MUA's own schedule,
+/// and needs every entry in it to lay out buffers and free time.
+#[derive(Debug, Serialize)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    /// Items across all pages, ignoring `page` and `cursor`
+    pub total: i64,
+    pub page: Option<i64>,
+    pub limit: i64,
+    /// None on the last page
+    pub next_cursor: Option<String>,
+}
+
+impl<T> Page<T> {
+    /// A numbered page, 1-based
+    pub fn numbered(items: Vec<T>, total: i64, page: i64, limit: i64) -> Self {
+        Self { items, total, page: Some(page), limit, next_cursor: None }
+    }
+
+    /// A keyset page from up to `limit + 1` rows; the extra row only says there is a next page
+    pub fn keyset(mut items: Vec<T>, total: i64, limit: i64, cursor_of: impl Fn(&T) -> Cursor) -> Self {
+        let has_more = items.len() as i64 > limit;
+        items.truncate(limit as usize);
+
+        let next_cursor = if has_more {
+            items.last().map(|item| cursor_of(item).encode())
+        } else {
+            None
+        };
+
+        Self { items, total, page: None, limit, next_cursor }
+    }
+}
+
+/// `limit` and `cursor` for keyset-paged lists
+#[derive(Debug, Default, Deserialize)]
+pub struct PageQuery {
+    pub cursor: Option<String>,
+    pub limit: Option<i64>,
+}
+
+impl PageQuery {
+    pub fn limit(&self) -> i64 {
+        clamp_limit(self.limit)
+    }
+
+    /// The cursor to continue from, which has to come from a list in the same `sort`
+    pub fn cursor(&self, sort: &str) -> Result<Option<Cursor>> {
+        let Some(cursor) = self.cursor.as_deref().filter(|cursor| !cursor.is_empty()) else {
+            return Ok(None);
+        };
+
+        let cursor = Cursor::decode(cursor)?;
+        if cursor.sort != sort {
+            return Err(anyhow::anyhow!("Invalid cursor: it belongs to a different sort order, start again without it"));
+        }
+        Ok(Some(cursor))
+    }
+
+    /// Continue after the cursor, order by `sort_column` with `id_column` breaking ties and
+    /// fetch one row past the limit for `Page::keyset`; `sql` must already have its WHERE clause
+    pub fn push_keyset(&self, sql: &mut QueryBuilder<'_, Postgres>, sort: &str, sort_column: &str, id_column: &str, descending: bool) -> Result<()> {
+        let (comparison, direction) = if descending { ("<", "DESC") } else { (">", "ASC") };
+
+        if let Some(after) = self.cursor(sort)? {
+            sql.push(format!(" AND ({}, {}) {} (", sort_column, id_column, comparison));
+            sql.push_bind(after.at);
+            sql.push(", ");
+            sql.push_bind(after.id);
+            sql.push(")");
+        }
+        sql.push(format!(" ORDER BY {} {}, {} {} LIMIT ", sort_column, direction, id_column, direction));
+        sql.push_bind(self.limit() + 1);
+
+        Ok(())
+    }
+}
+
+/// `page` and `limit` for lists paged by number, for orders a cursor can't follow
+#[derive(Debug, Default, Deserialize)]
+pub struct PageNumberQuery {
+    pub page: Option<i64>,
+    pub limit: Option<i64>,
+}
+
+impl PageNumberQuery {
+    /// 1-based
+    pub fn page(&self) -> i64 {
+        self.page.unwrap_or(1).max(1)
+    }
+
+    pub fn limit(&self) -> i64 {
+        clamp_limit(self.limit)
+    }
+
+    pub fn offset(&self) -> i64 {
+        (self.page() - 1) * self.limit()
+    }
+}
+
+/// `limit` within 1..=MAX_PAGE_LIMIT, defaulting to DEFAULT_PAGE_LIMIT
+pub fn clamp_limit(limit: Option<i64>) -> i64 {
+    limit.unwrap_or(DEFAULT_PAGE_LIMIT).clamp(1, MAX_PAGE_LIMIT)
+}
+
+/// Where a keyset page ended: the list's sort key, the last item's sort timestamp
+/// and its id to break ties
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cursor {
+    pub sort: String,
+    pub at: DateTime<Utc>,
+    pub id: Uuid,
+}
+
+impl Cursor {
+    pub fn new(sort: &str, at: DateTime<Utc>, id: Uuid) -> Self {
+        Self { sort: sort.to_string(), at, id }
+    }
+
+    /// Opaque to clients; they only hand it back
+    pub fn encode(&self) -> String {
+        general_purpose::URL_SAFE_NO_PAD.encode(format!("{}|{}|{}", self.sort, self.at.to_rfc3339(), self.id))
+    }
+
+    pub fn decode(value: &str) -> Result<Self> {
+        let invalid = || anyhow::anyhow!("Invalid cursor");
+
+        let decoded = general_purpose::URL_SAFE_NO_PAD.decode(value).map_err(|_| invalid())?;
+        let decoded = String::from_utf8(decoded).map_err(|_| invalid())?;
+        let mut parts = decoded.splitn(3, '|');
+        let (Some(sort), Some(at), Some(id)) = (parts.next(), parts.next(), parts.next()) else {
+            return Err(invalid());
+        };
+        if sort.is_empty() {
+            return Err(invalid());
+        }
+
+        Ok(Self {
+            sort: sort.to_string(),
+            at: DateTime::parse_from_rfc3339(at).map_err(|_| invalid())?.with_timezone(&Utc),
+            id: Uuid::parse_str(id).map_err(|_| invalid())?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cursor() -> Cursor {
+        let at = DateTime::parse_from_rfc3339("2026-06-01T12:30:00.123456Z").unwrap().with_timezone(&Utc);
+        Cursor::new(NEWEST_FIRST, at, Uuid::parse_str("6f1c1a52-8a57-4a5e-9d1c-2c4a0e6f9b11").unwrap())
+    }
+
+    fn encoded(raw: &str) -> String {
+        general_purpose::URL_SAFE_NO_PAD.encode(raw)
+    }
+
+    #[test]
+    fn cursor_survives_a_round_trip() {
+        assert_eq!(Cursor::decode(&cursor().encode()).unwrap(), cursor());
+    }
+
+    #[test]
+    fn encoded_cursor_is_safe_in_a_query_string() {
+        let value = cursor().encode();
+        assert!(value.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'), "{}", value);
+    }
+
+    #[test]
+    fn rejects_malformed_cursors() {
+        let malformed = [
+            "not base64!".to_string(),
+            general_purpose::URL_SAFE_NO_PAD.encode([0xff, 0xfe, 0xfd]),
+            encoded("newest|2026-06-01T12:30:00Z"),
+            encoded("2026-06-01T12:30:00Z|6f1c1a52-8a57-4a5e-9d1c-2c4a0e6f9b11"),
+            encoded("|2026-06-01T12:30:00Z|6f1c1a52-8a57-4a5e-9d1c-2c4a0e6f9b11"),
+            encoded("newest|yesterday|6f1c1a52-8a57-4a5e-9d1c-2c4a0e6f9b11"),
+            encoded("newest|2026-06-01T12:30:00Z|not-a-uuid"),
+            encoded("||"),
+        ];
+
+        for value in malformed {
+            let error = Cursor::decode(&value).unwrap_err();
+            assert_eq!(error.to_string(), "Invalid cursor", "{:?} was accepted", value);
+        }
+    }
+
+    #[test]
+    fn empty_cursor_starts_from_the_first_page() {
+        let query = PageQuery { cursor: Some(String::new()), limit: None };
+        assert_eq!(query.cursor(NEWEST_FIRST).unwrap(), None);
+    }
+
+    #[test]
+    fn cursor_only_continues_the_sort_it_came_from() {
+        let query = PageQuery { cursor: Some(cursor().encode()), limit: None };
+        assert_eq!(query.cursor(NEWEST_FIRST).unwrap(), Some(cursor()));
+
+        let error = query.cursor("event_date_asc").unwrap_err();
+        assert!(error.to_string().starts_with("Invalid cursor"), "{}", error);
+    }
+
+    #[test]
+    fn keyset_page_only_has_a_cursor_when_there_are_more_rows() {
+        let rows: Vec<Cursor> = (0..3).map(|minutes| Cursor::new(NEWEST_FIRST, cursor().at + chrono::Duration::minutes(minutes), Uuid::new_v4())).collect();
+
+        let page = Page::keyset(rows.clone(), 3, 2, |row| row.clone());
+        assert_eq!(page.items.len(), 2);
+        assert_eq!(page.next_cursor, Some(rows[1].encode()));
+
+        let last = Page::keyset(rows[2..].to_vec(), 3, 2, |row| row.clone());
+        assert_eq!(last.next_cursor, None);
+    }
+
+    #[test]
+    fn page_numbers_and_limits_are_kept_in_range() {
+        let query = PageNumberQuery { page: Some(0), limit: Some(1_000) };
+        assert_eq!((query.page(), query.limit(), query.offset()), (1, MAX_PAGE_LIMIT, 0));
+
+        let query = PageNumberQuery { page: Some(3), limit: None };
+        assert_eq!(query.offset(), 2 * DEFAULT_PAGE_LIMIT);
+    }
+}
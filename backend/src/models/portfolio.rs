@@ -10,6 +10,8 @@ pub struct PortfolioItem {
     pub title: String,
     pub description: Option<String>,
     pub image_url: String,
+    pub thumbnail_url: Option<String>,
+    pub medium_url: Option<String>,
     pub service_type: Option<String>,
     pub created_at: DateTime<Utc>,
 }
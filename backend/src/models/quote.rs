@@ -0,0 +1,42 @@
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use serde_with::DisplayFromStr;
+use sqlx::{FromRow, types::BigDecimal};
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "quote_status", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum QuoteStatus {
+    /// Waiting for the other party
+    Open,
+    Accepted,
+    /// Answered with a new offer
+    Countered,
+    /// The booking was cancelled or expired before anyone agreed
+    Closed,
+}
+
+#[serde_as]
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct BookingQuote {
+    pub id: Uuid,
+    pub booking_id: Uuid,
+    pub proposed_by: Uuid,
+    #[serde_as(as = "DisplayFromStr")]
+    pub price: BigDecimal,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub deposit_amount: Option<BigDecimal>,
+    pub notes: Option<String>,
+    pub status: QuoteStatus,
+    pub responded_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateQuoteRequest {
+    pub price: String,
+    pub deposit_amount: Option<String>,
+    pub notes: Option<String>,
+}
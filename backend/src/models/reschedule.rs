@@ -0,0 +1,40 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "reschedule_status", rename_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
+pub enum RescheduleStatus {
+    Pending,
+    Accepted,
+    Declined,
+    /// Taken back by the party who proposed it
+    Withdrawn,
+}
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct RescheduleRequest {
+    pub id: Uuid,
+    pub booking_id: Uuid,
+    pub requested_by: Uuid,
+    pub proposed_event_date: DateTime<Utc>,
+    pub proposed_duration_hours: i32,
+    pub previous_event_date: DateTime<Utc>,
+    pub previous_duration_hours: i32,
+    pub reason: Option<String>,
+    pub status: RescheduleStatus,
+    pub responded_by: Option<Uuid>,
+    pub responded_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateRescheduleRequest {
+    /// RFC 3339
+    pub event_date: String,
+    /// Defaults to the booking's current duration
+    pub duration_hours: Option<i32>,
+    pub reason: Option<String>,
+}
@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use chrono::{DateTime, FixedOffset, NaiveDate, Utc};
+
+/// Longest reason a MUA can give for time off
+pub const MAX_TIME_OFF_REASON_LENGTH: usize = 500;
+
+#[derive(Debug, Serialize, Deserialize, FromRow)]
+pub struct TimeOff {
+    pub id: Uuid,
+    pub mua_id: Uuid,
+    /// First and last day off, inclusive, in the MUA's timezone
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CreateTimeOffRequest {
+    /// YYYY-MM-DD
+    pub start_date: String,
+    /// YYYY-MM-DD, the last day off
+    pub end_date: String,
+    pub reason: Option<String>,
+}
+
+/// A confirmed booking that falls inside new time off; it stays booked until someone cancels or moves it
+#[derive(Debug, Serialize)]
+pub struct TimeOffConflict {
+    pub booking_id: Uuid,
+    pub customer_name: String,
+    pub service_type: String,
+    /// In the MUA's timezone
+    pub event_date: DateTime<FixedOffset>,
+    pub duration_hours: i32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct TimeOffCreated {
+    #[serde(flatten)]
+    pub time_off: TimeOff,
+    pub conflicting_bookings: Vec<TimeOffConflict>,
+}
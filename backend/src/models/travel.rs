@@ -0,0 +1,121 @@
+use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
+use serde_with::DisplayFromStr;
+use sqlx::types::BigDecimal;
+
+/// What a MUA charges to travel to an event, measured in a straight line from their base location.
+///
+/// The first `free_radius_km` are free, every km after that costs `fee_per_km`,
+/// and events further than `max_distance_km` can't be booked.
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TravelPricing {
+    pub free_radius_km: f64,
+    #[serde_as(as = "DisplayFromStr")]
+    pub fee_per_km: BigDecimal,
+    pub max_distance_km: Option<f64>,
+}
+
+impl TravelPricing {
+    pub fn validate(&self) -> anyhow::Result<()> {
+        if !self.free_radius_km.is_finite() || self.free_radius_km < 0.0 {
+            return Err(anyhow::anyhow!("Invalid travel pricing: free_radius_km cannot be negative"));
+        }
+        if self.fee_per_km < BigDecimal::default() {
+            return Err(anyhow::anyhow!("Invalid travel pricing: fee_per_km cannot be negative"));
+        }
+        if let Some(max_distance_km) = self.max_distance_km {
+            if !max_distance_km.is_finite() || max_distance_km <= 0.0 {
+                return Err(anyhow::anyhow!("Invalid travel pricing: max_distance_km must be positive"));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Whether any of the settings depend on where the event is
+    pub fn needs_event_location(&self) -> bool {
+        self.fee_per_km > BigDecimal::default() || self.max_distance_km.is_some()
+    }
+
+    /// The fee for an event `distance_km` away, or an error if it's out of range
+    pub fn fee_for(&self, distance_km: f64) -> anyhow::Result<BigDecimal> {
+        if self.max_distance_km.is_some_and(|max_distance_km| distance_km > max_distance_km) {
+            return Err(anyhow::anyhow!(
+                "Event location too far: the MUA travels up to {} km",
+                self.max_distance_km.unwrap_or_default()
+            ));
+        }
+
+        let chargeable_km = (distance_km - self.free_radius_km).max(0.0);
+        let chargeable_km = BigDecimal::try_from(chargeable_km).unwrap_or_default().round(2);
+
+        Ok((chargeable_km * &self.fee_per_km).round(2))
+    }
+}
+
+/// The travel part of a new booking's price
+#[derive(Debug, Clone, Default)]
+pub struct TravelFee {
+    /// None when the event or the MUA has no coordinates
+    pub distance_km: Option<f64>,
+    pub fee: BigDecimal,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateTravelPricingRequest {
+    pub free_radius_km: f64,
+    pub fee_per_km: String,
+    pub max_distance_km: Option<f64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn pricing(free_radius_km: f64, fee_per_km: &str, max_distance_km: Option<f64>) -> TravelPricing {
+        TravelPricing { free_radius_km, fee_per_km: BigDecimal::from_str(fee_per_km).unwrap(), max_distance_km }
+    }
+
+    #[test]
+    fn inside_the_free_radius_costs_nothing() {
+        let pricing = pricing(10.0, "5000", None);
+
+        assert_eq!(pricing.fee_for(0.0).unwrap(), BigDecimal::from(0));
+        assert_eq!(pricing.fee_for(10.0).unwrap(), BigDecimal::from(0));
+    }
+
+    #[test]
+    fn charges_only_the_km_past_the_free_radius() {
+        let pricing = pricing(10.0, "5000", None);
+
+        assert_eq!(pricing.fee_for(25.0).unwrap(), BigDecimal::from(75_000));
+        assert_eq!(pricing.fee_for(10.5).unwrap(), BigDecimal::from(2_500));
+    }
+
+    #[test]
+    fn rounds_the_chargeable_distance_and_fee_to_cents() {
+        let pricing = pricing(0.0, "1000.50", None);
+        // 1.23 km at 1000.50 is 1230.615
+        assert_eq!(pricing.fee_for(1.234567).unwrap(), BigDecimal::from_str("1230.62").unwrap());
+    }
+
+    #[test]
+    fn rejects_events_past_the_max_distance() {
+        let pricing = pricing(10.0, "5000", Some(50.0));
+
+        assert_eq!(pricing.fee_for(50.0).unwrap(), BigDecimal::from(200_000));
+        let error = pricing.fee_for(50.1).unwrap_err();
+        assert!(error.to_string().starts_with("Event location too far"), "{}", error);
+    }
+
+    #[test]
+    fn rejects_negative_or_non_finite_settings() {
+        assert!(pricing(-1.0, "5000", None).validate().is_err());
+        assert!(pricing(f64::NAN, "5000", None).validate().is_err());
+        assert!(pricing(10.0, "-1", None).validate().is_err());
+        assert!(pricing(10.0, "5000", Some(0.0)).validate().is_err());
+        assert!(pricing(0.0, "0", Some(25.0)).validate().is_ok());
+    }
+}
@@ -1,11 +1,11 @@
 use anyhow::Result;
 use async_trait::async_trait;
 use uuid::Uuid;
-use sqlx::{PgPool, query, query_as, types::BigDecimal};
+use sqlx::{PgPool, Postgres, QueryBuilder, Transaction, query, query_as, types::{BigDecimal, Json}};
 use chrono::{DateTime, Utc};
 use std::str::FromStr;
 
-use crate::models::{Booking, CreateBookingRequest, UpdateBookingStatusRequest, BookingStatus};
+use crate::models::{Booking, BookingFilter, BookingLineItem, BookingParticipant, BookingSort, BookingTimeframe, CancellationPolicy, Cursor, CreateBookingRequest, TravelFee, UpdateBookingStatusRequest, BookingStatus};
 use super::traits::BookingRepository;
 
 #[derive(Debug, Clone)]
@@ -25,15 +25,23 @@ impl Default for BookingRepositoryImpl {
 
 #[async_trait]
 impl BookingRepository for BookingRepositoryImpl {
-    async fn create_booking(&self, pool: &PgPool, booking_data: &CreateBookingRequest, customer_id: Uuid) -> Result<Booking> {
+    async fn create_booking(
+        &self,
+        pool: &PgPool,
+        booking_data: &CreateBookingRequest,
+        customer_id: Uuid,
+        response_deadline: DateTime<Utc>,
+        cancellation_policy: &CancellationPolicy,
+        travel_fee: &TravelFee,
+    ) -> Result<Booking> {
         // Parse the event date string
         let event_date = DateTime::parse_from_rfc3339(&booking_data.event_date)
             .map_err(|_| anyhow::anyhow!("Invalid date format"))?
             .with_timezone(&Utc);
 
-        // Parse price string to BigDecimal
+        // Parse price string to BigDecimal; the travel fee comes on top of the services
         let price = BigDecimal::from_str(&booking_data.price)
-            .map_err(|_| anyhow::anyhow!("Invalid price format"))?;
+            .map_err(|_| anyhow::anyhow!("Invalid price format"))? + &travel_fee.fee;
 
         // Parse deposit amount if provided
         let deposit_amount = booking_data.deposit_amount.as_ref()
@@ -41,17 +49,26 @@ impl BookingRepository for BookingRepositoryImpl {
             .transpose()
             .map_err(|_| anyhow::anyhow!("Invalid deposit amount format"))?;
 
+        // The booking and its line items are written together
+        let mut tx = pool.begin().await?;
+
         let booking_row = query_as::<_, Booking>(
             r#"
             INSERT INTO bookings (
                 customer_id, mua_id, service_type, description, event_date,
                 event_location, duration_hours, price, status,
-                deposit_amount, deposit_paid, final_payment_paid, created_at, updated_at
+                deposit_amount, deposit_paid, final_payment_paid, response_deadline,
+                cancellation_policy, event_latitude, event_longitude, travel_distance_km,
+                travel_fee, created_at, updated_at
             )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, NOW(), NOW())
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, NOW(), NOW())
             RETURNING id, customer_id, mua_id, service_type, description, event_date,
                       event_location, duration_hours, price, status,
-                      deposit_amount, deposit_paid, final_payment_paid, created_at, updated_at
+                      deposit_amount, deposit_paid, final_payment_paid,
+                      response_deadline, cancellation_reason, completed_at,
+                      review_window_closes_at, cancellation_policy, refund_amount,
+                      refund_reference, refunded_at, event_latitude, event_longitude,
+                      travel_distance_km, travel_fee, created_at, updated_at
             "#
         )
         .bind(customer_id)
@@ -66,12 +83,50 @@ impl BookingRepository for BookingRepositoryImpl {
         .bind(deposit_amount)
         .bind(false)
         .bind(false)
-        .fetch_one(pool)
+        .bind(response_deadline)
+        .bind(Json(cancellation_policy))
+        .bind(booking_data.event_latitude)
+        .bind(booking_data.event_longitude)
+        .bind(travel_fee.distance_km)
+        .bind(&travel_fee.fee)
+        .fetch_one(&mut tx)
         .await?;
 
+        for (position, item) in booking_data.line_items.iter().enumerate() {
+            let unit_price = BigDecimal::from_str(&item.unit_price)
+                .map_err(|_| anyhow::anyhow!("Invalid unit price format"))?;
+
+            query(
+                r#"
+                INSERT INTO booking_line_items (booking_id, service_type, person_count, unit_price, minutes_per_person, position, created_at)
+                VALUES ($1, $2, $3, $4, $5, $6, NOW())
+                "#
+            )
+            .bind(booking_row.id)
+            .bind(&item.service_type)
+            .bind(item.person_count)
+            .bind(unit_price)
+            .bind(item.minutes_per_person)
+            .bind(position as i32)
+            .execute(&mut tx)
+            .await?;
+        }
+
+        tx.commit().await?;
         Ok(booking_row)
     }
 
+    async fn find_line_items(&self, pool: &PgPool, booking_ids: &[Uuid]) -> Result<Vec<BookingLineItem>> {
+        let line_items = query_as::<_, BookingLineItem>(
+            "SELECT * FROM booking_line_items WHERE booking_id = ANY($1) ORDER BY booking_id, position"
+        )
+        .bind(booking_ids)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(line_items)
+    }
+
     async fn find_booking_by_id(&self, pool: &PgPool, booking_id: Uuid) -> Result<Option<Booking>> {
         let booking = query_as::<_, Booking>("SELECT * FROM bookings WHERE id = $1")
             .bind(booking_id)
@@ -81,33 +136,171 @@ impl BookingRepository for BookingRepositoryImpl {
         Ok(booking)
     }
 
-    async fn find_bookings_by_customer(&self, pool: &PgPool, customer_id: Uuid) -> Result<Vec<Booking>> {
-        let bookings = query_as::<_, Booking>("SELECT * FROM bookings WHERE customer_id = $1 ORDER BY created_at DESC")
-            .bind(customer_id)
+    async fn find_bookings(&self, pool: &PgPool, participant: BookingParticipant, filter: &BookingFilter, after: Option<Cursor>, limit: i64) -> Result<Vec<Booking>> {
+        let mut sql = QueryBuilder::<Postgres>::new("SELECT * FROM bookings WHERE ");
+        push_participant(&mut sql, participant);
+        push_filter(&mut sql, filter);
+
+        let (sort_column, direction, comparison) = match filter.sort {
+            BookingSort::Newest => ("created_at", "DESC", "<"),
+            BookingSort::EventDateAsc => ("event_date", "ASC", ">"),
+            BookingSort::EventDateDesc => ("event_date", "DESC", "<"),
+        };
+
+        if let Some(after) = after {
+            sql.push(format!(" AND ({}, id) {} (", sort_column, comparison));
+            sql.push_bind(after.at);
+            sql.push(", ");
+            sql.push_bind(after.id);
+            sql.push(")");
+        }
+
+        sql.push(format!(" ORDER BY {0} {1}, id {1} LIMIT ", sort_column, direction));
+        sql.push_bind(limit);
+
+        let bookings = sql.build_query_as::<Booking>()
             .fetch_all(pool)
             .await?;
 
         Ok(bookings)
     }
 
-    async fn find_bookings_by_mua(&self, pool: &PgPool, mua_id: Uuid) -> Result<Vec<Booking>> {
-        let bookings = query_as::<_, Booking>("SELECT * FROM bookings WHERE mua_id = $1 ORDER BY created_at DESC")
-            .bind(mua_id)
-            .fetch_all(pool)
+    async fn count_bookings(&self, pool: &PgPool, participant: BookingParticipant, filter: &BookingFilter) -> Result<i64> {
+        let mut sql = QueryBuilder::<Postgres>::new("SELECT COUNT(*) FROM bookings WHERE ");
+        push_participant(&mut sql, participant);
+        push_filter(&mut sql, filter);
+
+        let (total,): (i64,) = sql.build_query_as()
+            .fetch_one(pool)
             .await?;
 
-        Ok(bookings)
+        Ok(total)
     }
 
-    async fn update_booking_status(&self, pool: &PgPool, booking_id: Uuid, status: BookingStatus) -> Result<Booking> {
+    async fn update_booking_status(&self, tx: &mut Transaction<'_, Postgres>, booking_id: Uuid, expected: BookingStatus, status: BookingStatus, cancellation_reason: Option<&str>) -> Result<Booking> {
+        // Guarded on the status the caller checked, so a sweeper or the other party
+        // changing it in the meantime makes this fail instead of being overwritten
         let updated_booking = query_as::<_, Booking>(
-            "UPDATE bookings SET status = $1, updated_at = NOW() WHERE id = $2 RETURNING *"
+            "UPDATE bookings SET status = $1, cancellation_reason = COALESCE($3, cancellation_reason), updated_at = NOW() WHERE id = $2 AND status = $4 RETURNING *"
         )
         .bind(status as BookingStatus)
         .bind(booking_id)
-        .fetch_one(pool)
-        .await?;
+        .bind(cancellation_reason)
+        .bind(expected as BookingStatus)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("Booking has changed: its status was updated in the meantime, reload it and try again"))?;
 
         Ok(updated_booking)
     }
-}
\ No newline at end of file
+}
+
+fn push_participant(sql: &mut QueryBuilder<'_, Postgres>, participant: BookingParticipant) {
+    match participant {
+        BookingParticipant::Customer(customer_id) => {
+            sql.push("customer_id = ");
+            sql.push_bind(customer_id);
+        }
+        BookingParticipant::Mua(mua_id) => {
+            sql.push("mua_id = ");
+            sql.push_bind(mua_id);
+        }
+    }
+}
+
+fn push_filter(sql: &mut QueryBuilder<'_, Postgres>, filter: &BookingFilter) {
+    if !filter.statuses.is_empty() {
+        sql.push(" AND status = ANY(");
+        sql.push_bind(filter.statuses.clone());
+        sql.push(")");
+    }
+
+    // Calendar days are the MUA's, like the event date shown to both sides
+    if filter.event_from.is_some() || filter.event_to.is_some() {
+        let local_date = " AND (event_date AT TIME ZONE (SELECT timezone FROM mua_profiles WHERE mua_profiles.id = bookings.mua_id))::date ";
+        if let Some(from) = filter.event_from {
+            sql.push(local_date);
+            sql.push(">= ");
+            sql.push_bind(from);
+        }
+        if let Some(to) = filter.event_to {
+            sql.push(local_date);
+            sql.push("<= ");
+            sql.push_bind(to);
+        }
+    }
+
+    match filter.timeframe {
+        Some(BookingTimeframe::Upcoming) => { sql.push(" AND event_date >= NOW()"); }
+        Some(BookingTimeframe::Past) => { sql.push(" AND event_date < NOW()"); }
+        None => {}
+    }
+
+    if let Some(service_type) = &filter.service_type {
+        sql.push(" AND (lower(service_type) = lower(");
+        sql.push_bind(service_type.clone());
+        sql.push(") OR EXISTS (SELECT 1 FROM booking_line_items li WHERE li.booking_id = bookings.id AND lower(li.service_type) = lower(");
+        sql.push_bind(service_type.clone());
+        sql.push(")))");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn insert_pending_booking(pool: &PgPool) -> Uuid {
+        let customer_id: Uuid = insert_user(pool, "customer@test.muaconnect.dev", "customer").await;
+        let mua_user_id: Uuid = insert_user(pool, "mua@test.muaconnect.dev", "mua").await;
+        let mua_id: Uuid = sqlx::query_scalar("INSERT INTO mua_profiles (user_id, location) VALUES ($1, 'Jakarta') RETURNING id")
+            .bind(mua_user_id)
+            .fetch_one(pool)
+            .await
+            .unwrap();
+
+        sqlx::query_scalar(
+            r#"
+            INSERT INTO bookings (customer_id, mua_id, service_type, event_date, event_location, duration_hours, price, status)
+            VALUES ($1, $2, 'Party Makeup', NOW() + INTERVAL '5 days', 'Jakarta', 2, 800000, 'pending')
+            RETURNING id
+            "#
+        )
+        .bind(customer_id)
+        .bind(mua_id)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+    }
+
+    async fn insert_user(pool: &PgPool, email: &str, user_type: &str) -> Uuid {
+        sqlx::query_scalar(
+            "INSERT INTO users (email, password_hash, user_type, full_name) VALUES ($1, 'x', $2::user_type, $1) RETURNING id"
+        )
+        .bind(email)
+        .bind(user_type)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn status_update_only_applies_from_the_expected_status(pool: PgPool) {
+        let repository = BookingRepositoryImpl::new();
+        let booking_id = insert_pending_booking(&pool).await;
+
+        // The expiry sweep cancels the request after the MUA loaded it but before they confirm
+        let mut tx = pool.begin().await.unwrap();
+        repository.update_booking_status(&mut tx, booking_id, BookingStatus::Pending, BookingStatus::Cancelled, Some("expired")).await.unwrap();
+        tx.commit().await.unwrap();
+
+        let mut tx = pool.begin().await.unwrap();
+        let error = repository.update_booking_status(&mut tx, booking_id, BookingStatus::Pending, BookingStatus::Confirmed, None).await
+            .unwrap_err()
+            .to_string();
+        assert!(error.starts_with("Booking has changed"), "{}", error);
+
+        let booking = repository.find_booking_by_id(&pool, booking_id).await.unwrap().unwrap();
+        assert_eq!(booking.status, BookingStatus::Cancelled);
+        assert_eq!(booking.cancellation_reason.as_deref(), Some("expired"));
+    }
+}
@@ -1,10 +1,11 @@
 use anyhow::Result;
 use async_trait::async_trait;
 use uuid::Uuid;
-use sqlx::{PgPool, query, Row};
+use sqlx::{PgPool, Postgres, QueryBuilder, query, Row};
+use sqlx::postgres::PgRow;
 use serde_json::Value;
 
-use crate::models::{MuaProfileResponse, CreateMuaProfileRequest, SearchMuasRequest, User};
+use crate::models::{clamp_limit, MuaProfileResponse, CreateMuaProfileRequest, MuaSearchSort, Page, SearchMuasRequest, User};
 use super::traits::MuaRepository;
 
 #[derive(Debug, Clone)]
@@ -24,131 +25,93 @@ impl Default for MuaRepositoryImpl {
 
 #[async_trait]
 impl MuaRepository for MuaRepositoryImpl {
-    async fn search_muas(&self, pool: &PgPool, params: &SearchMuasRequest) -> Result<Vec<MuaProfileResponse>> {
-        let mut sql = String::from(
-            "SELECT mp.id as mua_id, mp.user_id, mp.bio, mp.experience_years, mp.specialization,
-                    mp.location, mp.latitude, mp.longitude, mp.is_available, mp.average_rating,
-                    mp.total_reviews, mp.created_at as mua_created_at, mp.updated_at as mua_updated_at,
-                    u.id as user_id, u.email, u.password_hash, u.user_type, u.full_name,
-                    u.phone_number, u.profile_picture_url, u.is_verified, u.created_at, u.updated_at
-             FROM mua_profiles mp
-             JOIN users u ON mp.user_id = u.id
-             WHERE u.user_type = 'mua'"
-        );
-
-        let mut conditions = Vec::new();
-
-        if let Some(location) = &params.location {
-            if !location.is_empty() {
-                conditions.push(format!("mp.location ILIKE '%{}%'", location));
-            }
-        }
+    async fn search_muas(&self, pool: &PgPool, params: &SearchMuasRequest) -> Result<Page<MuaProfileResponse>> {
+        let search_text = params.q.as_deref()
+            .map(str::trim)
+            .filter(|q| !q.is_empty());
 
-        if let Some(min_rating) = params.min_rating {
-            if min_rating > 0.0 {
-                conditions.push(format!("mp.average_rating >= {}", min_rating));
-            }
-        }
+        let service_category = params.service_category.as_deref()
+            .map(str::trim)
+            .filter(|category| !category.is_empty());
 
-        if let Some(specialization) = &params.specialization {
-            if !specialization.is_empty() {
-                conditions.push(format!("mp.specialization @> ARRAY['{}']", specialization));
-            }
-        }
+        let mut sql = QueryBuilder::<Postgres>::new("SELECT ");
+        sql.push(MUA_PROFILE_COLUMNS);
 
-        if !conditions.is_empty() {
-            sql.push_str(" AND ");
-            sql.push_str(&conditions.join(" AND "));
+        if search_text.is_some() {
+            // The query is unaccented, so the text has to be too for "cafe" to mark "café"
+            for (text, column, highlight_all) in HIGHLIGHTS {
+                sql.push(format!(", ts_headline('simple', immutable_unaccent(COALESCE({}, '')), search.query, ", text));
+                sql.push_bind(headline_options(highlight_all));
+                sql.push(format!(") AS {}", column));
+            }
         }
 
-        sql.push_str(" ORDER BY mp.average_rating DESC NULLS LAST");
+        push_search_filters(&mut sql, params, search_text, service_category, true);
 
-        if let Some(limit) = params.limit {
-            sql.push_str(&format!(" LIMIT {}", limit));
+        let sort = params.sort.unwrap_or(if search_text.is_some() { MuaSearchSort::Relevance } else { MuaSearchSort::Rating });
+        match sort {
+            MuaSearchSort::Relevance if search_text.is_some() => {
+                // Relevance, normalised to 0..1, counts for most; rating out of 5 breaks near-ties
+                sql.push(
+                    " ORDER BY ts_rank_cd(mp.search_document, search.query, 32) * 0.7
+                        + COALESCE(mp.average_rating, 0) / 5 * 0.3 DESC,
+                      mp.average_rating DESC NULLS LAST"
+                );
+            }
+            MuaSearchSort::Relevance | MuaSearchSort::Rating => {
+                sql.push(" ORDER BY mp.average_rating DESC NULLS LAST");
+            }
+            MuaSearchSort::PriceAsc => {
+                sql.push(" ORDER BY pricing.starting_price ASC NULLS LAST, mp.average_rating DESC NULLS LAST");
+            }
+            MuaSearchSort::PriceDesc => {
+                sql.push(" ORDER BY pricing.starting_price DESC NULLS LAST, mp.average_rating DESC NULLS LAST");
+            }
         }
 
-        if let Some(page) = params.page {
-            let offset = (page - 1) * params.limit.unwrap_or(12);
-            sql.push_str(&format!(" OFFSET {}", offset));
-        }
+        let page = i64::from(params.page.unwrap_or(1).max(1));
+        let limit = clamp_limit(params.limit.map(i64::from));
+        sql.push(" LIMIT ");
+        sql.push_bind(limit);
+        sql.push(" OFFSET ");
+        sql.push_bind((page - 1) * limit);
 
-        let rows = query(&sql)
+        let rows = sql.build()
             .fetch_all(pool)
             .await?;
 
-        let results = rows.into_iter().map(|row| -> anyhow::Result<MuaProfileResponse> {
-            Ok(MuaProfileResponse {
-                id: row.get("mua_id"),
-                user: User {
-                    id: row.get("user_id"),
-                    email: row.get("email"),
-                    password_hash: row.get("password_hash"),
-                    user_type: row.get("user_type"),
-                    full_name: row.get("full_name"),
-                    phone_number: row.get("phone_number"),
-                    profile_picture_url: row.get("profile_picture_url"),
-                    is_verified: row.get("is_verified"),
-                    created_at: row.get("created_at"),
-                    updated_at: row.get("updated_at"),
-                },
-                bio: row.get("bio"),
-                experience_years: row.get("experience_years"),
-                specialization: row.get("specialization"),
-                location: row.get("location"),
-                latitude: row.get::<Option<sqlx::types::BigDecimal>, _>("latitude")
-                    .map(|bd| bd.to_string().parse::<f64>().unwrap_or(0.0)),
-                longitude: row.get::<Option<sqlx::types::BigDecimal>, _>("longitude")
-                    .map(|bd| bd.to_string().parse::<f64>().unwrap_or(0.0)),
-                is_available: row.get("is_available"),
-                average_rating: row.get("average_rating"),
-                total_reviews: row.get("total_reviews"),
-                created_at: row.get("mua_created_at"),
-            })
-        }).collect::<Result<Vec<_>, _>>()?;
-
-        Ok(results)
+        let results = rows.into_iter().map(|row| {
+            let mut mua = mua_profile_from_row(&row);
+            mua.highlights = search_text.map(|_| {
+                HIGHLIGHTS.iter()
+                    .filter_map(|(_, column, _)| render_highlight(row.get(*column)))
+                    .collect()
+            });
+            mua
+        }).collect();
+
+        let mut count = QueryBuilder::<Postgres>::new("SELECT COUNT(*)");
+        push_search_filters(&mut count, params, search_text, service_category, false);
+        let (total,): (i64,) = count.build_query_as()
+            .fetch_one(pool)
+            .await?;
+
+        Ok(Page::numbered(results, total, page, limit))
     }
 
     async fn get_mua_by_id(&self, pool: &PgPool, mua_id: Uuid) -> Result<Option<MuaProfileResponse>> {
-        let row = query(
-            "SELECT mp.*, u.* FROM mua_profiles mp
-             JOIN users u ON mp.user_id = u.id
-             WHERE mp.id = $1"
-        )
-        .bind(mua_id)
-        .fetch_optional(pool)
-        .await?;
+        let mut sql = QueryBuilder::<Postgres>::new("SELECT ");
+        sql.push(MUA_PROFILE_COLUMNS);
+        sql.push(" FROM mua_profiles mp JOIN users u ON mp.user_id = u.id");
+        push_pricing_join(&mut sql, None);
+        sql.push(" WHERE mp.id = ");
+        sql.push_bind(mua_id);
 
-        match row {
-            Some(row) => Ok(Some(MuaProfileResponse {
-                id: row.get("id"),
-                user: User {
-                    id: row.get("user_id"),
-                    email: row.get("email"),
-                    password_hash: row.get("password_hash"),
-                    user_type: row.get("user_type"),
-                    full_name: row.get("full_name"),
-                    phone_number: row.get("phone_number"),
-                    profile_picture_url: row.get("profile_picture_url"),
-                    is_verified: row.get("is_verified"),
-                    created_at: row.get("created_at"),
-                    updated_at: row.get("updated_at"),
-                },
-                bio: row.get("bio"),
-                experience_years: row.get("experience_years"),
-                specialization: row.get("specialization"),
-                location: row.get("location"),
-                latitude: row.get::<Option<sqlx::types::BigDecimal>, _>("latitude")
-                    .map(|bd| bd.to_string().parse::<f64>().unwrap_or(0.0)),
-                longitude: row.get::<Option<sqlx::types::BigDecimal>, _>("longitude")
-                    .map(|bd| bd.to_string().parse::<f64>().unwrap_or(0.0)),
-                is_available: row.get("is_available"),
-                average_rating: row.get("average_rating"),
-                total_reviews: row.get("total_reviews"),
-                created_at: row.get("created_at"),
-            })),
-            None => Ok(None),
-        }
+        let row = sql.build()
+            .fetch_optional(pool)
+            .await?;
+
+        Ok(row.map(|row| mua_profile_from_row(&row)))
     }
 
     async fn get_mua_by_user_id(&self, pool: &PgPool, user_id: Uuid) -> Result<Option<Uuid>> {
@@ -183,9 +146,9 @@ impl MuaRepository for MuaRepositoryImpl {
         // Insert the MUA profile
         let row = query(
             r#"
-            INSERT INTO mua_profiles (user_id, bio, experience_years, specialization, location, latitude, longitude, is_available, created_at, updated_at)
-            VALUES ($1, $2, $3, $4, $5, $6, $7, true, NOW(), NOW())
-            RETURNING id, bio, experience_years, specialization, location, latitude, longitude, is_available, average_rating, total_reviews, created_at, updated_at
+            INSERT INTO mua_profiles (user_id, bio, experience_years, specialization, location, latitude, longitude, timezone, is_available, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, true, NOW(), NOW())
+            RETURNING id, bio, experience_years, specialization, location, latitude, longitude, is_available, average_rating, total_reviews, timezone, created_at, updated_at
             "#
         )
         .bind(user_id)
@@ -195,6 +158,7 @@ impl MuaRepository for MuaRepositoryImpl {
         .bind(&profile_data.location)
         .bind(profile_data.latitude)
         .bind(profile_data.longitude)
+        .bind(profile_data.timezone.as_deref().unwrap_or(crate::services::timezone_service::DEFAULT_TIMEZONE.name()))
         .fetch_one(pool)
         .await?;
 
@@ -210,9 +174,14 @@ impl MuaRepository for MuaRepositoryImpl {
             longitude: row.get::<Option<sqlx::types::BigDecimal>, _>("longitude")
                 .map(|bd| bd.to_string().parse::<f64>().unwrap_or(0.0)),
             is_available: row.get("is_available"),
-            average_rating: row.get("average_rating"),
+            average_rating: row.get::<Option<sqlx::types::BigDecimal>, _>("average_rating")
+                .map(|bd| bd.to_string().parse::<f64>().unwrap_or(0.0)),
             total_reviews: row.get("total_reviews"),
+            timezone: row.get("timezone"),
+            starting_price: None,
             created_at: row.get("created_at"),
+            is_favorite: None,
+            highlights: None,
         })
     }
 
@@ -227,15 +196,17 @@ impl MuaRepository for MuaRepositoryImpl {
 
         let portfolio_item = query(
             r#"
-            INSERT INTO portfolio_items (mua_id, title, description, image_url, service_type, created_at)
-            VALUES ($1, $2, $3, $4, $5, NOW())
-            RETURNING id, title, description, image_url, service_type, created_at
+            INSERT INTO portfolio_items (mua_id, title, description, image_url, thumbnail_url, medium_url, service_type, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, NOW())
+            RETURNING id, title, description, image_url, thumbnail_url, medium_url, service_type, created_at
             "#
         )
         .bind(mua_id)
         .bind(title)
         .bind(portfolio_data.get("description").and_then(|v| v.as_str()))
         .bind(image_url)
+        .bind(portfolio_data.get("thumbnail_url").and_then(|v| v.as_str()))
+        .bind(portfolio_data.get("medium_url").and_then(|v| v.as_str()))
         .bind(portfolio_data.get("service_type").and_then(|v| v.as_str()))
         .fetch_one(pool)
         .await?;
@@ -245,10 +216,270 @@ impl MuaRepository for MuaRepositoryImpl {
             "title": portfolio_item.get::<String, _>("title"),
             "description": portfolio_item.get::<Option<String>, _>("description"),
             "image_url": portfolio_item.get::<String, _>("image_url"),
+            "thumbnail_url": portfolio_item.get::<Option<String>, _>("thumbnail_url"),
+            "medium_url": portfolio_item.get::<Option<String>, _>("medium_url"),
             "service_type": portfolio_item.get::<Option<String>, _>("service_type"),
             "created_at": portfolio_item.get::<chrono::DateTime<chrono::Utc>, _>("created_at")
         });
 
         Ok(response)
     }
-}
\ No newline at end of file
+}
+
+/// FROM and WHERE for a search, shared by the page and its count. `with_highlights` also
+/// joins the portfolio text the headlines are cut from.
+fn push_search_filters(
+    sql: &mut QueryBuilder<'_, Postgres>,
+    params: &SearchMuasRequest,
+    search_text: Option<&str>,
+    service_category: Option<&str>,
+    with_highlights: bool,
+) {
+    sql.push(
+        " FROM mua_profiles mp
+         JOIN users u ON mp.user_id = u.id"
+    );
+
+    if let Some(search_text) = search_text {
+        sql.push(" CROSS JOIN websearch_to_tsquery('simple', immutable_unaccent(");
+        sql.push_bind(search_text.to_string());
+        sql.push(")) AS search(query)");
+
+        if with_highlights {
+            sql.push(
+                " LEFT JOIN LATERAL (
+                     SELECT string_agg(concat_ws(' ', pi.title, pi.description), ' ') AS text
+                     FROM portfolio_items pi
+                     WHERE pi.mua_id = mp.id
+                 ) portfolio ON true"
+            );
+        }
+    }
+
+    push_pricing_join(sql, service_category);
+
+    sql.push(" WHERE u.user_type = 'mua'");
+
+    if search_text.is_some() {
+        sql.push(" AND mp.search_document @@ search.query");
+    }
+
+    if let Some(location) = &params.location {
+        if !location.is_empty() {
+            sql.push(" AND mp.location ILIKE ");
+            sql.push_bind(format!("%{}%", escape_like(location)));
+        }
+    }
+
+    if let Some(min_rating) = params.min_rating {
+        if min_rating > 0.0 {
+            sql.push(" AND mp.average_rating >= ");
+            sql.push_bind(min_rating);
+        }
+    }
+
+    if let Some(specialization) = &params.specialization {
+        if !specialization.is_empty() {
+            sql.push(" AND mp.specialization @> ARRAY[");
+            sql.push_bind(specialization.clone());
+            sql.push("]");
+        }
+    }
+
+    if let Some(min_price) = params.min_price {
+        sql.push(" AND pricing.starting_price >= ");
+        sql.push_bind(min_price);
+    }
+
+    if let Some(max_price) = params.max_price {
+        sql.push(" AND pricing.starting_price <= ");
+        sql.push_bind(max_price);
+    }
+
+    if service_category.is_some() {
+        sql.push(" AND pricing.starting_price IS NOT NULL");
+    }
+
+    if let Some(min_experience_years) = params.min_experience_years {
+        sql.push(" AND mp.experience_years >= ");
+        sql.push_bind(min_experience_years);
+    }
+}
+
+/// Profile and owner columns `mua_profile_from_row` reads, for a query joining
+/// `mua_profiles mp`, `users u` and `push_pricing_join`
+pub(crate) const MUA_PROFILE_COLUMNS: &str =
+    "mp.id as mua_id, mp.user_id, mp.bio, mp.experience_years, mp.specialization,
+     mp.location, mp.latitude, mp.longitude, mp.is_available, mp.average_rating,
+     mp.total_reviews, mp.timezone, mp.created_at as mua_created_at,
+     u.email, u.password_hash, u.user_type, u.full_name,
+     u.phone_number, u.profile_picture_url, u.is_verified, u.created_at, u.updated_at,
+     pricing.starting_price";
+
+pub(crate) fn mua_profile_from_row(row: &PgRow) -> MuaProfileResponse {
+    MuaProfileResponse {
+        id: row.get("mua_id"),
+        user: User {
+            id: row.get("user_id"),
+            email: row.get("email"),
+            password_hash: row.get("password_hash"),
+            user_type: row.get("user_type"),
+            full_name: row.get("full_name"),
+            phone_number: row.get("phone_number"),
+            profile_picture_url: row.get("profile_picture_url"),
+            is_verified: row.get("is_verified"),
+            created_at: row.get("created_at"),
+            updated_at: row.get("updated_at"),
+        },
+        bio: row.get("bio"),
+        experience_years: row.get("experience_years"),
+        specialization: row.get("specialization"),
+        location: row.get("location"),
+        latitude: row.get::<Option<sqlx::types::BigDecimal>, _>("latitude")
+            .map(|bd| bd.to_string().parse::<f64>().unwrap_or(0.0)),
+        longitude: row.get::<Option<sqlx::types::BigDecimal>, _>("longitude")
+            .map(|bd| bd.to_string().parse::<f64>().unwrap_or(0.0)),
+        is_available: row.get("is_available"),
+        average_rating: row.get::<Option<sqlx::types::BigDecimal>, _>("average_rating")
+            .map(|bd| bd.to_string().parse::<f64>().unwrap_or(0.0)),
+        total_reviews: row.get("total_reviews"),
+        timezone: row.get("timezone"),
+        starting_price: row.get("starting_price"),
+        created_at: row.get("mua_created_at"),
+        is_favorite: None,
+        highlights: None,
+    }
+}
+
+/// Joins `pricing.starting_price`: the cheapest listed service, or failing that the cheapest
+/// line item on a completed booking. With a category, only services in it count; past line
+/// items are matched by their free-text service type containing the category.
+pub(crate) fn push_pricing_join(sql: &mut QueryBuilder<'_, Postgres>, service_category: Option<&str>) {
+    sql.push(
+        " LEFT JOIN LATERAL (
+             SELECT COALESCE(
+                 (SELECT MIN(so.price) FROM service_offerings so
+                  WHERE so.mua_id = mp.id"
+    );
+    if let Some(category) = service_category {
+        sql.push(" AND lower(so.category) = lower(");
+        sql.push_bind(category.to_string());
+        sql.push(")");
+    }
+    sql.push(
+        "),
+                 (SELECT MIN(li.unit_price) FROM booking_line_items li
+                  JOIN bookings b ON b.id = li.booking_id
+                  WHERE b.mua_id = mp.id AND b.status = 'completed'"
+    );
+    if let Some(category) = service_category {
+        sql.push(" AND li.service_type ILIKE ");
+        sql.push_bind(format!("%{}%", escape_like(category)));
+    }
+    sql.push(
+        ")
+             ) AS starting_price
+         ) pricing ON true"
+    );
+}
+
+/// `value` matched literally inside a LIKE pattern
+fn escape_like(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_")
+}
+
+/// ts_headline can't escape the text it marks up, so it marks matches with control
+/// characters and `render_highlight` swaps them for tags after escaping
+const HIGHLIGHT_START: char = '\u{2}';
+const HIGHLIGHT_STOP: char = '\u{3}';
+
+/// Text a search is highlighted in, the column its headline is selected as, and whether it's
+/// short enough to show whole rather than cut into fragments
+const HIGHLIGHTS: [(&str, &str, bool); 4] = [
+    ("u.full_name", "name_highlight", true),
+    ("array_to_string(mp.specialization, ', ')", "specialization_highlight", true),
+    ("mp.bio", "bio_highlight", false),
+    ("portfolio.text", "portfolio_highlight", false),
+];
+
+fn headline_options(highlight_all: bool) -> String {
+    if highlight_all {
+        return format!("StartSel={}, StopSel={}, HighlightAll=true", HIGHLIGHT_START, HIGHLIGHT_STOP);
+    }
+
+    format!(
+        "StartSel={}, StopSel={}, MaxWords=25, MinWords=10, MaxFragments=2, FragmentDelimiter=\" … \"",
+        HIGHLIGHT_START, HIGHLIGHT_STOP
+    )
+}
+
+/// The snippet as HTML with matches in `<mark>`, or None if nothing in it matched
+fn render_highlight(headline: String) -> Option<String> {
+    if !headline.contains(HIGHLIGHT_START) {
+        return None;
+    }
+
+    let mut html = String::with_capacity(headline.len());
+    for c in headline.chars() {
+        match c {
+            HIGHLIGHT_START => html.push_str("<mark>"),
+            HIGHLIGHT_STOP => html.push_str("</mark>"),
+            '&' => html.push_str("&amp;"),
+            '<' => html.push_str("&lt;"),
+            '>' => html.push_str("&gt;"),
+            '"' => html.push_str("&quot;"),
+            '\'' => html.push_str("&#39;"),
+            c => html.push(c),
+        }
+    }
+
+    Some(html)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn insert_mua(pool: &PgPool, full_name: &str, specialization: &[&str], bio: &str) {
+        let user_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO users (email, password_hash, user_type, full_name) VALUES ($1, 'x', 'mua', $2) RETURNING id"
+        )
+        .bind(format!("{}@test.muaconnect.dev", Uuid::new_v4()))
+        .bind(full_name)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+
+        sqlx::query("INSERT INTO mua_profiles (user_id, location, specialization, bio) VALUES ($1, 'Jakarta', $2, $3)")
+            .bind(user_id)
+            .bind(specialization.iter().map(|s| s.to_string()).collect::<Vec<_>>())
+            .bind(bio)
+            .execute(pool)
+            .await
+            .unwrap();
+    }
+
+    async fn highlights(pool: &PgPool, q: &str) -> Vec<String> {
+        let params: SearchMuasRequest = serde_json::from_value(serde_json::json!({ "q": q })).unwrap();
+        let page = MuaRepositoryImpl::new().search_muas(pool, &params).await.unwrap();
+        assert_eq!(page.items.len(), 1, "{} matched {} MUAs", q, page.items.len());
+        page.items.into_iter().next().unwrap().highlights.unwrap()
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn highlights_accented_text_for_an_unaccented_query(pool: PgPool) {
+        insert_mua(&pool, "Putri Ayu", &["Bridal"], "Studio rias di Café Seminyak, Bali").await;
+
+        let highlights = highlights(&pool, "cafe").await;
+        assert_eq!(highlights.len(), 1, "{:?}", highlights);
+        assert!(highlights[0].contains("<mark>Cafe</mark>"), "{:?}", highlights);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn highlights_name_and_specialization_matches(pool: PgPool) {
+        insert_mua(&pool, "Putri Ayu", &["Bridal", "Wisuda"], "Riasan natural untuk acara apa saja").await;
+
+        assert_eq!(highlights(&pool, "putri").await, vec!["<mark>Putri</mark> Ayu"]);
+        assert_eq!(highlights(&pool, "wisuda").await, vec!["Bridal, <mark>Wisuda</mark>"]);
+    }
+}
@@ -1,13 +1,13 @@
 use anyhow::Result;
 use async_trait::async_trait;
 use uuid::Uuid;
-use sqlx::PgPool;
+use sqlx::{PgPool, Postgres, Transaction};
 use serde_json::Value;
 use chrono::{DateTime, Utc};
 
 use crate::models::{
-    User, MuaProfileResponse, CreateMuaProfileRequest, SearchMuasRequest,
-    Booking, CreateBookingRequest, UpdateBookingStatusRequest, BookingStatus
+    User, MuaProfileResponse, CreateMuaProfileRequest, SearchMuasRequest, Page,
+    Booking, BookingFilter, BookingParticipant, Cursor, CreateBookingRequest, UpdateBookingStatusRequest, BookingStatus, CancellationPolicy, BookingLineItem, TravelFee
 };
 
 #[async_trait]
@@ -21,7 +21,7 @@ pub trait UserRepository: Send + Sync {
 
 #[async_trait]
 pub trait MuaRepository: Send + Sync {
-    async fn search_muas(&self, pool: &PgPool, params: &SearchMuasRequest) -> Result<Vec<MuaProfileResponse>>;
+    async fn search_muas(&self, pool: &PgPool, params: &SearchMuasRequest) -> Result<Page<MuaProfileResponse>>;
     async fn get_mua_by_id(&self, pool: &PgPool, mua_id: Uuid) -> Result<Option<MuaProfileResponse>>;
     async fn get_mua_by_user_id(&self, pool: &PgPool, user_id: Uuid) -> Result<Option<Uuid>>;
     async fn create_mua_profile(&self, pool: &PgPool, user_id: Uuid, profile_data: CreateMuaProfileRequest) -> Result<MuaProfileResponse>;
@@ -30,9 +30,20 @@ pub trait MuaRepository: Send + Sync {
 
 #[async_trait]
 pub trait BookingRepository: Send + Sync {
-    async fn create_booking(&self, pool: &PgPool, booking_data: &CreateBookingRequest, customer_id: Uuid) -> Result<Booking>;
+    async fn create_booking(
+        &self,
+        pool: &PgPool,
+        booking_data: &CreateBookingRequest,
+        customer_id: Uuid,
+        response_deadline: DateTime<Utc>,
+        cancellation_policy: &CancellationPolicy,
+        travel_fee: &TravelFee,
+    ) -> Result<Booking>;
     async fn find_booking_by_id(&self, pool: &PgPool, booking_id: Uuid) -> Result<Option<Booking>>;
-    async fn find_bookings_by_customer(&self, pool: &PgPool, customer_id: Uuid) -> Result<Vec<Booking>>;
-    async fn find_bookings_by_mua(&self, pool: &PgPool, mua_id: Uuid) -> Result<Vec<Booking>>;
-    async fn update_booking_status(&self, pool: &PgPool, booking_id: Uuid, status: BookingStatus) -> Result<Booking>;
+    async fn find_line_items(&self, pool: &PgPool, booking_ids: &[Uuid]) -> Result<Vec<BookingLineItem>>;
+    /// Up to `limit` bookings matching `filter`, in its sort order, following `after`
+    async fn find_bookings(&self, pool: &PgPool, participant: BookingParticipant, filter: &BookingFilter, after: Option<Cursor>, limit: i64) -> Result<Vec<Booking>>;
+    async fn count_bookings(&self, pool: &PgPool, participant: BookingParticipant, filter: &BookingFilter) -> Result<i64>;
+    /// Move a booking from `expected` to `status`; fails with "Booking has changed" if it's no longer in `expected`
+    async fn update_booking_status(&self, tx: &mut Transaction<'_, Postgres>, booking_id: Uuid, expected: BookingStatus, status: BookingStatus, cancellation_reason: Option<&str>) -> Result<Booking>;
 }
\ No newline at end of file
@@ -93,7 +93,7 @@ impl UserRepository for UserRepositoryImpl {
             param_index += 1;
         }
 
-        query.push_str(&format!(" WHERE id = ${} RETURNING *", param_index));
+        query.push_str(&format!(" WHERE id = ${}::uuid RETURNING *", param_index));
         params.push(id.to_string());
 
         let mut query_builder = query_as::<_, User>(&query);
@@ -109,7 +109,7 @@ impl UserRepository for UserRepositoryImpl {
     }
 
     async fn get_user_type(&self, pool: &PgPool, id: Uuid) -> Result<String> {
-        let row = query("SELECT user_type FROM users WHERE id = $1")
+        let row = query("SELECT user_type::text AS user_type FROM users WHERE id = $1")
             .bind(id)
             .fetch_one(pool)
             .await?;
@@ -67,6 +67,7 @@ impl AuthService for AuthServiceImpl {
                 longitude: None,
                 profile_picture_base64: None,
                 profile_picture_url: None,
+                timezone: None,
             };
 
             // Create MUA profile with default empty location
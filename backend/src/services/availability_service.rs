@@ -0,0 +1,454 @@
+use anyhow::Result;
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveTime, Utc};
+use chrono_tz::Tz;
+use sqlx::{PgConnection, PgPool, Postgres, Row, Transaction};
+use uuid::Uuid;
+
+use crate::models::{BookableSlot, BookingBuffers, MAX_BUFFER_MINUTES};
+
+use super::timezone_service;
+
+/// One row of `availability_slots`, in the MUA's local time
+struct ScheduleSlot {
+    start_time: NaiveTime,
+    end_time: NaiveTime,
+    day_of_week: Option<i32>,
+    specific_date: Option<NaiveDate>,
+    is_available: bool,
+}
+
+/// The parts of a MUA's profile that decide when they can be booked
+struct MuaSettings {
+    is_available: bool,
+    buffers: BookingBuffers,
+    timezone: Tz,
+}
+
+/// A confirmed booking or imported busy block a new booking has to keep its distance from
+struct BookedEvent {
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    coordinates: Option<(f64, f64)>,
+}
+
+/// Granularity of the start times offered by `bookable_slots`
+const SLOT_STEP_MINUTES: i64 = 30;
+
+/// Check that the MUA can take a booking from `start` for `duration_hours`.
+///
+/// Locks the MUA's profile row for the rest of the transaction, so callers that
+/// write the booking before committing can't race another booking into the same slot.
+/// `event_coordinates` sizes the travel buffer to neighbouring bookings.
+/// `exclude_booking_id` skips the booking being moved when rescheduling.
+pub async fn ensure_slot_available(
+    tx: &mut Transaction<'_, Postgres>,
+    mua_id: Uuid,
+    start: DateTime<Utc>,
+    duration_hours: i32,
+    event_coordinates: Option<(f64, f64)>,
+    exclude_booking_id: Option<Uuid>,
+) -> Result<()> {
+    if duration_hours <= 0 {
+        return Err(anyhow::anyhow!("Invalid duration: must be at least one hour"));
+    }
+    let end = start + Duration::hours(duration_hours as i64);
+
+    let MuaSettings { is_available, buffers, timezone } = load_mua_settings(tx, mua_id, true).await?;
+
+    if !is_available {
+        return Err(anyhow::anyhow!("Time slot unavailable: this MUA is not accepting bookings"));
+    }
+
+    let time_off = load_time_off(tx, mua_id, timezone, start, end).await?;
+
+    if time_off.iter().any(|&(off_start, off_end)| off_start < end && start < off_end) {
+        return Err(anyhow::anyhow!("Time slot unavailable: the MUA has taken time off then"));
+    }
+
+    let slots = load_schedule(tx, mua_id).await?;
+
+    if !fits_schedule(&slots, timezone, start, end) {
+        return Err(anyhow::anyhow!("Time slot unavailable: outside the MUA's available hours"));
+    }
+
+    let booked = load_confirmed_events(tx, mua_id, start, end, exclude_booking_id).await?;
+
+    if booked.iter().any(|event| event.start < end && start < event.end) {
+        return Err(anyhow::anyhow!("Time slot unavailable: the MUA already has a booking at that time"));
+    }
+
+    if booked.iter().any(|event| clashes(&buffers, start, end, event_coordinates, event)) {
+        return Err(anyhow::anyhow!("Time slot unavailable: too close to another booking to prepare and travel"));
+    }
+
+    let busy = load_busy_blocks(tx, mua_id, start, end).await?;
+
+    if busy.iter().any(|block| clashes(&buffers, start, end, event_coordinates, block)) {
+        return Err(anyhow::anyhow!("Time slot unavailable: the MUA is busy in another calendar at that time"));
+    }
+
+    Ok(())
+}
+
+/// Start times on `date` (in the MUA's timezone) where a booking of `duration_hours` would currently be accepted.
+///
+/// Applies the same time-off, schedule, overlap, busy-block and buffer rules as `ensure_slot_available`,
+/// without taking any locks, so a slot can still be gone by the time it's confirmed.
+pub async fn bookable_slots(
+    pool: &PgPool,
+    mua_id: Uuid,
+    date: NaiveDate,
+    duration_hours: i32,
+    event_coordinates: Option<(f64, f64)>,
+) -> Result<Vec<BookableSlot>> {
+    if duration_hours <= 0 || duration_hours > 24 {
+        return Err(anyhow::anyhow!("Invalid duration: must be between 1 and 24 hours"));
+    }
+    let duration = Duration::hours(duration_hours as i64);
+
+    let mut conn = pool.acquire().await?;
+    let MuaSettings { is_available, buffers, timezone } = load_mua_settings(&mut conn, mua_id, false).await?;
+    if !is_available {
+        return Ok(Vec::new());
+    }
+
+    // Not always 24 hours apart in zones with daylight saving
+    let day_start = timezone_service::start_of_day(date, timezone);
+    let day_end = date.succ_opt()
+        .map(|next_day| timezone_service::start_of_day(next_day, timezone))
+        .ok_or_else(|| anyhow::anyhow!("Invalid date"))?;
+
+    let time_off = load_time_off(&mut conn, mua_id, timezone, day_start, day_end).await?;
+    let slots = load_schedule(&mut conn, mua_id).await?;
+    let mut booked = load_confirmed_events(&mut conn, mua_id, day_start, day_end, None).await?;
+    booked.extend(load_busy_blocks(&mut conn, mua_id, day_start, day_end).await?);
+
+    let now = Utc::now();
+    let mut bookable = Vec::new();
+    let mut start = day_start;
+    while start + duration <= day_end {
+        let end = start + duration;
+        if start > now
+            && !time_off.iter().any(|&(off_start, off_end)| off_start < end && start < off_end)
+            && fits_schedule(&slots, timezone, start, end)
+            && !booked.iter().any(|event| clashes(&buffers, start, end, event_coordinates, event))
+        {
+            bookable.push(BookableSlot {
+                start_time: timezone_service::local_time(start, timezone),
+                end_time: timezone_service::local_time(end, timezone),
+            });
+        }
+        start += Duration::minutes(SLOT_STEP_MINUTES);
+    }
+
+    Ok(bookable)
+}
+
+/// Whether the MUA takes bookings at all, the buffers they keep and their timezone; `lock` holds the profile row
+async fn load_mua_settings(conn: &mut PgConnection, mua_id: Uuid, lock: bool) -> Result<MuaSettings> {
+    let query = if lock {
+        "SELECT is_available, prep_buffer_minutes, travel_buffer_minutes, travel_buffer_minutes_per_km, timezone FROM mua_profiles WHERE id = $1 FOR UPDATE"
+    } else {
+        "SELECT is_available, prep_buffer_minutes, travel_buffer_minutes, travel_buffer_minutes_per_km, timezone FROM mua_profiles WHERE id = $1"
+    };
+
+    let row = sqlx::query(query)
+        .bind(mua_id)
+        .fetch_optional(&mut *conn)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("MUA profile not found"))?;
+
+    Ok(MuaSettings {
+        is_available: row.get::<Option<bool>, _>("is_available").unwrap_or(true),
+        buffers: BookingBuffers {
+            prep_minutes: row.get("prep_buffer_minutes"),
+            travel_minutes: row.get("travel_buffer_minutes"),
+            travel_minutes_per_km: row.get("travel_buffer_minutes_per_km"),
+        },
+        timezone: timezone_service::parse_timezone(row.get("timezone"))?,
+    })
+}
+
+pub async fn booking_buffers_for_mua(pool: &PgPool, mua_id: Uuid) -> Result<BookingBuffers> {
+    let mut conn = pool.acquire().await?;
+    Ok(load_mua_settings(&mut conn, mua_id, false).await?.buffers)
+}
+
+async fn load_schedule(conn: &mut PgConnection, mua_id: Uuid) -> Result<Vec<ScheduleSlot>> {
+    let slots = sqlx::query(
+        "SELECT start_time, end_time, day_of_week, specific_date, is_available FROM availability_slots WHERE mua_id = $1"
+    )
+    .bind(mua_id)
+    .fetch_all(&mut *conn)
+    .await?
+    .into_iter()
+    .map(|row| ScheduleSlot {
+        start_time: row.get("start_time"),
+        end_time: row.get("end_time"),
+        day_of_week: row.get("day_of_week"),
+        specific_date: row.get("specific_date"),
+        is_available: row.get("is_available"),
+    })
+    .collect();
+
+    Ok(slots)
+}
+
+/// The MUA's time off touching `start..end`, as the instants it covers
+async fn load_time_off(
+    conn: &mut PgConnection,
+    mua_id: Uuid,
+    timezone: Tz,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> Result<Vec<(DateTime<Utc>, DateTime<Utc>)>> {
+    let periods = sqlx::query(
+        "SELECT start_date, end_date FROM time_off WHERE mua_id = $1 AND start_date <= $3 AND end_date >= $2"
+    )
+    .bind(mua_id)
+    .bind(start.with_timezone(&timezone).date_naive())
+    .bind(end.with_timezone(&timezone).date_naive())
+    .fetch_all(&mut *conn)
+    .await?
+    .into_iter()
+    .map(|row| super::time_off_service::period_bounds(row.get("start_date"), row.get("end_date"), timezone))
+    .collect();
+
+    Ok(periods)
+}
+
+/// Confirmed bookings close enough to `start..end` that their buffers could matter
+async fn load_confirmed_events(
+    conn: &mut PgConnection,
+    mua_id: Uuid,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    exclude_booking_id: Option<Uuid>,
+) -> Result<Vec<BookedEvent>> {
+    let reach = Duration::minutes(MAX_BUFFER_MINUTES);
+
+    let events = sqlx::query(
+        r#"
+        SELECT event_date, event_date + make_interval(hours => duration_hours) AS event_end,
+               event_latitude, event_longitude
+        FROM bookings
+        WHERE mua_id = $1
+          AND status = 'confirmed'
+          AND ($4::uuid IS NULL OR id <> $4)
+          AND event_date < $3
+          AND event_date + make_interval(hours => duration_hours) > $2
+        "#
+    )
+    .bind(mua_id)
+    .bind(start - reach)
+    .bind(end + reach)
+    .bind(exclude_booking_id)
+    .fetch_all(&mut *conn)
+    .await?
+    .into_iter()
+    .map(|row| BookedEvent {
+        start: row.get("event_date"),
+        end: row.get("event_end"),
+        coordinates: match (row.get::<Option<f64>, _>("event_latitude"), row.get::<Option<f64>, _>("event_longitude")) {
+            (Some(latitude), Some(longitude)) => Some((latitude, longitude)),
+            _ => None,
+        },
+    })
+    .collect();
+
+    Ok(events)
+}
+
+/// Time blocked by the MUA's imported external calendars near `start..end`; they have no location
+async fn load_busy_blocks(
+    conn: &mut PgConnection,
+    mua_id: Uuid,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> Result<Vec<BookedEvent>> {
+    let reach = Duration::minutes(MAX_BUFFER_MINUTES);
+
+    let blocks = sqlx::query(
+        "SELECT starts_at, ends_at FROM busy_blocks WHERE mua_id = $1 AND starts_at < $3 AND ends_at > $2"
+    )
+    .bind(mua_id)
+    .bind(start - reach)
+    .bind(end + reach)
+    .fetch_all(&mut *conn)
+    .await?
+    .into_iter()
+    .map(|row| BookedEvent {
+        start: row.get("starts_at"),
+        end: row.get("ends_at"),
+        coordinates: None,
+    })
+    .collect();
+
+    Ok(blocks)
+}
+
+/// Whether `start..end` overlaps `event` once the buffer between the two is added on both sides
+fn clashes(
+    buffers: &BookingBuffers,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    coordinates: Option<(f64, f64)>,
+    event: &BookedEvent,
+) -> bool {
+    let distance = match (coordinates, event.coordinates) {
+        (Some((latitude, longitude)), Some((event_latitude, event_longitude))) => {
+            Some(super::travel_service::distance_km(latitude, longitude, event_latitude, event_longitude))
+        }
+        _ => None,
+    };
+    let gap = Duration::minutes(buffers.minutes_between(distance));
+
+    event.start < end + gap && start < event.end + gap
+}
+
+/// MUAs without a schedule take bookings any time. Otherwise the booking has to sit
+/// inside one available slot on its day, where slots for that specific date replace
+/// the weekly ones, and must not touch a slot marked unavailable. Days and times are
+/// compared on the MUA's wall clock.
+fn fits_schedule(slots: &[ScheduleSlot], timezone: Tz, start: DateTime<Utc>, end: DateTime<Utc>) -> bool {
+    if slots.is_empty() {
+        return true;
+    }
+
+    let (start, end) = (start.with_timezone(&timezone), end.with_timezone(&timezone));
+    let date = start.date_naive();
+    if end.date_naive() != date {
+        return false;
+    }
+
+    let date_slots: Vec<&ScheduleSlot> = slots.iter()
+        .filter(|slot| slot.specific_date == Some(date))
+        .collect();
+
+    let day_slots = if date_slots.is_empty() {
+        let weekday = date.weekday().num_days_from_sunday() as i32;
+        slots.iter()
+            .filter(|slot| slot.specific_date.is_none() && slot.day_of_week == Some(weekday))
+            .collect()
+    } else {
+        date_slots
+    };
+
+    let (start_time, end_time) = (start.time(), end.time());
+
+    let blocked = day_slots.iter()
+        .any(|slot| !slot.is_available && slot.start_time < end_time && start_time < slot.end_time);
+
+    !blocked && day_slots.iter()
+        .any(|slot| slot.is_available && slot.start_time <= start_time && end_time <= slot.end_time)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(value: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(value).unwrap().with_timezone(&Utc)
+    }
+
+    /// Booked 10:00-12:00 UTC
+    fn event(coordinates: Option<(f64, f64)>) -> BookedEvent {
+        BookedEvent { start: at("2026-06-01T10:00:00Z"), end: at("2026-06-01T12:00:00Z"), coordinates }
+    }
+
+    /// 30 minutes to pack up and 30 to get there, plus 2 a km
+    fn buffers() -> BookingBuffers {
+        BookingBuffers { prep_minutes: 30, travel_minutes: 30, travel_minutes_per_km: 2.0 }
+    }
+
+    #[test]
+    fn keeps_the_buffer_before_an_event() {
+        let event = event(None);
+        assert!(!clashes(&buffers(), at("2026-06-01T07:00:00Z"), at("2026-06-01T09:00:00Z"), None, &event));
+        assert!(clashes(&buffers(), at("2026-06-01T07:01:00Z"), at("2026-06-01T09:01:00Z"), None, &event));
+    }
+
+    #[test]
+    fn keeps_the_buffer_after_an_event() {
+        let event = event(None);
+        assert!(!clashes(&buffers(), at("2026-06-01T13:00:00Z"), at("2026-06-01T15:00:00Z"), None, &event));
+        assert!(clashes(&buffers(), at("2026-06-01T12:59:00Z"), at("2026-06-01T14:59:00Z"), None, &event));
+    }
+
+    #[test]
+    fn bookings_that_exactly_touch_do_not_clash_without_buffers() {
+        let event = event(None);
+        let no_buffers = BookingBuffers::default();
+        assert!(!clashes(&no_buffers, at("2026-06-01T12:00:00Z"), at("2026-06-01T14:00:00Z"), None, &event));
+        assert!(!clashes(&no_buffers, at("2026-06-01T08:00:00Z"), at("2026-06-01T10:00:00Z"), None, &event));
+        assert!(clashes(&no_buffers, at("2026-06-01T11:59:00Z"), at("2026-06-01T14:00:00Z"), None, &event));
+    }
+
+    #[test]
+    fn adds_travel_time_for_the_distance_between_events() {
+        // About 22 km apart, so 45 minutes of travel on top of the hour
+        let (jakarta, bekasi) = ((-6.2, 106.8), (-6.2, 107.0));
+        let event = event(Some(jakarta));
+        let (start, end) = (at("2026-06-01T13:30:00Z"), at("2026-06-01T15:00:00Z"));
+
+        assert!(clashes(&buffers(), start, end, Some(bekasi), &event));
+        assert!(!clashes(&buffers(), at("2026-06-01T13:45:00Z"), at("2026-06-01T15:00:00Z"), Some(bekasi), &event));
+    }
+
+    #[test]
+    fn falls_back_to_the_fixed_buffer_without_a_distance() {
+        let (start, end) = (at("2026-06-01T13:30:00Z"), at("2026-06-01T15:00:00Z"));
+
+        // Either side missing coordinates means no distance to add
+        assert!(!clashes(&buffers(), start, end, None, &event(Some((-6.2, 106.8)))));
+        assert!(!clashes(&buffers(), start, end, Some((-6.2, 107.0)), &event(None)));
+    }
+
+    fn weekly(day_of_week: i32, start: &str, end: &str, is_available: bool) -> ScheduleSlot {
+        ScheduleSlot {
+            start_time: NaiveTime::parse_from_str(start, "%H:%M").unwrap(),
+            end_time: NaiveTime::parse_from_str(end, "%H:%M").unwrap(),
+            day_of_week: Some(day_of_week),
+            specific_date: None,
+            is_available,
+        }
+    }
+
+    #[test]
+    fn fits_the_schedule_on_the_mua_wall_clock_across_a_dst_change() {
+        let berlin: Tz = "Europe/Berlin".parse().unwrap();
+        let sundays = [weekly(0, "09:00", "17:00", true)];
+
+        // 2026-03-29 is the Sunday clocks go forward, so 07:00 UTC is 09:00 in Berlin
+        assert!(fits_schedule(&sundays, berlin, at("2026-03-29T07:00:00Z"), at("2026-03-29T09:00:00Z")));
+        assert!(fits_schedule(&sundays, berlin, at("2026-03-29T13:00:00Z"), at("2026-03-29T15:00:00Z")));
+        assert!(!fits_schedule(&sundays, berlin, at("2026-03-29T14:00:00Z"), at("2026-03-29T16:00:00Z")));
+
+        // A week earlier it was still winter time, so the same UTC hours are 08:00 in Berlin
+        assert!(!fits_schedule(&sundays, berlin, at("2026-03-22T07:00:00Z"), at("2026-03-22T09:00:00Z")));
+        assert!(fits_schedule(&sundays, berlin, at("2026-03-22T08:00:00Z"), at("2026-03-22T10:00:00Z")));
+    }
+
+    #[test]
+    fn a_date_slot_replaces_the_weekly_ones_and_unavailable_slots_block() {
+        let jakarta: Tz = "Asia/Jakarta".parse().unwrap();
+        let mut slots = vec![weekly(1, "09:00", "17:00", true)];
+        let booking = (at("2026-06-01T03:00:00Z"), at("2026-06-01T05:00:00Z"));
+        assert!(fits_schedule(&slots, jakarta, booking.0, booking.1));
+
+        slots.push(ScheduleSlot {
+            specific_date: Some(NaiveDate::from_ymd_opt(2026, 6, 1).unwrap()),
+            day_of_week: None,
+            ..weekly(1, "13:00", "18:00", true)
+        });
+        assert!(!fits_schedule(&slots, jakarta, booking.0, booking.1));
+
+        slots.push(ScheduleSlot {
+            specific_date: Some(NaiveDate::from_ymd_opt(2026, 6, 1).unwrap()),
+            day_of_week: None,
+            ..weekly(1, "14:00", "15:00", false)
+        });
+        assert!(fits_schedule(&slots, jakarta, at("2026-06-01T06:00:00Z"), at("2026-06-01T07:00:00Z")));
+        assert!(!fits_schedule(&slots, jakarta, at("2026-06-01T06:30:00Z"), at("2026-06-01T07:30:00Z")));
+    }
+}
@@ -1,9 +1,14 @@
 use anyhow::Result;
 use async_trait::async_trait;
 use uuid::Uuid;
-use sqlx::PgPool;
+use sqlx::{PgExecutor, PgPool, Postgres, Row, Transaction, types::BigDecimal};
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+use std::env;
+use std::str::FromStr;
 
-use crate::models::{Booking, BookingResponse, CreateBookingRequest, UpdateBookingStatusRequest, BookingStatus};
+use crate::models::{Booking, BookingCounterpart, BookingDetail, BookingLineItem, BookingReviewState, BookingStatusChange, Review, UserType, BookingParticipant, BookingResponse, CreateBookingLineItem, CreateBookingRequest, BookingListQuery, BookingSort, Cursor, Page, UpdateBookingStatusRequest, BookingStatus};
+use super::job_queue::Job;
 use crate::repository::traits::{UserRepository, MuaRepository, BookingRepository};
 use super::traits::BookingService;
 
@@ -29,64 +34,142 @@ impl BookingService for BookingServiceImpl {
         &self,
         pool: &PgPool,
         auth_header: Option<String>,
-        booking_data: CreateBookingRequest,
+        mut booking_data: CreateBookingRequest,
     ) -> Result<BookingResponse> {
         let customer_id = super::user_service::get_user_id_from_auth_header(auth_header)?;
 
-        let booking = self.booking_repository.create_booking(pool, &booking_data, customer_id).await?;
-
-        Ok(BookingResponse {
-            id: booking.id,
-            customer_id: booking.customer_id,
-            mua_id: booking.mua_id,
-            service_type: booking.service_type,
-            description: booking.description,
-            event_date: booking.event_date,
-            event_location: booking.event_location,
-            duration_hours: booking.duration_hours,
-            price: booking.price,
-            status: booking.status,
-            deposit_amount: booking.deposit_amount,
-            deposit_paid: booking.deposit_paid,
-            final_payment_paid: booking.final_payment_paid,
-            created_at: booking.created_at,
-            updated_at: booking.updated_at,
-        })
-    }
-
-    async fn get_user_bookings(&self, pool: &PgPool, auth_header: Option<String>) -> Result<Vec<BookingResponse>> {
+        normalize_line_items(&mut booking_data)?;
+        let response_deadline = resolve_response_deadline(&booking_data)?;
+
+        // Pending requests don't hold the slot, so this is only an early check; confirming re-runs it
+        let event_date = DateTime::parse_from_rfc3339(&booking_data.event_date)
+            .map_err(|_| anyhow::anyhow!("Invalid date format"))?
+            .with_timezone(&Utc);
+        let mut availability_check = pool.begin().await?;
+        super::availability_service::ensure_slot_available(
+            &mut availability_check,
+            booking_data.mua_id,
+            event_date,
+            booking_data.duration_hours,
+            booking_data.event_latitude.zip(booking_data.event_longitude),
+            None
+        ).await?;
+        availability_check.rollback().await?;
+        let travel_fee = super::travel_service::travel_fee_for_booking(
+            pool,
+            booking_data.mua_id,
+            booking_data.event_latitude,
+            booking_data.event_longitude
+        ).await?;
+        let cancellation_policy = super::cancellation_service::cancellation_policy_for_mua(pool, booking_data.mua_id).await?;
+        let booking = self.booking_repository.create_booking(
+            pool,
+            &booking_data,
+            customer_id,
+            response_deadline,
+            &cancellation_policy,
+            &travel_fee
+        ).await?;
+        record_status_change(pool, booking.id, None, booking.status, Some(customer_id), None).await?;
+
+        // The booking exists either way, a missed notification shouldn't fail the request
+        if let Err(e) = super::notification_service::schedule_booking_requested(pool, &booking).await {
+            eprintln!("Failed to schedule booking request notification: {}", e);
+        }
+
+        self.with_line_items(pool, vec![booking]).await?
+            .pop()
+            .ok_or_else(|| anyhow::anyhow!("Booking not found"))
+    }
+
+    async fn get_user_bookings(&self, pool: &PgPool, auth_header: Option<String>, query: BookingListQuery) -> Result<Page<BookingResponse>> {
         let user_id = super::user_service::get_user_id_from_auth_header(auth_header)?;
+        let filter = query.filter()?;
+        let page_query = query.page_query();
+        let after = page_query.cursor(filter.sort.key())?;
+        let limit = page_query.limit();
 
         // Check if user is MUA or customer
         let user_type = self.user_repository.get_user_type(pool, user_id).await?;
 
-        let bookings = if user_type == "mua" {
+        let participant = if user_type == "mua" {
             // Get MUA profile first
             let mua_id = self.mua_repository.get_mua_by_user_id(pool, user_id).await?
                 .ok_or_else(|| anyhow::anyhow!("MUA profile not found"))?;
 
-            self.booking_repository.find_bookings_by_mua(pool, mua_id).await?
+            BookingParticipant::Mua(mua_id)
         } else {
-            self.booking_repository.find_bookings_by_customer(pool, user_id).await?
+            BookingParticipant::Customer(user_id)
+        };
+
+        // One extra row tells whether there's a next page
+        let bookings = self.booking_repository.find_bookings(pool, participant, &filter, after, limit + 1).await?;
+        let total = self.booking_repository.count_bookings(pool, participant, &filter).await?;
+
+        let bookings = self.with_line_items(pool, bookings).await?;
+        // The cursor carries the sort key, so it only continues a list in the same sort
+        Ok(Page::keyset(bookings, total, limit, |booking| match filter.sort {
+            BookingSort::Newest => Cursor::new(filter.sort.key(), booking.created_at, booking.id),
+            BookingSort::EventDateAsc | BookingSort::EventDateDesc => Cursor::new(filter.sort.key(), booking.event_date.with_timezone(&Utc), booking.id),
+        }))
+    }
+
+    async fn get_booking_detail(&self, pool: &PgPool, auth_header: Option<String>, booking_id: Uuid) -> Result<BookingDetail> {
+        let user_id = super::user_service::get_user_id_from_auth_header(auth_header)?;
+        let (booking, mua_user_id) = load_participant_booking(pool, booking_id, user_id).await?;
+        let counterpart_id = if user_id == booking.customer_id { mua_user_id } else { booking.customer_id };
+
+        let counterpart = sqlx::query("SELECT user_type, full_name, profile_picture_url, phone_number FROM users WHERE id = $1")
+            .bind(counterpart_id)
+            .fetch_one(pool)
+            .await?;
+        let user_type: UserType = counterpart.get("user_type");
+        let phone_shared = matches!(booking.status, BookingStatus::Confirmed | BookingStatus::Completed);
+        let counterpart = BookingCounterpart {
+            user_id: counterpart_id,
+            user_type,
+            mua_id: (counterpart_id == mua_user_id).then_some(booking.mua_id),
+            full_name: counterpart.get("full_name"),
+            profile_picture_url: counterpart.get("profile_picture_url"),
+            phone_number: if phone_shared { counterpart.get("phone_number") } else { None },
         };
 
-        Ok(bookings.into_iter().map(|b: Booking| BookingResponse {
-            id: b.id,
-            customer_id: b.customer_id,
-            mua_id: b.mua_id,
-            service_type: b.service_type,
-            description: b.description,
-            event_date: b.event_date,
-            event_location: b.event_location,
-            duration_hours: b.duration_hours,
-            price: b.price,
-            status: b.status,
-            deposit_amount: b.deposit_amount,
-            deposit_paid: b.deposit_paid,
-            final_payment_paid: b.final_payment_paid,
-            created_at: b.created_at,
-            updated_at: b.updated_at,
-        }).collect())
+        let status_history = sqlx::query_as::<_, BookingStatusChange>(
+            r#"
+            SELECT from_status, to_status, changed_by, reason, created_at
+            FROM booking_status_history
+            WHERE booking_id = $1
+            ORDER BY created_at, id
+            "#
+        )
+        .bind(booking_id)
+        .fetch_all(pool)
+        .await?;
+
+        let reviews = sqlx::query_as::<_, Review>(
+            "SELECT id, booking_id, reviewer_id, reviewee_id, rating, comment, created_at FROM reviews WHERE booking_id = $1"
+        )
+        .bind(booking_id)
+        .fetch_all(pool)
+        .await?;
+        let (my_review, counterpart_review) = reviews.into_iter().fold((None, None), |(mine, theirs), review| {
+            if review.reviewer_id == user_id { (Some(review), theirs) } else { (mine, Some(review)) }
+        });
+
+        let window_open = booking.review_window_closes_at.is_some_and(|closes_at| closes_at > Utc::now());
+        let reviews = BookingReviewState {
+            window_closes_at: booking.review_window_closes_at,
+            can_review: booking.status == BookingStatus::Completed && window_open && my_review.is_none(),
+            my_review,
+            counterpart_review,
+        };
+
+        let payment = booking.payment();
+        let booking = self.with_line_items(pool, vec![booking]).await?
+            .pop()
+            .ok_or_else(|| anyhow::anyhow!("Booking not found"))?;
+
+        Ok(BookingDetail { booking, counterpart, payment, status_history, reviews })
     }
 
     async fn update_booking_status(
@@ -103,7 +186,8 @@ impl BookingService for BookingServiceImpl {
             .ok_or_else(|| anyhow::anyhow!("Booking not found"))?;
 
         // Check if user is either the customer or the MUA
-        if booking.customer_id != user_id {
+        let is_customer = booking.customer_id == user_id;
+        if !is_customer {
             // If not customer, check if user is the MUA
             let mua_id = self.mua_repository.get_mua_by_user_id(pool, user_id).await?
                 .ok_or_else(|| anyhow::anyhow!("Unauthorized"))?;
@@ -113,32 +197,355 @@ impl BookingService for BookingServiceImpl {
             }
         }
 
-        let updated_booking = self.booking_repository.update_booking_status(
-            pool,
+        if !booking.status.can_become(status_data.status) {
+            return Err(anyhow::anyhow!(
+                "Invalid status: a {} booking can't become {}",
+                booking.status.as_str(),
+                status_data.status.as_str()
+            ));
+        }
+
+        // The expiry sweep may not have caught it yet, but the request is already void
+        let is_expired = booking.status == BookingStatus::Pending
+            && booking.response_deadline.is_some_and(|deadline| deadline <= Utc::now());
+        if is_expired && status_data.status == BookingStatus::Confirmed {
+            return Err(anyhow::anyhow!("Invalid status: the response deadline for this booking has passed"));
+        }
+
+        // Only the MUA can tell whether the customer turned up, and only once the event has started
+        if status_data.status == BookingStatus::NoShow {
+            if is_customer {
+                return Err(anyhow::anyhow!("Unauthorized"));
+            }
+            if booking.event_date > Utc::now() {
+                return Err(anyhow::anyhow!("Invalid status: only confirmed bookings whose event has started can be marked as no-show"));
+            }
+        }
+
+        if status_data.status == BookingStatus::Completed {
+            check_completion(&booking, Utc::now())?;
+        }
+
+        // Confirming is the MUA's answer to the request; the customer agrees to a price by accepting a quote
+        if status_data.status == BookingStatus::Confirmed && is_customer {
+            return Err(anyhow::anyhow!("Forbidden: only the MUA can confirm a booking"));
+        }
+
+        let is_cancelling = status_data.status == BookingStatus::Cancelled;
+        let cancellation_reason = status_data.reason.as_deref().filter(|_| is_cancelling);
+
+        // With a negotiation under way, only accepting the open quote can confirm
+        if status_data.status == BookingStatus::Confirmed
+            && super::quote_service::find_open_quote(pool, booking_id).await?.is_some()
+        {
+            return Err(anyhow::anyhow!("Invalid status: a quote is waiting for a response, accept or counter it instead"));
+        }
+
+        // The status, its refund and its history are written together, so a failure part
+        // way leaves the booking as it was and the change can be retried
+        let mut tx = pool.begin().await?;
+
+        // Held until the confirmation is written so two overlapping bookings can't both be confirmed
+        if status_data.status == BookingStatus::Confirmed {
+            super::availability_service::ensure_slot_available(
+                &mut tx,
+                booking.mua_id,
+                booking.event_date,
+                booking.duration_hours,
+                booking.event_coordinates(),
+                Some(booking.id)
+            ).await?;
+        }
+
+        let mut updated_booking = self.booking_repository.update_booking_status(
+            &mut tx,
+            booking_id,
+            booking.status,
+            status_data.status,
+            cancellation_reason
+        ).await?;
+
+        if is_cancelling {
+            super::quote_service::close_open_quotes(&mut tx, booking_id).await?;
+            let refund_amount = super::cancellation_service::refund_for_cancellation(&booking, is_customer, Utc::now());
+            updated_booking = super::cancellation_service::settle_cancellation(&mut tx, booking_id, refund_amount).await?;
+        }
+
+        // Invites reviews the same way the completion sweep does
+        if updated_booking.status == BookingStatus::Completed {
+            updated_booking = open_review_window(&mut tx, booking_id).await?;
+            super::job_queue::enqueue(&mut tx, &Job::BookingCompleted { booking_id }, Utc::now()).await?;
+        }
+
+        record_status_change(
+            &mut tx,
             booking_id,
-            status_data.status
+            Some(booking.status),
+            updated_booking.status,
+            Some(user_id),
+            status_data.reason.as_deref()
         ).await?;
 
-        Ok(BookingResponse {
-            id: updated_booking.id,
-            customer_id: updated_booking.customer_id,
-            mua_id: updated_booking.mua_id,
-            service_type: updated_booking.service_type,
-            description: updated_booking.description,
-            event_date: updated_booking.event_date,
-            event_location: updated_booking.event_location,
-            duration_hours: updated_booking.duration_hours,
-            price: updated_booking.price,
-            status: updated_booking.status,
-            deposit_amount: updated_booking.deposit_amount,
-            deposit_paid: updated_booking.deposit_paid,
-            final_payment_paid: updated_booking.final_payment_paid,
-            created_at: updated_booking.created_at,
-            updated_at: updated_booking.updated_at,
-        })
+        tx.commit().await?;
+
+        if is_cancelling {
+            if let Err(e) = super::notification_service::schedule_favorite_openings(pool, booking.mua_id).await {
+                eprintln!("Failed to schedule favorite openings notifications: {}", e);
+            }
+        }
+
+        if updated_booking.status == BookingStatus::Confirmed {
+            if let Err(e) = super::notification_service::schedule_booking_confirmed(pool, &updated_booking).await {
+                eprintln!("Failed to schedule booking confirmation notifications: {}", e);
+            }
+        }
+
+        self.with_line_items(pool, vec![updated_booking]).await?
+            .pop()
+            .ok_or_else(|| anyhow::anyhow!("Booking not found"))
+    }
+}
+
+impl BookingServiceImpl {
+    /// Attach each booking's line items and its MUA's timezone, keeping the bookings in order
+    async fn with_line_items(&self, pool: &PgPool, bookings: Vec<Booking>) -> Result<Vec<BookingResponse>> {
+        let booking_ids: Vec<Uuid> = bookings.iter().map(|booking| booking.id).collect();
+        let mut line_items_by_booking: HashMap<Uuid, Vec<BookingLineItem>> = HashMap::new();
+        for item in self.booking_repository.find_line_items(pool, &booking_ids).await? {
+            line_items_by_booking.entry(item.booking_id).or_default().push(item);
+        }
+
+        let mua_ids: Vec<Uuid> = bookings.iter().map(|booking| booking.mua_id).collect();
+        let timezones = super::timezone_service::timezones_for_muas(pool, &mua_ids).await?;
+
+        Ok(bookings
+            .into_iter()
+            .map(|booking| {
+                let line_items = line_items_by_booking.remove(&booking.id).unwrap_or_default();
+                let timezone = timezones.get(&booking.mua_id).copied().unwrap_or(super::timezone_service::DEFAULT_TIMEZONE);
+                BookingResponse::with_line_items(booking, line_items, timezone)
+            })
+            .collect())
     }
 }
 
+/// Longest service name a line item can hold (`booking_line_items.service_type`)
+const MAX_ITEM_SERVICE_TYPE_CHARS: usize = 100;
+/// Longest service summary a booking can hold (`bookings.service_type`)
+const MAX_SERVICE_SUMMARY_CHARS: usize = 255;
+
+/// Derive duration, price and service type from the line items, or turn the
+/// single-service fields of older clients into one item
+fn normalize_line_items(booking_data: &mut CreateBookingRequest) -> Result<()> {
+    if booking_data.line_items.is_empty() {
+        if booking_data.service_type.trim().is_empty() || booking_data.duration_hours <= 0 || booking_data.price.is_empty() {
+            return Err(anyhow::anyhow!("Invalid line items: add at least one service"));
+        }
+        if booking_data.service_type.trim().chars().count() > MAX_ITEM_SERVICE_TYPE_CHARS {
+            return Err(anyhow::anyhow!("Invalid service type: keep it under {} characters", MAX_ITEM_SERVICE_TYPE_CHARS));
+        }
+
+        booking_data.line_items.push(CreateBookingLineItem {
+            service_type: booking_data.service_type.clone(),
+            person_count: 1,
+            unit_price: booking_data.price.clone(),
+            minutes_per_person: booking_data.duration_hours * 60,
+        });
+        return Ok(());
+    }
+
+    let mut total_minutes: i64 = 0;
+    let mut total_price = BigDecimal::default();
+    for item in &mut booking_data.line_items {
+        item.service_type = item.service_type.trim().to_string();
+        if item.service_type.is_empty() || item.person_count < 1 || item.minutes_per_person < 1 {
+            return Err(anyhow::anyhow!("Invalid line items: each service needs a name, at least one person and a duration"));
+        }
+        if item.service_type.chars().count() > MAX_ITEM_SERVICE_TYPE_CHARS {
+            return Err(anyhow::anyhow!("Invalid line items: service names must be under {} characters", MAX_ITEM_SERVICE_TYPE_CHARS));
+        }
+
+        let unit_price = BigDecimal::from_str(&item.unit_price)
+            .map_err(|_| anyhow::anyhow!("Invalid unit price format"))?;
+        if unit_price < BigDecimal::default() {
+            return Err(anyhow::anyhow!("Invalid line items: prices can't be negative"));
+        }
+
+        total_minutes += i64::from(item.person_count) * i64::from(item.minutes_per_person);
+        total_price += unit_price * BigDecimal::from(item.person_count);
+    }
+
+    // Slots are booked in whole hours, so round the artist's total time up
+    booking_data.duration_hours = i32::try_from((total_minutes + 59) / 60)
+        .map_err(|_| anyhow::anyhow!("Invalid line items: total duration is too long"))?;
+    booking_data.price = total_price.round(2).to_string();
+    booking_data.service_type = service_summary(&booking_data.line_items);
+
+    Ok(())
+}
+
+/// Join the item names into the booking's service type, listing as many as fit
+/// and counting the rest ("Bridal, Hair +3 more")
+fn service_summary(items: &[CreateBookingLineItem]) -> String {
+    let mut summary = String::new();
+    for (index, item) in items.iter().enumerate() {
+        let remaining = items.len() - index - 1;
+        let separator = if summary.is_empty() { "" } else { ", " };
+        let suffix = if remaining > 0 { format!(" +{} more", remaining) } else { String::new() };
+        let fits = summary.chars().count() + separator.len() + item.service_type.chars().count() + suffix.len()
+            <= MAX_SERVICE_SUMMARY_CHARS;
+        if !fits {
+            // Items are at most 100 characters, so the first one always fits
+            summary.push_str(&format!(" +{} more", remaining + 1));
+            return summary;
+        }
+        summary.push_str(separator);
+        summary.push_str(&item.service_type);
+    }
+    summary
+}
+
+/// Hours after the event ends before it is completed automatically, from
+/// `BOOKING_COMPLETION_GRACE_HOURS` (default 24); the MUA can mark a no-show until then
+fn completion_grace_hours() -> i64 {
+    env::var("BOOKING_COMPLETION_GRACE_HOURS")
+        .ok()
+        .and_then(|v| v.parse::<i64>().ok())
+        .unwrap_or(24)
+}
+
+/// Days both parties have to review a completed booking, from `REVIEW_WINDOW_DAYS` (default 14)
+fn review_window_days() -> i64 {
+    env::var("REVIEW_WINDOW_DAYS")
+        .ok()
+        .and_then(|v| v.parse::<i64>().ok())
+        .unwrap_or(14)
+}
+
+/// Hours a MUA gets to answer a request, from `BOOKING_RESPONSE_HOURS` (default 24)
+pub fn default_response_hours() -> i64 {
+    env::var("BOOKING_RESPONSE_HOURS")
+        .ok()
+        .and_then(|v| v.parse::<i64>().ok())
+        .unwrap_or(24)
+}
+
+/// Use the requested deadline if given, otherwise the default window; never later than the event
+fn resolve_response_deadline(booking_data: &CreateBookingRequest) -> Result<DateTime<Utc>> {
+    let event_date = DateTime::parse_from_rfc3339(&booking_data.event_date)
+        .map_err(|_| anyhow::anyhow!("Invalid date format"))?
+        .with_timezone(&Utc);
+
+    let now = Utc::now();
+
+    let deadline = match &booking_data.response_deadline {
+        Some(deadline) => {
+            let deadline = DateTime::parse_from_rfc3339(deadline)
+                .map_err(|_| anyhow::anyhow!("Invalid response deadline format"))?
+                .with_timezone(&Utc);
+
+            if deadline <= now || deadline > event_date {
+                return Err(anyhow::anyhow!("Invalid response deadline: must be in the future and no later than the event"));
+            }
+            deadline
+        }
+        None => (now + Duration::hours(default_response_hours())).min(event_date),
+    };
+
+    Ok(deadline)
+}
+
+/// The booking plus the user id behind its MUA profile, once `user_id` is known to take part in it.
+///
+/// Anyone else gets the same "not found" as for a missing booking, so ids can't be probed.
+pub async fn load_participant_booking(pool: &PgPool, booking_id: Uuid, user_id: Uuid) -> Result<(Booking, Uuid)> {
+    let booking = sqlx::query_as::<_, Booking>("SELECT * FROM bookings WHERE id = $1")
+        .bind(booking_id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("Booking not found"))?;
+
+    let mua_user_id: Uuid = sqlx::query_scalar("SELECT user_id FROM mua_profiles WHERE id = $1")
+        .bind(booking.mua_id)
+        .fetch_one(pool)
+        .await?;
+
+    if user_id != booking.customer_id && user_id != mua_user_id {
+        return Err(anyhow::anyhow!("Booking not found"));
+    }
+
+    Ok((booking, mua_user_id))
+}
+
+/// Append to `booking_status_history`; `changed_by` is None for automatic transitions
+pub async fn record_status_change<'e, E: PgExecutor<'e>>(
+    executor: E,
+    booking_id: Uuid,
+    from_status: Option<BookingStatus>,
+    to_status: BookingStatus,
+    changed_by: Option<Uuid>,
+    reason: Option<&str>,
+) -> Result<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO booking_status_history (booking_id, from_status, to_status, changed_by, reason, created_at)
+        VALUES ($1, $2, $3, $4, $5, NOW())
+        "#
+    )
+    .bind(booking_id)
+    .bind(from_status)
+    .bind(to_status)
+    .bind(changed_by)
+    .bind(reason)
+    .execute(executor)
+    .await?;
+
+    Ok(())
+}
+
+/// Cancel pending requests whose response deadline has passed and notify both parties
+pub async fn expire_overdue_bookings(pool: &PgPool) -> Result<usize> {
+    let mut tx = pool.begin().await?;
+
+    // Nobody accepted the booking, so whatever was paid goes back in full
+    let expired: Vec<(Uuid, BigDecimal)> = sqlx::query_as(
+        r#"
+        UPDATE bookings
+        SET status = 'cancelled',
+            cancellation_reason = 'expired',
+            refund_amount = CASE
+                WHEN final_payment_paid THEN price
+                WHEN deposit_paid THEN COALESCE(deposit_amount, 0)
+                ELSE 0
+            END,
+            updated_at = NOW()
+        WHERE status = 'pending' AND response_deadline <= NOW()
+        RETURNING id, refund_amount
+        "#
+    )
+    .fetch_all(&mut tx)
+    .await?;
+
+    for (booking_id, refund_amount) in &expired {
+        record_status_change(
+            &mut tx,
+            *booking_id,
+            Some(BookingStatus::Pending),
+            BookingStatus::Cancelled,
+            None,
+            Some("expired")
+        ).await?;
+        super::quote_service::close_open_quotes(&mut tx, *booking_id).await?;
+        super::job_queue::enqueue(&mut tx, &Job::BookingExpired { booking_id: *booking_id }, Utc::now()).await?;
+        if *refund_amount > BigDecimal::default() {
+            super::job_queue::enqueue(&mut tx, &Job::IssueRefund { booking_id: *booking_id }, Utc::now()).await?;
+        }
+    }
+
+    tx.commit().await?;
+    Ok(expired.len())
+}
+
 // Legacy functions for backward compatibility
 pub async fn create_booking(
     pool: &PgPool,
@@ -156,7 +563,7 @@ pub async fn create_booking(
     booking_service.create_booking(pool, auth_header, booking_data).await
 }
 
-pub async fn get_user_bookings(pool: &PgPool, auth_header: Option<String>) -> Result<Vec<BookingResponse>> {
+pub async fn get_user_bookings(pool: &PgPool, auth_header: Option<String>, query: BookingListQuery) -> Result<Page<BookingResponse>> {
     let user_repository = crate::repository::UserRepositoryImpl::new();
     let mua_repository = crate::repository::MuaRepositoryImpl::new();
     let booking_repository = crate::repository::BookingRepositoryImpl::new();
@@ -165,7 +572,19 @@ pub async fn get_user_bookings(pool: &PgPool, auth_header: Option<String>) -> Re
         Box::new(mua_repository),
         Box::new(booking_repository)
     );
-    booking_service.get_user_bookings(pool, auth_header).await
+    booking_service.get_user_bookings(pool, auth_header, query).await
+}
+
+pub async fn get_booking_detail(pool: &PgPool, auth_header: Option<String>, booking_id: Uuid) -> Result<BookingDetail> {
+    let user_repository = crate::repository::UserRepositoryImpl::new();
+    let mua_repository = crate::repository::MuaRepositoryImpl::new();
+    let booking_repository = crate::repository::BookingRepositoryImpl::new();
+    let booking_service = BookingServiceImpl::new(
+        Box::new(user_repository),
+        Box::new(mua_repository),
+        Box::new(booking_repository)
+    );
+    booking_service.get_booking_detail(pool, auth_header, booking_id).await
 }
 
 pub async fn update_booking_status(
@@ -183,4 +602,270 @@ pub async fn update_booking_status(
         Box::new(booking_repository)
     );
     booking_service.update_booking_status(pool, auth_header, booking_id, status_data).await
-}
\ No newline at end of file
+}
+/// Completing opens reviews, so it has to follow a confirmed booking whose event is over
+fn check_completion(booking: &Booking, now: DateTime<Utc>) -> Result<()> {
+    if booking.status != BookingStatus::Confirmed {
+        return Err(anyhow::anyhow!("Invalid status: only confirmed bookings can be completed"));
+    }
+    if booking.event_end() > now {
+        return Err(anyhow::anyhow!("Invalid status: the booking can't be completed before its event has ended"));
+    }
+    Ok(())
+}
+
+/// Stamp a booking as completed now and open its review window
+async fn open_review_window(tx: &mut Transaction<'_, Postgres>, booking_id: Uuid) -> Result<Booking> {
+    let booking = sqlx::query_as::<_, Booking>(
+        r#"
+        UPDATE bookings
+        SET completed_at = NOW(), review_window_closes_at = NOW() + make_interval(days => $2)
+        WHERE id = $1
+        RETURNING *
+        "#
+    )
+    .bind(booking_id)
+    .bind(review_window_days() as i32)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    Ok(booking)
+}
+
+/// Complete confirmed bookings whose event ended more than the grace period ago and invite reviews
+pub async fn complete_finished_bookings(pool: &PgPool) -> Result<usize> {
+    let mut tx = pool.begin().await?;
+
+    let completed_ids: Vec<Uuid> = sqlx::query_scalar(
+        r#"
+        UPDATE bookings
+        SET status = 'completed',
+            completed_at = NOW(),
+            review_window_closes_at = NOW() + make_interval(days => $2),
+            updated_at = NOW()
+        WHERE status = 'confirmed'
+          AND event_date + make_interval(hours => duration_hours + $1) <= NOW()
+        RETURNING id
+        "#
+    )
+    .bind(completion_grace_hours() as i32)
+    .bind(review_window_days() as i32)
+    .fetch_all(&mut tx)
+    .await?;
+
+    for booking_id in &completed_ids {
+        record_status_change(
+            &mut tx,
+            *booking_id,
+            Some(BookingStatus::Confirmed),
+            BookingStatus::Completed,
+            None,
+            Some("event ended")
+        ).await?;
+        super::job_queue::enqueue(&mut tx, &Job::BookingCompleted { booking_id: *booking_id }, Utc::now()).await?;
+    }
+
+    tx.commit().await?;
+    Ok(completed_ids.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2026-06-01T12:00:00Z").unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn completes_confirmed_booking_once_event_has_ended() {
+        let booking = Booking::fixture(BookingStatus::Confirmed, now() - Duration::hours(3), 3);
+        assert!(check_completion(&booking, now()).is_ok());
+    }
+
+    #[test]
+    fn rejects_completing_before_event_has_started() {
+        let booking = Booking::fixture(BookingStatus::Confirmed, now() + Duration::days(2), 3);
+        assert!(check_completion(&booking, now()).is_err());
+    }
+
+    #[test]
+    fn rejects_completing_while_event_is_running() {
+        let booking = Booking::fixture(BookingStatus::Confirmed, now() - Duration::hours(2), 3);
+        let error = check_completion(&booking, now()).unwrap_err();
+        assert!(error.to_string().starts_with("Invalid status"));
+    }
+
+    #[test]
+    fn rejects_completing_pending_booking_even_after_its_date() {
+        let booking = Booking::fixture(BookingStatus::Pending, now() - Duration::days(1), 3);
+        assert!(check_completion(&booking, now()).is_err());
+    }
+
+    fn booking_request(items: &[(&str, i32)]) -> CreateBookingRequest {
+        serde_json::from_value(serde_json::json!({
+            "mua_id": Uuid::nil(),
+            "event_date": "2026-06-10T09:00:00Z",
+            "event_location": "Jakarta",
+            "line_items": items.iter().map(|(service_type, person_count)| serde_json::json!({
+                "service_type": service_type,
+                "person_count": person_count,
+                "unit_price": "150000",
+                "minutes_per_person": 45,
+            })).collect::<Vec<_>>(),
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn derives_totals_and_summary_from_line_items() {
+        let mut request = booking_request(&[(" Bridal ", 1), ("Bridesmaid", 3)]);
+        normalize_line_items(&mut request).unwrap();
+        assert_eq!(request.duration_hours, 3);
+        assert_eq!(request.price, "600000");
+        assert_eq!(request.service_type, "Bridal, Bridesmaid");
+        assert_eq!(request.line_items[0].service_type, "Bridal");
+    }
+
+    #[test]
+    fn rejects_service_names_longer_than_an_item_holds() {
+        let long_name = "a".repeat(MAX_ITEM_SERVICE_TYPE_CHARS + 1);
+        let mut request = booking_request(&[(long_name.as_str(), 1)]);
+        let error = normalize_line_items(&mut request).unwrap_err();
+        assert!(error.to_string().starts_with("Invalid line items"));
+
+        let max_name = "a".repeat(MAX_ITEM_SERVICE_TYPE_CHARS);
+        let mut request = booking_request(&[(max_name.as_str(), 1)]);
+        assert!(normalize_line_items(&mut request).is_ok());
+    }
+
+    #[test]
+    fn caps_the_summary_at_the_booking_column_length() {
+        let name = "é".repeat(MAX_ITEM_SERVICE_TYPE_CHARS);
+        let items = vec![(name.as_str(), 1); 6];
+        let mut request = booking_request(&items);
+        normalize_line_items(&mut request).unwrap();
+        assert!(request.service_type.chars().count() <= MAX_SERVICE_SUMMARY_CHARS);
+        assert!(request.service_type.ends_with(" +4 more"), "{}", request.service_type);
+        assert_eq!(request.line_items.len(), 6);
+    }
+
+    #[test]
+    fn rejects_legacy_service_type_longer_than_an_item_holds() {
+        let mut request = booking_request(&[]);
+        request.service_type = "a".repeat(MAX_ITEM_SERVICE_TYPE_CHARS + 1);
+        request.duration_hours = 2;
+        request.price = "500000".to_string();
+        assert!(normalize_line_items(&mut request).unwrap_err().to_string().starts_with("Invalid service type"));
+    }
+
+    async fn insert_user(pool: &PgPool, email: &str, user_type: &str) -> Uuid {
+        sqlx::query_scalar(
+            "INSERT INTO users (email, password_hash, user_type, full_name) VALUES ($1, 'x', $2::user_type, $1) RETURNING id"
+        )
+        .bind(email)
+        .bind(user_type)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn only_participants_can_load_a_booking(pool: PgPool) {
+        let customer_id = insert_user(&pool, "customer@test.muaconnect.dev", "customer").await;
+        let mua_user_id = insert_user(&pool, "mua@test.muaconnect.dev", "mua").await;
+        let stranger_id = insert_user(&pool, "stranger@test.muaconnect.dev", "customer").await;
+        let mua_id: Uuid = sqlx::query_scalar("INSERT INTO mua_profiles (user_id, location) VALUES ($1, 'Jakarta') RETURNING id")
+            .bind(mua_user_id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        let booking_id: Uuid = sqlx::query_scalar(
+            r#"
+            INSERT INTO bookings (customer_id, mua_id, service_type, event_date, event_location, duration_hours, price, status)
+            VALUES ($1, $2, 'Party Makeup', NOW() + INTERVAL '5 days', 'Jakarta', 2, 800000, 'pending')
+            RETURNING id
+            "#
+        )
+        .bind(customer_id)
+        .bind(mua_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+        for participant in [customer_id, mua_user_id] {
+            let (booking, loaded_mua_user_id) = load_participant_booking(&pool, booking_id, participant).await.unwrap();
+            assert_eq!((booking.id, loaded_mua_user_id), (booking_id, mua_user_id));
+        }
+
+        let error = load_participant_booking(&pool, booking_id, stranger_id).await.unwrap_err();
+        assert_eq!(error.to_string(), "Booking not found");
+    }
+
+    fn status_request(status: BookingStatus) -> UpdateBookingStatusRequest {
+        UpdateBookingStatusRequest { status, reason: None }
+    }
+
+    fn bearer(user_id: Uuid) -> Option<String> {
+        Some(format!("Bearer {}", super::super::auth_service::generate_jwt_token(user_id).unwrap()))
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn completed_and_no_show_bookings_are_final(pool: PgPool) {
+        let customer_id = insert_user(&pool, "customer@test.muaconnect.dev", "customer").await;
+        let mua_user_id = insert_user(&pool, "mua@test.muaconnect.dev", "mua").await;
+        let mua_id: Uuid = sqlx::query_scalar("INSERT INTO mua_profiles (user_id, location) VALUES ($1, 'Jakarta') RETURNING id")
+            .bind(mua_user_id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        let insert_finished_booking = || sqlx::query_scalar::<_, Uuid>(
+            r#"
+            INSERT INTO bookings (customer_id, mua_id, service_type, event_date, event_location, duration_hours, price, status)
+            VALUES ($1, $2, 'Party Makeup', NOW() - INTERVAL '1 day', 'Jakarta', 2, 800000, 'confirmed')
+            RETURNING id
+            "#
+        )
+        .bind(customer_id)
+        .bind(mua_id)
+        .fetch_one(&pool);
+
+        let completed_id = insert_finished_booking().await.unwrap();
+        let completed = update_booking_status(&pool, bearer(mua_user_id), completed_id, status_request(BookingStatus::Completed)).await.unwrap();
+        let completed_at = completed.completed_at.unwrap();
+
+        for status in [BookingStatus::Confirmed, BookingStatus::Completed, BookingStatus::NoShow] {
+            let error = update_booking_status(&pool, bearer(mua_user_id), completed_id, status_request(status)).await.unwrap_err();
+            assert!(error.to_string().starts_with("Invalid status"), "{:?}: {}", status, error);
+        }
+
+        let (status, still_completed_at): (BookingStatus, Option<DateTime<Utc>>) = sqlx::query_as("SELECT status, completed_at FROM bookings WHERE id = $1")
+            .bind(completed_id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!((status, still_completed_at), (BookingStatus::Completed, Some(completed_at)));
+
+        let invitations: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM jobs WHERE job_type = 'booking_completed' AND payload->>'booking_id' = $1")
+            .bind(completed_id.to_string())
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(invitations, 1);
+
+        let no_show_id = insert_finished_booking().await.unwrap();
+        update_booking_status(&pool, bearer(mua_user_id), no_show_id, status_request(BookingStatus::NoShow)).await.unwrap();
+        for status in [BookingStatus::Pending, BookingStatus::Confirmed, BookingStatus::Completed] {
+            let error = update_booking_status(&pool, bearer(mua_user_id), no_show_id, status_request(status)).await.unwrap_err();
+            assert!(error.to_string().starts_with("Invalid status"), "{:?}: {}", status, error);
+        }
+    }
+
+    #[test]
+    fn rejects_completing_cancelled_or_no_show_booking() {
+        for status in [BookingStatus::Cancelled, BookingStatus::NoShow] {
+            let booking = Booking::fixture(status, now() - Duration::days(1), 3);
+            assert!(check_completion(&booking, now()).is_err(), "{:?} was completed", status);
+        }
+    }
+}
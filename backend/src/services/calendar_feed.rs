@@ -0,0 +1,116 @@
+use anyhow::Result;
+use chrono::Utc;
+use serde::Serialize;
+use sqlx::PgPool;
+use std::env;
+use uuid::Uuid;
+
+use super::dashboard_service::DashboardServiceImpl;
+use super::ical::{self, CalendarEvent};
+
+/// Where a MUA subscribes to their bookings from an external calendar
+#[derive(Debug, Serialize)]
+pub struct CalendarFeed {
+    pub token: String,
+    pub feed_url: String,
+}
+
+/// Public base of feed URLs, from `CALENDAR_FEED_BASE_URL`
+fn feed_base_url() -> String {
+    env::var("CALENDAR_FEED_BASE_URL")
+        .unwrap_or_else(|_| "http://127.0.0.1:8080/api/calendar".to_string())
+}
+
+fn generate_token() -> String {
+    hex::encode(rand::random::<[u8; 32]>())
+}
+
+fn feed_for_token(token: String) -> CalendarFeed {
+    let feed_url = format!("{}/{}.ics", feed_base_url().trim_end_matches('/'), token);
+    CalendarFeed { token, feed_url }
+}
+
+/// The MUA's feed, creating its token the first time it's asked for
+pub async fn get_calendar_feed(pool: &PgPool, user_id: Uuid) -> Result<CalendarFeed> {
+    let token: Option<String> = sqlx::query_scalar(
+        r#"
+        UPDATE mua_profiles
+        SET calendar_feed_token = COALESCE(calendar_feed_token, $1)
+        WHERE user_id = $2
+        RETURNING calendar_feed_token
+        "#
+    )
+    .bind(generate_token())
+    .bind(user_id)
+    .fetch_optional(pool)
+    .await?;
+
+    let token = token.ok_or_else(|| anyhow::anyhow!("MUA profile not found"))?;
+    Ok(feed_for_token(token))
+}
+
+/// Replace the token so subscriptions using the old URL stop working
+pub async fn rotate_calendar_feed(pool: &PgPool, user_id: Uuid) -> Result<CalendarFeed> {
+    let token: Option<String> = sqlx::query_scalar(
+        "UPDATE mua_profiles SET calendar_feed_token = $1, updated_at = NOW() WHERE user_id = $2 RETURNING calendar_feed_token"
+    )
+    .bind(generate_token())
+    .bind(user_id)
+    .fetch_optional(pool)
+    .await?;
+
+    let token = token.ok_or_else(|| anyhow::anyhow!("MUA profile not found"))?;
+    Ok(feed_for_token(token))
+}
+
+/// Render the bookings of the MUA owning `token` as an ICS calendar.
+///
+/// Confirmed bookings are always included, pending ones (as tentative) only when asked for.
+pub async fn render_calendar_feed(pool: &PgPool, token: &str, include_pending: bool) -> Result<String> {
+    let mua_id: Uuid = sqlx::query_scalar("SELECT id FROM mua_profiles WHERE calendar_feed_token = $1")
+        .bind(token)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("Calendar feed not found"))?;
+
+    let dashboard_service = DashboardServiceImpl::new(
+        Box::new(crate::repository::UserRepositoryImpl::new()),
+        Box::new(crate::repository::MuaRepositoryImpl::new()),
+        Box::new(crate::repository::BookingRepositoryImpl::new())
+    );
+    let bookings = dashboard_service.calendar_bookings_for_mua(pool, mua_id, "", "").await?;
+
+    let events: Vec<CalendarEvent> = bookings
+        .into_iter()
+        .filter_map(|booking| {
+            let status = match booking.status.as_str() {
+                "confirmed" => "CONFIRMED",
+                "pending" if include_pending => "TENTATIVE",
+                _ => return None,
+            };
+
+            let mut description = vec![format!("Customer: {}", booking.customer_name)];
+            if let Some(phone) = &booking.customer_phone {
+                description.push(format!("Phone: {}", phone));
+            }
+            for item in &booking.line_items {
+                description.push(format!("{} x{}", item.service_type, item.person_count));
+            }
+            if let Some(notes) = booking.notes.as_deref().filter(|notes| !notes.is_empty()) {
+                description.push(notes.to_string());
+            }
+
+            Some(CalendarEvent {
+                uid: format!("{}@muaconnect", booking.id),
+                start: booking.start_time.with_timezone(&Utc),
+                end: booking.end_time.with_timezone(&Utc),
+                summary: format!("{} - {}", booking.service_type, booking.customer_name),
+                location: booking.location,
+                description: Some(description.join("\n")),
+                status,
+            })
+        })
+        .collect();
+
+    Ok(ical::render_calendar("MUA Connect", &events))
+}
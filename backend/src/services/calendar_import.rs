@@ -0,0 +1,602 @@
+use anyhow::Result;
+use chrono_tz::Tz;
+use reqwest::{header, redirect, Url};
+use sqlx::{PgPool, Postgres, QueryBuilder};
+use std::env;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::time::Duration;
+use tokio::io::AsyncReadExt;
+use uuid::Uuid;
+
+use crate::models::{AddExternalCalendarRequest, Cursor, ExternalCalendar, Page, PageQuery, OLDEST_FIRST};
+use super::ical::{self, BusyEvent};
+
+/// Largest calendar we download or accept as an upload
+pub const MAX_CALENDAR_SIZE: usize = 2 * 1024 * 1024;
+
+/// Most busy blocks kept per calendar, the soonest first
+const MAX_BUSY_BLOCKS: usize = 2000;
+
+/// How far ahead recurring events are expanded into busy blocks
+const IMPORT_HORIZON_DAYS: i64 = 365;
+
+/// Redirects followed per fetch, each target checked like the original URL
+const MAX_REDIRECTS: usize = 5;
+
+/// `file://` sources are only for local development and tests, enabled by `CALENDAR_IMPORT_ALLOW_FILE_URLS=true`
+fn file_urls_allowed() -> bool {
+    env::var("CALENDAR_IMPORT_ALLOW_FILE_URLS")
+        .map(|v| v == "true")
+        .unwrap_or(false)
+}
+
+async fn mua_id_for_user(pool: &PgPool, user_id: Uuid) -> Result<Uuid> {
+    sqlx::query_scalar("SELECT id FROM mua_profiles WHERE user_id = $1")
+        .bind(user_id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("MUA profile not found"))
+}
+
+/// Normalise a subscription URL, rejecting schemes we can't fetch
+fn validate_source_url(url: &str) -> Result<String> {
+    let url = url.trim();
+
+    if let Some(rest) = url.strip_prefix("webcal://") {
+        return Ok(format!("https://{}", rest));
+    }
+    if url.starts_with("https://") || url.starts_with("http://") {
+        return Ok(url.to_string());
+    }
+    if url.starts_with("file://") && file_urls_allowed() {
+        return Ok(url.to_string());
+    }
+
+    Err(anyhow::anyhow!("Invalid calendar URL: use an http(s) or webcal link"))
+}
+
+/// Whether the server may connect to `ip`; loopback, private, link-local and other
+/// non-global ranges are refused so a calendar URL can't reach internal services
+fn is_public_address(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_public_ipv4(ip),
+        IpAddr::V6(ip) => {
+            if let Some(mapped) = ip.to_ipv4_mapped() {
+                return is_public_ipv4(mapped);
+            }
+            let segments = ip.segments();
+            // NAT64 (64:ff9b::/96) reaches the embedded IPv4 address
+            if segments[..6] == [0x64, 0xff9b, 0, 0, 0, 0] {
+                let [a, b] = segments[6].to_be_bytes();
+                let [c, d] = segments[7].to_be_bytes();
+                return is_public_ipv4(Ipv4Addr::new(a, b, c, d));
+            }
+            !(ip.is_loopback()
+                || ip.is_unspecified()
+                || ip.is_multicast()
+                || (segments[0] & 0xfe00) == 0xfc00 // unique local
+                || (segments[0] & 0xffc0) == 0xfe80 // link-local
+                || (segments[0] == 0x2001 && segments[1] == 0x0db8)) // documentation
+        }
+    }
+}
+
+fn is_public_ipv4(ip: Ipv4Addr) -> bool {
+    let [a, b, c, _] = ip.octets();
+    !(ip.is_loopback()
+        || ip.is_private()
+        || ip.is_link_local()
+        || ip.is_unspecified()
+        || ip.is_broadcast()
+        || ip.is_multicast()
+        || ip.is_documentation()
+        || a == 0
+        || a >= 240
+        || (a == 100 && (64..128).contains(&b)) // carrier-grade NAT
+        || (a == 192 && b == 0 && c == 0)
+        || (a == 198 && (18..20).contains(&b))) // benchmarking
+}
+
+/// Resolve the URL's host, refusing it unless every address it resolves to is public.
+///
+/// The returned address is the one to connect to, so a second lookup can't swap in a private one.
+async fn resolve_public_address(url: &Url) -> Result<SocketAddr> {
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(anyhow::anyhow!("Invalid calendar URL: use an http(s) or webcal link"));
+    }
+    let port = url.port_or_known_default()
+        .ok_or_else(|| anyhow::anyhow!("Invalid calendar URL: no port"))?;
+
+    let addresses: Vec<SocketAddr> = match (url.domain(), url.host_str()) {
+        (Some(domain), _) => tokio::net::lookup_host((domain, port)).await
+            .map_err(|_| anyhow::anyhow!("Invalid calendar URL: couldn't resolve {}", domain))?
+            .collect(),
+        (None, Some(literal)) => {
+            let ip: IpAddr = literal.trim_start_matches('[').trim_end_matches(']').parse()
+                .map_err(|_| anyhow::anyhow!("Invalid calendar URL: bad address {}", literal))?;
+            vec![SocketAddr::new(ip, port)]
+        }
+        (None, None) => return Err(anyhow::anyhow!("Invalid calendar URL: no host")),
+    };
+
+    if addresses.is_empty() || addresses.iter().any(|address| !is_public_address(address.ip())) {
+        return Err(anyhow::anyhow!("Invalid calendar URL: it points to a private or local address"));
+    }
+
+    Ok(addresses[0])
+}
+
+/// Read at most `MAX_CALENDAR_SIZE` bytes, whatever Content-Length claims
+async fn read_capped(mut response: reqwest::Response) -> Result<Vec<u8>> {
+    if response.content_length().is_some_and(|length| length as usize > MAX_CALENDAR_SIZE) {
+        return Err(anyhow::anyhow!("Calendar is larger than 2MB"));
+    }
+
+    let mut data = Vec::new();
+    while let Some(chunk) = response.chunk().await? {
+        if data.len() + chunk.len() > MAX_CALENDAR_SIZE {
+            return Err(anyhow::anyhow!("Calendar is larger than 2MB"));
+        }
+        data.extend_from_slice(&chunk);
+    }
+
+    Ok(data)
+}
+
+async fn fetch_calendar(source_url: &str) -> Result<String> {
+    if let Some(path) = source_url.strip_prefix("file://") {
+        if !file_urls_allowed() {
+            return Err(anyhow::anyhow!("Reading calendars from files is disabled"));
+        }
+        let mut data = Vec::new();
+        tokio::fs::File::open(path).await?
+            .take(MAX_CALENDAR_SIZE as u64 + 1)
+            .read_to_end(&mut data)
+            .await?;
+        if data.len() > MAX_CALENDAR_SIZE {
+            return Err(anyhow::anyhow!("Calendar is larger than 2MB"));
+        }
+        return Ok(String::from_utf8_lossy(&data).into_owned());
+    }
+
+    let mut url = Url::parse(source_url)
+        .map_err(|_| anyhow::anyhow!("Invalid calendar URL: {}", source_url))?;
+
+    for _ in 0..=MAX_REDIRECTS {
+        let address = resolve_public_address(&url).await?;
+
+        // Redirects are followed by hand so every hop goes through the same address check
+        let mut client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(15))
+            .redirect(redirect::Policy::none());
+        if let Some(domain) = url.domain() {
+            client = client.resolve(domain, address);
+        }
+        let response = client.build()?.get(url.clone()).send().await?;
+
+        if response.status().is_redirection() {
+            let location = response.headers()
+                .get(header::LOCATION)
+                .and_then(|location| location.to_str().ok())
+                .ok_or_else(|| anyhow::anyhow!("Calendar server returned {} without a location", response.status()))?;
+            url = url.join(location)
+                .map_err(|_| anyhow::anyhow!("Calendar server redirected to an invalid URL"))?;
+            continue;
+        }
+        if !response.status().is_success() {
+            return Err(anyhow::anyhow!("Calendar server returned {}", response.status()));
+        }
+
+        let data = read_capped(response).await?;
+        return Ok(String::from_utf8_lossy(&data).into_owned());
+    }
+
+    Err(anyhow::anyhow!("Calendar server redirected more than {} times", MAX_REDIRECTS))
+}
+
+/// Times without a zone are read in the MUA's timezone
+fn parse_calendar(ics: &str, timezone: Tz) -> Result<Vec<BusyEvent>> {
+    if !ics.contains("BEGIN:VCALENDAR") {
+        return Err(anyhow::anyhow!("Invalid calendar file: not an iCalendar document"));
+    }
+
+    let now = chrono::Utc::now();
+    Ok(ical::parse_busy_events(ics, timezone, now, now + chrono::Duration::days(IMPORT_HORIZON_DAYS)))
+}
+
+/// Swap the calendar's busy blocks for `events`, keeping only those that haven't ended yet
+async fn replace_busy_blocks(pool: &PgPool, calendar_id: Uuid, mua_id: Uuid, mut events: Vec<BusyEvent>) -> Result<usize> {
+    let now = chrono::Utc::now();
+    events.retain(|event| event.end > now);
+    events.sort_by_key(|event| event.start);
+    events.truncate(MAX_BUSY_BLOCKS);
+
+    let mut tx = pool.begin().await?;
+
+    sqlx::query("DELETE FROM busy_blocks WHERE external_calendar_id = $1")
+        .bind(calendar_id)
+        .execute(&mut tx)
+        .await?;
+
+    for event in &events {
+        sqlx::query(
+            r#"
+            INSERT INTO busy_blocks (mua_id, external_calendar_id, uid, summary, starts_at, ends_at, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, NOW())
+            "#
+        )
+        .bind(mua_id)
+        .bind(calendar_id)
+        .bind(&event.uid)
+        .bind(&event.summary)
+        .bind(event.start)
+        .bind(event.end)
+        .execute(&mut tx)
+        .await?;
+    }
+
+    sqlx::query(
+        "UPDATE external_calendars SET last_synced_at = NOW(), last_attempted_at = NOW(), last_error = NULL WHERE id = $1"
+    )
+    .bind(calendar_id)
+    .execute(&mut tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(events.len())
+}
+
+async fn find_calendar(pool: &PgPool, calendar_id: Uuid) -> Result<Option<ExternalCalendar>> {
+    let calendar = sqlx::query_as::<_, ExternalCalendar>(
+        r#"
+        SELECT ec.id, ec.mua_id, ec.name, ec.source_url, ec.last_synced_at, ec.last_error, ec.created_at,
+               (SELECT COUNT(*) FROM busy_blocks b WHERE b.external_calendar_id = ec.id) AS busy_block_count
+        FROM external_calendars ec
+        WHERE ec.id = $1
+        "#
+    )
+    .bind(calendar_id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(calendar)
+}
+
+pub async fn get_external_calendars(pool: &PgPool, user_id: Uuid, page_query: PageQuery) -> Result<Page<ExternalCalendar>> {
+    let mua_id = mua_id_for_user(pool, user_id).await?;
+
+    let mut sql = QueryBuilder::<Postgres>::new(
+        r#"
+        SELECT ec.id, ec.mua_id, ec.name, ec.source_url, ec.last_synced_at, ec.last_error, ec.created_at,
+               (SELECT COUNT(*) FROM busy_blocks b WHERE b.external_calendar_id = ec.id) AS busy_block_count
+        FROM external_calendars ec
+        WHERE ec.mua_id = "#
+    );
+    sql.push_bind(mua_id);
+    page_query.push_keyset(&mut sql, OLDEST_FIRST, "ec.created_at", "ec.id", false)?;
+
+    let calendars = sql.build_query_as::<ExternalCalendar>()
+        .fetch_all(pool)
+        .await?;
+
+    let total: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM external_calendars WHERE mua_id = $1")
+        .bind(mua_id)
+        .fetch_one(pool)
+        .await?;
+
+    Ok(Page::keyset(calendars, total, page_query.limit(), |calendar| Cursor::new(OLDEST_FIRST, calendar.created_at, calendar.id)))
+}
+
+/// Subscribe to a calendar by URL and import it straight away.
+///
+/// The calendar is kept even if that first import fails, with the error recorded,
+/// so a server that is briefly down doesn't make the MUA add it again.
+pub async fn add_external_calendar(pool: &PgPool, user_id: Uuid, request: AddExternalCalendarRequest) -> Result<ExternalCalendar> {
+    let mua_id = mua_id_for_user(pool, user_id).await?;
+    let source_url = validate_source_url(&request.url)?;
+    if !source_url.starts_with("file://") {
+        let url = Url::parse(&source_url)
+            .map_err(|_| anyhow::anyhow!("Invalid calendar URL: {}", source_url))?;
+        resolve_public_address(&url).await?;
+    }
+    let name = request.name
+        .filter(|name| !name.trim().is_empty())
+        .unwrap_or_else(|| "External calendar".to_string());
+
+    let calendar_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO external_calendars (mua_id, name, source_url, created_at) VALUES ($1, $2, $3, NOW()) RETURNING id"
+    )
+    .bind(mua_id)
+    .bind(name.trim())
+    .bind(&source_url)
+    .fetch_one(pool)
+    .await?;
+
+    if let Err(e) = import_external_calendar(pool, calendar_id).await {
+        eprintln!("Initial import of calendar {} failed: {}", calendar_id, e);
+    }
+
+    find_calendar(pool, calendar_id).await?
+        .ok_or_else(|| anyhow::anyhow!("Calendar not found"))
+}
+
+/// Import an uploaded .ics file once; it isn't refreshed afterwards
+pub async fn upload_external_calendar(pool: &PgPool, user_id: Uuid, name: Option<String>, data: &[u8]) -> Result<ExternalCalendar> {
+    let mua_id = mua_id_for_user(pool, user_id).await?;
+
+    if data.len() > MAX_CALENDAR_SIZE {
+        return Err(anyhow::anyhow!("Invalid calendar file: larger than 2MB"));
+    }
+    let timezone = super::timezone_service::timezone_for_mua(pool, mua_id).await?;
+    let events = parse_calendar(&String::from_utf8_lossy(data), timezone)?;
+    let name = name
+        .filter(|name| !name.trim().is_empty())
+        .unwrap_or_else(|| "Uploaded calendar".to_string());
+
+    let calendar_id: Uuid = sqlx::query_scalar(
+        "INSERT INTO external_calendars (mua_id, name, created_at) VALUES ($1, $2, NOW()) RETURNING id"
+    )
+    .bind(mua_id)
+    .bind(name.trim())
+    .fetch_one(pool)
+    .await?;
+
+    replace_busy_blocks(pool, calendar_id, mua_id, events).await?;
+
+    find_calendar(pool, calendar_id).await?
+        .ok_or_else(|| anyhow::anyhow!("Calendar not found"))
+}
+
+/// Re-import one of the MUA's subscribed calendars now
+pub async fn sync_external_calendar(pool: &PgPool, user_id: Uuid, calendar_id: Uuid) -> Result<ExternalCalendar> {
+    let mua_id = mua_id_for_user(pool, user_id).await?;
+    let calendar = find_calendar(pool, calendar_id).await?
+        .filter(|calendar| calendar.mua_id == mua_id)
+        .ok_or_else(|| anyhow::anyhow!("Calendar not found"))?;
+
+    if calendar.source_url.is_none() {
+        return Err(anyhow::anyhow!("Invalid request: uploaded calendars can't be refreshed, upload the file again"));
+    }
+
+    import_external_calendar(pool, calendar_id).await?;
+
+    find_calendar(pool, calendar_id).await?
+        .ok_or_else(|| anyhow::anyhow!("Calendar not found"))
+}
+
+/// Remove a calendar and the busy time it imported
+pub async fn delete_external_calendar(pool: &PgPool, user_id: Uuid, calendar_id: Uuid) -> Result<()> {
+    let mua_id = mua_id_for_user(pool, user_id).await?;
+
+    let result = sqlx::query("DELETE FROM external_calendars WHERE id = $1 AND mua_id = $2")
+        .bind(calendar_id)
+        .bind(mua_id)
+        .execute(pool)
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(anyhow::anyhow!("Calendar not found"));
+    }
+
+    Ok(())
+}
+
+/// Fetch a subscribed calendar and replace its busy blocks, recording the error if that fails
+pub async fn import_external_calendar(pool: &PgPool, calendar_id: Uuid) -> Result<usize> {
+    let (mua_id, source_url): (Uuid, Option<String>) = sqlx::query_as(
+        "SELECT mua_id, source_url FROM external_calendars WHERE id = $1"
+    )
+    .bind(calendar_id)
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| anyhow::anyhow!("Calendar not found"))?;
+
+    let source_url = source_url
+        .ok_or_else(|| anyhow::anyhow!("Calendar {} has no URL to import from", calendar_id))?;
+
+    let imported = async {
+        let timezone = super::timezone_service::timezone_for_mua(pool, mua_id).await?;
+        let ics = fetch_calendar(&source_url).await?;
+        let events = parse_calendar(&ics, timezone)?;
+        replace_busy_blocks(pool, calendar_id, mua_id, events).await
+    }
+    .await;
+
+    if let Err(e) = &imported {
+        sqlx::query("UPDATE external_calendars SET last_attempted_at = NOW(), last_error = $2 WHERE id = $1")
+            .bind(calendar_id)
+            .bind(e.to_string())
+            .execute(pool)
+            .await?;
+    }
+
+    imported
+}
+
+/// Subscribed calendars not attempted in the last `interval_seconds`, oldest first
+pub async fn find_calendars_due(pool: &PgPool, interval_seconds: i64, limit: i64) -> Result<Vec<Uuid>> {
+    let ids = sqlx::query_scalar(
+        r#"
+        SELECT id FROM external_calendars
+        WHERE source_url IS NOT NULL
+          AND (last_attempted_at IS NULL OR last_attempted_at < NOW() - make_interval(secs => $1))
+        ORDER BY last_attempted_at NULLS FIRST
+        LIMIT $2
+        "#
+    )
+    .bind(interval_seconds as f64)
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(ids)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{DateTime, Duration, DurationRound, Utc};
+    use std::net::Ipv6Addr;
+
+    fn ics_time(time: DateTime<Utc>) -> String {
+        time.format("%Y%m%dT%H%M%SZ").to_string()
+    }
+
+    /// A stand-in subscription: an .ics file served through `file://`
+    fn write_calendar(name: &str, events: &str) -> String {
+        env::set_var("CALENDAR_IMPORT_ALLOW_FILE_URLS", "true");
+        let path = env::temp_dir().join(format!("{}-{}.ics", name, Uuid::new_v4()));
+        std::fs::write(&path, format!("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n{}END:VCALENDAR\r\n", events)).unwrap();
+        format!("file://{}", path.display())
+    }
+
+    async fn insert_mua(pool: &PgPool) -> Uuid {
+        let user_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO users (email, password_hash, user_type, full_name) VALUES ($1, 'x', 'mua', 'Test MUA') RETURNING id"
+        )
+        .bind(format!("{}@test.muaconnect.dev", Uuid::new_v4()))
+        .fetch_one(pool)
+        .await
+        .unwrap();
+
+        sqlx::query_scalar("INSERT INTO mua_profiles (user_id, location) VALUES ($1, 'Jakarta') RETURNING id")
+            .bind(user_id)
+            .fetch_one(pool)
+            .await
+            .unwrap()
+    }
+
+    async fn subscribe(pool: &PgPool, mua_id: Uuid, source_url: &str) -> Uuid {
+        sqlx::query_scalar("INSERT INTO external_calendars (mua_id, name, source_url, created_at) VALUES ($1, 'Work', $2, NOW()) RETURNING id")
+            .bind(mua_id)
+            .bind(source_url)
+            .fetch_one(pool)
+            .await
+            .unwrap()
+    }
+
+    async fn busy_blocks(pool: &PgPool, calendar_id: Uuid) -> Vec<(DateTime<Utc>, DateTime<Utc>)> {
+        sqlx::query_as("SELECT starts_at, ends_at FROM busy_blocks WHERE external_calendar_id = $1 ORDER BY starts_at")
+            .bind(calendar_id)
+            .fetch_all(pool)
+            .await
+            .unwrap()
+    }
+
+    async fn slot_available(pool: &PgPool, mua_id: Uuid, start: DateTime<Utc>) -> Result<()> {
+        let mut tx = pool.begin().await?;
+        super::super::availability_service::ensure_slot_available(&mut tx, mua_id, start, 2, None, None).await
+    }
+
+    #[test]
+    fn refuses_internal_addresses() {
+        for ip in [
+            IpAddr::V4(Ipv4Addr::LOCALHOST),
+            IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+            IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1)),
+            IpAddr::V4(Ipv4Addr::new(172, 16, 5, 4)),
+            IpAddr::V4(Ipv4Addr::new(192, 168, 1, 1)),
+            IpAddr::V4(Ipv4Addr::new(169, 254, 169, 254)),
+            IpAddr::V4(Ipv4Addr::new(100, 64, 0, 1)),
+            IpAddr::V6(Ipv6Addr::LOCALHOST),
+            IpAddr::V6(Ipv6Addr::UNSPECIFIED),
+            IpAddr::V6("fe80::1".parse().unwrap()),
+            IpAddr::V6("fd00::1".parse().unwrap()),
+            IpAddr::V6("::ffff:127.0.0.1".parse().unwrap()),
+            IpAddr::V6("::ffff:169.254.169.254".parse().unwrap()),
+            IpAddr::V6("64:ff9b::a00:1".parse().unwrap()),
+        ] {
+            assert!(!is_public_address(ip), "{} should be refused", ip);
+        }
+
+        for ip in [
+            IpAddr::V4(Ipv4Addr::new(8, 8, 8, 8)),
+            IpAddr::V4(Ipv4Addr::new(172, 32, 0, 1)),
+            IpAddr::V6("2606:4700::1111".parse().unwrap()),
+        ] {
+            assert!(is_public_address(ip), "{} should be allowed", ip);
+        }
+    }
+
+    #[tokio::test]
+    async fn fetch_rejects_local_hosts_before_connecting() {
+        for url in ["http://127.0.0.1:9/cal.ics", "http://[::1]/cal.ics", "http://localhost/cal.ics", "https://169.254.169.254/latest"] {
+            let error = fetch_calendar(url).await.unwrap_err().to_string();
+            assert!(error.starts_with("Invalid calendar URL"), "{}: {}", url, error);
+        }
+    }
+
+    #[tokio::test]
+    async fn reads_a_calendar_from_a_file() {
+        let start = Utc::now().duration_trunc(Duration::hours(1)).unwrap() + Duration::days(2);
+        let url = write_calendar("read", &format!(
+            "BEGIN:VEVENT\r\nUID:one\r\nDTSTART:{}\r\nDTEND:{}\r\nEND:VEVENT\r\n",
+            ics_time(start),
+            ics_time(start + Duration::hours(1)),
+        ));
+
+        let ics = fetch_calendar(&url).await.unwrap();
+        let events = parse_calendar(&ics, chrono_tz::Asia::Jakarta).unwrap();
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].start, start);
+        assert!(parse_calendar("not a calendar", chrono_tz::Asia::Jakarta).is_err());
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn refresh_replaces_busy_blocks_and_blocks_bookings(pool: PgPool) {
+        let mua_id = insert_mua(&pool).await;
+        let start = Utc::now().duration_trunc(Duration::days(1)).unwrap() + Duration::days(2) + Duration::hours(3);
+
+        // Three daily occurrences with the middle one excluded
+        let url = write_calendar("refresh", &format!(
+            "BEGIN:VEVENT\r\nUID:series\r\nDTSTART:{}\r\nDTEND:{}\r\nRRULE:FREQ=DAILY;COUNT=3\r\nEXDATE:{}\r\nEND:VEVENT\r\n",
+            ics_time(start),
+            ics_time(start + Duration::hours(2)),
+            ics_time(start + Duration::days(1)),
+        ));
+        let calendar_id = subscribe(&pool, mua_id, &url).await;
+
+        assert_eq!(import_external_calendar(&pool, calendar_id).await.unwrap(), 2);
+        assert_eq!(busy_blocks(&pool, calendar_id).await, vec![
+            (start, start + Duration::hours(2)),
+            (start + Duration::days(2), start + Duration::days(2) + Duration::hours(2)),
+        ]);
+
+        let error = slot_available(&pool, mua_id, start).await.unwrap_err().to_string();
+        assert!(error.contains("busy in another calendar"), "{}", error);
+        slot_available(&pool, mua_id, start + Duration::days(1)).await.unwrap();
+
+        // The calendar changes upstream: the next refresh drops the old blocks
+        let path = url.strip_prefix("file://").unwrap();
+        let moved = start + Duration::days(1);
+        std::fs::write(path, format!(
+            "BEGIN:VCALENDAR\r\nBEGIN:VEVENT\r\nUID:moved\r\nDTSTART:{}\r\nDURATION:PT2H\r\nEND:VEVENT\r\nEND:VCALENDAR\r\n",
+            ics_time(moved),
+        )).unwrap();
+
+        assert_eq!(import_external_calendar(&pool, calendar_id).await.unwrap(), 1);
+        assert_eq!(busy_blocks(&pool, calendar_id).await, vec![(moved, moved + Duration::hours(2))]);
+        slot_available(&pool, mua_id, start).await.unwrap();
+        assert!(slot_available(&pool, mua_id, moved).await.is_err());
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn failed_refresh_keeps_blocks_and_records_the_error(pool: PgPool) {
+        let mua_id = insert_mua(&pool).await;
+        let start = Utc::now().duration_trunc(Duration::hours(1)).unwrap() + Duration::days(3);
+        let url = write_calendar("failed", &format!(
+            "BEGIN:VEVENT\r\nUID:one\r\nDTSTART:{}\r\nDURATION:PT1H\r\nEND:VEVENT\r\n",
+            ics_time(start),
+        ));
+        let calendar_id = subscribe(&pool, mua_id, &url).await;
+        import_external_calendar(&pool, calendar_id).await.unwrap();
+
+        std::fs::remove_file(url.strip_prefix("file://").unwrap()).unwrap();
+        assert!(import_external_calendar(&pool, calendar_id).await.is_err());
+
+        let calendar = find_calendar(&pool, calendar_id).await.unwrap().unwrap();
+        assert!(calendar.last_error.is_some());
+        assert_eq!(busy_blocks(&pool, calendar_id).await.len(), 1);
+    }
+}
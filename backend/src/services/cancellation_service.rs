@@ -0,0 +1,94 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Postgres, Row, Transaction, types::{BigDecimal, Json}};
+use std::env;
+use uuid::Uuid;
+
+use crate::models::{Booking, CancellationPolicy};
+use super::job_queue::{self, Job};
+use super::payment_provider::{PaymentProvider, RefundRequest};
+
+/// The policy new bookings with this MUA get, falling back to the default
+pub async fn cancellation_policy_for_mua(pool: &PgPool, mua_id: Uuid) -> Result<CancellationPolicy> {
+    let policy: Option<Option<Json<CancellationPolicy>>> = sqlx::query_scalar(
+        "SELECT cancellation_policy FROM mua_profiles WHERE id = $1"
+    )
+    .bind(mua_id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(policy.flatten().map(|policy| policy.0).unwrap_or_default())
+}
+
+/// What is owed back when `booking` is cancelled at `cancelled_at`.
+///
+/// The booking's own policy applies when the customer cancels; if the MUA
+/// cancels, everything paid is returned.
+pub fn refund_for_cancellation(booking: &Booking, cancelled_by_customer: bool, cancelled_at: DateTime<Utc>) -> BigDecimal {
+    let amount_paid = booking.amount_paid();
+
+    if !cancelled_by_customer {
+        return amount_paid;
+    }
+
+    let policy = booking.cancellation_policy.as_ref()
+        .map(|policy| policy.0.clone())
+        .unwrap_or_default();
+    let hours_before = (booking.event_date - cancelled_at).num_hours();
+
+    policy.refund_amount(&amount_paid, hours_before)
+}
+
+/// Persist the refund for a cancelled booking and queue it for the payment provider,
+/// inside the transaction that cancels it
+pub async fn settle_cancellation(tx: &mut Transaction<'_, Postgres>, booking_id: Uuid, refund_amount: BigDecimal) -> Result<Booking> {
+    let booking = sqlx::query_as::<_, Booking>(
+        "UPDATE bookings SET refund_amount = $2, updated_at = NOW() WHERE id = $1 RETURNING *"
+    )
+    .bind(booking_id)
+    .bind(&refund_amount)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    if refund_amount > BigDecimal::default() {
+        job_queue::enqueue(&mut *tx, &Job::IssueRefund { booking_id }, Utc::now()).await?;
+    }
+
+    Ok(booking)
+}
+
+/// Send a booking's refund to the payment provider, once
+pub async fn issue_refund(pool: &PgPool, provider: &dyn PaymentProvider, booking_id: Uuid) -> Result<()> {
+    let row = sqlx::query("SELECT refund_amount, refunded_at FROM bookings WHERE id = $1")
+        .bind(booking_id)
+        .fetch_optional(pool)
+        .await?;
+
+    let Some(row) = row else {
+        return Ok(());
+    };
+
+    let refund_amount: Option<BigDecimal> = row.get("refund_amount");
+    let refunded_at: Option<DateTime<Utc>> = row.get("refunded_at");
+
+    let Some(amount) = refund_amount.filter(|amount| *amount > BigDecimal::default()) else {
+        return Ok(());
+    };
+    if refunded_at.is_some() {
+        return Ok(());
+    }
+
+    let reference = provider.refund(&RefundRequest {
+        booking_id,
+        amount,
+        currency: env::var("PAYMENT_CURRENCY").unwrap_or_else(|_| "IDR".to_string()),
+    }).await?;
+
+    sqlx::query("UPDATE bookings SET refund_reference = $2, refunded_at = NOW() WHERE id = $1")
+        .bind(booking_id)
+        .bind(reference)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
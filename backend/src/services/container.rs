@@ -102,10 +102,90 @@ mod mock_services {
     use anyhow::Result;
     use uuid::Uuid;
     use sqlx::PgPool;
+    use sqlx::types::BigDecimal;
     use serde_json::Value;
 
     use crate::models::*;
 
+    fn mock_user_response(full_name: &str) -> UserResponse {
+        UserResponse {
+            id: Uuid::new_v4(),
+            email: "test@example.com".to_string(),
+            user_type: UserType::Customer,
+            full_name: full_name.to_string(),
+            phone_number: Some("1234567890".to_string()),
+            profile_picture_url: None,
+            is_verified: false,
+            created_at: chrono::Utc::now(),
+        }
+    }
+
+    fn mock_mua_profile() -> MuaProfileResponse {
+        MuaProfileResponse {
+            id: Uuid::new_v4(),
+            user: User {
+                id: Uuid::new_v4(),
+                email: "mua@example.com".to_string(),
+                password_hash: "hash".to_string(),
+                user_type: UserType::Mua,
+                full_name: "MUA Artist".to_string(),
+                phone_number: Some("1234567890".to_string()),
+                profile_picture_url: None,
+                is_verified: true,
+                created_at: chrono::Utc::now(),
+                updated_at: chrono::Utc::now(),
+            },
+            bio: Some("Professional makeup artist".to_string()),
+            experience_years: Some(5),
+            specialization: Some(vec!["bridal".to_string()]),
+            location: "Jakarta".to_string(),
+            latitude: Some(-6.2088),
+            longitude: Some(106.8456),
+            is_available: true,
+            average_rating: Some(4.5),
+            total_reviews: Some(10),
+            timezone: "Asia/Jakarta".to_string(),
+            starting_price: None,
+            created_at: chrono::Utc::now(),
+            is_favorite: None,
+            highlights: None,
+        }
+    }
+
+    fn mock_booking(status: BookingStatus) -> BookingResponse {
+        BookingResponse {
+            id: Uuid::new_v4(),
+            customer_id: Uuid::new_v4(),
+            mua_id: Uuid::new_v4(),
+            service_type: "bridal".to_string(),
+            description: Some("Bridal makeup".to_string()),
+            event_date: chrono::Utc::now().fixed_offset(),
+            timezone: "Asia/Jakarta".to_string(),
+            event_location: "Venue".to_string(),
+            duration_hours: 4,
+            price: BigDecimal::from(200),
+            status,
+            deposit_amount: Some(BigDecimal::from(50)),
+            deposit_paid: false,
+            final_payment_paid: false,
+            response_deadline: None,
+            cancellation_reason: None,
+            completed_at: None,
+            review_window_closes_at: None,
+            cancellation_policy: CancellationPolicy::default(),
+            refund_amount: None,
+            refunded_at: None,
+            event_latitude: None,
+            event_longitude: None,
+            travel_distance_km: None,
+            travel_fee: BigDecimal::default(),
+            price_adjustment: BigDecimal::default(),
+            line_items: vec![],
+            created_at: chrono::Utc::now(),
+            updated_at: chrono::Utc::now(),
+        }
+    }
+
     #[derive(Debug, Clone)]
     pub struct MockAuthService;
 
@@ -113,17 +193,7 @@ mod mock_services {
     impl AuthService for MockAuthService {
         async fn register(&self, _pool: &PgPool, _req: CreateUserRequest) -> Result<AuthResponse> {
             Ok(AuthResponse {
-                user: UserResponse {
-                    id: Uuid::new_v4(),
-                    email: "test@example.com".to_string(),
-                    user_type: "customer".to_string(),
-                    full_name: "Test User".to_string(),
-                    phone_number: Some("1234567890".to_string()),
-                    profile_picture_url: None,
-                    is_verified: false,
-                    created_at: chrono::Utc::now(),
-                    updated_at: chrono::Utc::now(),
-                },
+                user: mock_user_response("Test User"),
                 access_token: "mock_token".to_string(),
                 token_type: "Bearer".to_string(),
                 expires_in: 604800,
@@ -132,17 +202,7 @@ mod mock_services {
 
         async fn login(&self, _pool: &PgPool, _req: LoginRequest) -> Result<AuthResponse> {
             Ok(AuthResponse {
-                user: UserResponse {
-                    id: Uuid::new_v4(),
-                    email: "test@example.com".to_string(),
-                    user_type: "customer".to_string(),
-                    full_name: "Test User".to_string(),
-                    phone_number: Some("1234567890".to_string()),
-                    profile_picture_url: None,
-                    is_verified: false,
-                    created_at: chrono::Utc::now(),
-                    updated_at: chrono::Utc::now(),
-                },
+                user: mock_user_response("Test User"),
                 access_token: "mock_token".to_string(),
                 token_type: "Bearer".to_string(),
                 expires_in: 604800,
@@ -160,31 +220,11 @@ mod mock_services {
     #[async_trait]
     impl UserService for MockUserService {
         async fn get_profile(&self, _pool: &PgPool, _auth_header: Option<String>) -> Result<UserResponse> {
-            Ok(UserResponse {
-                id: Uuid::new_v4(),
-                email: "test@example.com".to_string(),
-                user_type: "customer".to_string(),
-                full_name: "Test User".to_string(),
-                phone_number: Some("1234567890".to_string()),
-                profile_picture_url: None,
-                is_verified: false,
-                created_at: chrono::Utc::now(),
-                updated_at: chrono::Utc::now(),
-            })
+            Ok(mock_user_response("Test User"))
         }
 
         async fn update_profile(&self, _pool: &PgPool, _auth_header: Option<String>, _profile_data: Value) -> Result<UserResponse> {
-            Ok(UserResponse {
-                id: Uuid::new_v4(),
-                email: "test@example.com".to_string(),
-                user_type: "customer".to_string(),
-                full_name: "Updated User".to_string(),
-                phone_number: Some("1234567890".to_string()),
-                profile_picture_url: None,
-                is_verified: false,
-                created_at: chrono::Utc::now(),
-                updated_at: chrono::Utc::now(),
-            })
+            Ok(mock_user_response("Updated User"))
         }
     }
 
@@ -193,36 +233,16 @@ mod mock_services {
 
     #[async_trait]
     impl MuaService for MockMuaService {
-        async fn search_muas(&self, _pool: &PgPool, _params: SearchMuasRequest) -> Result<Vec<MuaProfileResponse>> {
-            Ok(vec![])
+        async fn search_muas(&self, _pool: &PgPool, _params: SearchMuasRequest) -> Result<Page<MuaProfileResponse>> {
+            Ok(Page::numbered(vec![], 0, 1, DEFAULT_PAGE_LIMIT))
         }
 
         async fn get_mua_by_id(&self, _pool: &PgPool, _mua_id: Uuid) -> Result<MuaProfileResponse> {
-            Ok(MuaProfileResponse {
-                id: Uuid::new_v4(),
-                user: User {
-                    id: Uuid::new_v4(),
-                    email: "mua@example.com".to_string(),
-                    password_hash: "hash".to_string(),
-                    user_type: "mua".to_string(),
-                    full_name: "MUA Artist".to_string(),
-                    phone_number: Some("1234567890".to_string()),
-                    profile_picture_url: None,
-                    is_verified: true,
-                    created_at: chrono::Utc::now(),
-                    updated_at: chrono::Utc::now(),
-                },
-                bio: Some("Professional makeup artist".to_string()),
-                experience_years: Some(5),
-                specialization: vec!["bridal".to_string()],
-                location: Some("New York".to_string()),
-                latitude: Some(40.7128),
-                longitude: Some(-74.0060),
-                is_available: true,
-                average_rating: Some(4.5),
-                total_reviews: Some(10),
-                created_at: chrono::Utc::now(),
-            })
+            Ok(mock_mua_profile())
+        }
+
+        async fn create_profile(&self, _pool: &PgPool, _auth_header: Option<String>, _profile_data: CreateMuaProfileRequest) -> Result<MuaProfileResponse> {
+            Ok(mock_mua_profile())
         }
 
         async fn create_portfolio_item(&self, _pool: &PgPool, _auth_header: Option<String>, _portfolio_data: Value) -> Result<Value> {
@@ -236,47 +256,19 @@ mod mock_services {
     #[async_trait]
     impl BookingService for MockBookingService {
         async fn create_booking(&self, _pool: &PgPool, _auth_header: Option<String>, _booking_data: CreateBookingRequest) -> Result<BookingResponse> {
-            Ok(BookingResponse {
-                id: Uuid::new_v4(),
-                customer_id: Uuid::new_v4(),
-                mua_id: Uuid::new_v4(),
-                service_type: "bridal".to_string(),
-                description: "Bridal makeup".to_string(),
-                event_date: chrono::Utc::now(),
-                event_location: "Venue".to_string(),
-                duration_hours: 4,
-                price: 200.0,
-                status: crate::models::BookingStatus::Pending,
-                deposit_amount: 50.0,
-                deposit_paid: false,
-                final_payment_paid: false,
-                created_at: chrono::Utc::now(),
-                updated_at: chrono::Utc::now(),
-            })
+            Ok(mock_booking(BookingStatus::Pending))
+        }
+
+        async fn get_user_bookings(&self, _pool: &PgPool, _auth_header: Option<String>, _query: BookingListQuery) -> Result<Page<BookingResponse>> {
+            Ok(Page { items: vec![], total: 0, page: None, limit: DEFAULT_PAGE_LIMIT, next_cursor: None })
         }
 
-        async fn get_user_bookings(&self, _pool: &PgPool, _auth_header: Option<String>) -> Result<Vec<BookingResponse>> {
-            Ok(vec![])
+        async fn get_booking_detail(&self, _pool: &PgPool, _auth_header: Option<String>, _booking_id: Uuid) -> Result<BookingDetail> {
+            Err(anyhow::anyhow!("Booking not found"))
         }
 
         async fn update_booking_status(&self, _pool: &PgPool, _auth_header: Option<String>, _booking_id: Uuid, _status_data: UpdateBookingStatusRequest) -> Result<BookingResponse> {
-            Ok(BookingResponse {
-                id: Uuid::new_v4(),
-                customer_id: Uuid::new_v4(),
-                mua_id: Uuid::new_v4(),
-                service_type: "bridal".to_string(),
-                description: "Bridal makeup".to_string(),
-                event_date: chrono::Utc::now(),
-                event_location: "Venue".to_string(),
-                duration_hours: 4,
-                price: 200.0,
-                status: crate::models::BookingStatus::Confirmed,
-                deposit_amount: 50.0,
-                deposit_paid: false,
-                final_payment_paid: false,
-                created_at: chrono::Utc::now(),
-                updated_at: chrono::Utc::now(),
-            })
+            Ok(mock_booking(BookingStatus::Confirmed))
         }
     }
 
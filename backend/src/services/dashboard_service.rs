@@ -1,13 +1,15 @@
 use anyhow::Result;
 use serde_json::Value;
 use uuid::Uuid;
-use sqlx::{PgPool, Row, types::BigDecimal};
+use sqlx::{PgPool, Row, types::{BigDecimal, Json}};
+use std::collections::HashMap;
 use std::str::FromStr;
 
 use crate::models::{
-    DashboardStats, DashboardResponse, RecentBooking,
-    PortfolioItem, CreatePortfolioRequest, UpdatePortfolioRequest,
-    availability::{CreateAvailabilityRequest, AvailabilitySlot, AvailabilityResponse, TimeSlotResponse}
+    DashboardStats, DashboardResponse, RecentBooking, BookingLineItem,
+    Page, PageNumberQuery, PortfolioItem, CreatePortfolioRequest, UpdatePortfolioRequest,
+    CancellationPolicy, UpdateCancellationPolicyRequest, TravelPricing, UpdateTravelPricingRequest, MuaTimezone, UpdateTimezoneRequest,
+    availability::{CreateAvailabilityRequest, AvailabilitySlot, AvailabilityResponse, TimeSlotResponse, BookingBuffers, UpdateBookingBuffersRequest, UpdateWeeklyScheduleRequest, WeeklySchedule}
 };
 use chrono::NaiveTime;
 use crate::models::dashboard::UpdateAvailabilityRequest;
@@ -63,20 +65,140 @@ impl DashboardServiceImpl {
 
         // Update availability in mua_profiles table
         let result = sqlx::query(
-            "UPDATE mua_profiles SET is_available = $1, updated_at = NOW() WHERE user_id = $2 RETURNING is_available"
+            "UPDATE mua_profiles SET is_available = $1, updated_at = NOW() WHERE user_id = $2 RETURNING id, is_available"
         )
         .bind(request.is_available)
         .bind(user_id)
         .fetch_one(pool)
         .await?;
 
+        if request.is_available {
+            schedule_favorite_openings(pool, result.get("id")).await;
+        }
+
         Ok(serde_json::json!({
             "is_available": result.get::<bool, _>("is_available"),
             "message": "Availability updated successfully"
         }))
     }
 
-    pub async fn get_portfolio_items(&self, pool: &PgPool, auth_header: Option<String>) -> Result<Vec<PortfolioItem>> {
+    pub async fn get_cancellation_policy(&self, pool: &PgPool, auth_header: Option<String>) -> Result<CancellationPolicy> {
+        let user_id = super::user_service::get_user_id_from_auth_header(auth_header)?;
+
+        let mua_id = self.mua_repository.get_mua_by_user_id(pool, user_id).await?
+            .ok_or_else(|| anyhow::anyhow!("MUA profile not found"))?;
+
+        super::cancellation_service::cancellation_policy_for_mua(pool, mua_id).await
+    }
+
+    /// Applies to bookings made from now on; existing bookings keep the policy they were made under
+    pub async fn update_cancellation_policy(
+        &self,
+        pool: &PgPool,
+        auth_header: Option<String>,
+        request: UpdateCancellationPolicyRequest
+    ) -> Result<CancellationPolicy> {
+        let user_id = super::user_service::get_user_id_from_auth_header(auth_header)?;
+
+        let mut policy = CancellationPolicy { tiers: request.tiers };
+        policy.validate()?;
+        policy.tiers.sort_by_key(|tier| std::cmp::Reverse(tier.min_hours_before));
+
+        let result = sqlx::query(
+            "UPDATE mua_profiles SET cancellation_policy = $1, updated_at = NOW() WHERE user_id = $2"
+        )
+        .bind(Json(&policy))
+        .bind(user_id)
+        .execute(pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(anyhow::anyhow!("MUA profile not found"));
+        }
+
+        Ok(policy)
+    }
+
+    pub async fn get_travel_pricing(&self, pool: &PgPool, auth_header: Option<String>) -> Result<TravelPricing> {
+        let user_id = super::user_service::get_user_id_from_auth_header(auth_header)?;
+
+        let mua_id = self.mua_repository.get_mua_by_user_id(pool, user_id).await?
+            .ok_or_else(|| anyhow::anyhow!("MUA profile not found"))?;
+
+        super::travel_service::travel_pricing_for_mua(pool, mua_id).await
+    }
+
+    /// Applies to bookings made from now on; existing bookings keep the fee they were priced with
+    pub async fn update_travel_pricing(
+        &self,
+        pool: &PgPool,
+        auth_header: Option<String>,
+        request: UpdateTravelPricingRequest
+    ) -> Result<TravelPricing> {
+        let user_id = super::user_service::get_user_id_from_auth_header(auth_header)?;
+
+        super::travel_service::update_travel_pricing(pool, user_id, request).await
+    }
+
+    pub async fn get_timezone(&self, pool: &PgPool, auth_header: Option<String>) -> Result<MuaTimezone> {
+        let user_id = super::user_service::get_user_id_from_auth_header(auth_header)?;
+
+        super::timezone_service::get_timezone(pool, user_id).await
+    }
+
+    pub async fn update_timezone(&self, pool: &PgPool, auth_header: Option<String>, request: UpdateTimezoneRequest) -> Result<MuaTimezone> {
+        let user_id = super::user_service::get_user_id_from_auth_header(auth_header)?;
+
+        super::timezone_service::update_timezone(pool, user_id, &request.timezone).await
+    }
+
+    pub async fn get_booking_buffers(&self, pool: &PgPool, auth_header: Option<String>) -> Result<BookingBuffers> {
+        let user_id = super::user_service::get_user_id_from_auth_header(auth_header)?;
+
+        let mua_id = self.mua_repository.get_mua_by_user_id(pool, user_id).await?
+            .ok_or_else(|| anyhow::anyhow!("MUA profile not found"))?;
+
+        super::availability_service::booking_buffers_for_mua(pool, mua_id).await
+    }
+
+    /// Checked against new confirmations only; bookings already confirmed stay as they are
+    pub async fn update_booking_buffers(
+        &self,
+        pool: &PgPool,
+        auth_header: Option<String>,
+        request: UpdateBookingBuffersRequest
+    ) -> Result<BookingBuffers> {
+        let user_id = super::user_service::get_user_id_from_auth_header(auth_header)?;
+
+        let buffers = BookingBuffers {
+            prep_minutes: request.prep_minutes,
+            travel_minutes: request.travel_minutes,
+            travel_minutes_per_km: request.travel_minutes_per_km,
+        };
+        buffers.validate()?;
+
+        let result = sqlx::query(
+            r#"
+            UPDATE mua_profiles
+            SET prep_buffer_minutes = $1, travel_buffer_minutes = $2, travel_buffer_minutes_per_km = $3, updated_at = NOW()
+            WHERE user_id = $4
+            "#
+        )
+        .bind(buffers.prep_minutes)
+        .bind(buffers.travel_minutes)
+        .bind(buffers.travel_minutes_per_km)
+        .bind(user_id)
+        .execute(pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Err(anyhow::anyhow!("MUA profile not found"));
+        }
+
+        Ok(buffers)
+    }
+
+    pub async fn get_portfolio_items(&self, pool: &PgPool, auth_header: Option<String>, page_query: PageNumberQuery) -> Result<Page<PortfolioItem>> {
         let user_id = super::user_service::get_user_id_from_auth_header(auth_header)?;
 
         // Get MUA profile ID
@@ -85,23 +207,32 @@ impl DashboardServiceImpl {
 
         // Get portfolio items
         let rows = sqlx::query(
-            "SELECT id, title, description, image_url, service_type, created_at FROM portfolio_items WHERE mua_id = $1 ORDER BY created_at DESC"
+            "SELECT id, title, description, image_url, thumbnail_url, medium_url, service_type, created_at FROM portfolio_items WHERE mua_id = $1 ORDER BY created_at DESC, id DESC LIMIT $2 OFFSET $3"
         )
         .bind(mua_id)
+        .bind(page_query.limit())
+        .bind(page_query.offset())
         .fetch_all(pool)
         .await?;
 
+        let total: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM portfolio_items WHERE mua_id = $1")
+            .bind(mua_id)
+            .fetch_one(pool)
+            .await?;
+
         let items = rows.into_iter().map(|row| PortfolioItem {
             id: row.get("id"),
             mua_id,
             title: row.get("title"),
             description: row.get("description"),
             image_url: row.get("image_url"),
+            thumbnail_url: row.get("thumbnail_url"),
+            medium_url: row.get("medium_url"),
             service_type: row.get("service_type"),
             created_at: row.get("created_at"),
         }).collect();
 
-        Ok(items)
+        Ok(Page::numbered(items, total, page_query.page(), page_query.limit()))
     }
 
     pub async fn create_portfolio_item(
@@ -116,21 +247,35 @@ impl DashboardServiceImpl {
         let mua_id = self.mua_repository.get_mua_by_user_id(pool, user_id).await?
             .ok_or_else(|| anyhow::anyhow!("MUA profile not found"))?;
 
+        // Make sure the presigned upload actually landed, then resize and strip it
+        let storage = super::object_storage::from_env().await?;
+        let (object_key, variants) = super::upload_session::attach_portfolio_upload(pool, storage.as_ref(), user_id, &request.image_url).await?;
+
         // Create portfolio item
-        let row = sqlx::query(
+        let row = match sqlx::query(
             r#"
-            INSERT INTO portfolio_items (mua_id, title, description, image_url, service_type, created_at)
-            VALUES ($1, $2, $3, $4, $5, NOW())
-            RETURNING id, title, description, image_url, service_type, created_at
+            INSERT INTO portfolio_items (mua_id, title, description, image_url, thumbnail_url, medium_url, service_type, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, NOW())
+            RETURNING id, title, description, image_url, thumbnail_url, medium_url, service_type, created_at
             "#
         )
         .bind(mua_id)
         .bind(&request.title)
         .bind(&request.description)
-        .bind(&request.image_url)
+        .bind(&variants.full_url)
+        .bind(&variants.thumbnail_url)
+        .bind(&variants.medium_url)
         .bind(&request.service_type)
         .fetch_one(pool)
-        .await?;
+        .await {
+            Ok(row) => row,
+            Err(e) => {
+                super::upload_session::discard_portfolio_upload(pool, &object_key, &variants).await?;
+                return Err(e.into());
+            }
+        };
+
+        super::upload_session::finish_upload(pool, &request.image_url).await?;
 
         Ok(PortfolioItem {
             id: row.get("id"),
@@ -138,6 +283,8 @@ impl DashboardServiceImpl {
             title: row.get("title"),
             description: row.get("description"),
             image_url: row.get("image_url"),
+            thumbnail_url: row.get("thumbnail_url"),
+            medium_url: row.get("medium_url"),
             service_type: row.get("service_type"),
             created_at: row.get("created_at"),
         })
@@ -176,12 +323,48 @@ impl DashboardServiceImpl {
         }
 
         if let Some(image_url) = &request.image_url {
-            sqlx::query("UPDATE portfolio_items SET image_url = $1, updated_at = NOW() WHERE id = $2 AND mua_id = $3")
-                .bind(image_url)
-                .bind(item_id)
-                .bind(mua_id)
-                .execute(pool)
-                .await?;
+            let storage = super::object_storage::from_env().await?;
+            let (object_key, variants) = super::upload_session::attach_portfolio_upload(pool, storage.as_ref(), user_id, image_url).await?;
+
+            // Swap the images and queue the old ones for removal in one transaction
+            let mut tx = pool.begin().await?;
+
+            let old_row = sqlx::query(
+                "SELECT image_url, thumbnail_url, medium_url FROM portfolio_items WHERE id = $1 AND mua_id = $2 FOR UPDATE"
+            )
+            .bind(item_id)
+            .bind(mua_id)
+            .fetch_optional(&mut tx)
+            .await?;
+
+            let Some(old_row) = old_row else {
+                drop(tx);
+                super::upload_session::discard_portfolio_upload(pool, &object_key, &variants).await?;
+                return Err(anyhow::anyhow!("Portfolio item not found"));
+            };
+
+            sqlx::query(
+                "UPDATE portfolio_items SET image_url = $1, thumbnail_url = $2, medium_url = $3, updated_at = NOW() WHERE id = $4 AND mua_id = $5"
+            )
+            .bind(&variants.full_url)
+            .bind(&variants.thumbnail_url)
+            .bind(&variants.medium_url)
+            .bind(item_id)
+            .bind(mua_id)
+            .execute(&mut tx)
+            .await?;
+
+            let old_urls = [
+                Some(old_row.get::<String, _>("image_url")),
+                old_row.get::<Option<String>, _>("thumbnail_url"),
+                old_row.get::<Option<String>, _>("medium_url"),
+            ];
+            for old_url in old_urls.into_iter().flatten() {
+                super::storage_outbox::enqueue_deletion(&mut tx, &old_url).await?;
+            }
+
+            tx.commit().await?;
+            super::upload_session::finish_upload(pool, image_url).await?;
         }
 
         if let Some(service_type) = &request.service_type {
@@ -194,7 +377,7 @@ impl DashboardServiceImpl {
         }
 
         let row = sqlx::query(
-            "SELECT id, title, description, image_url, service_type, created_at FROM portfolio_items WHERE id = $1 AND mua_id = $2"
+            "SELECT id, title, description, image_url, thumbnail_url, medium_url, service_type, created_at FROM portfolio_items WHERE id = $1 AND mua_id = $2"
         )
         .bind(item_id)
         .bind(mua_id)
@@ -207,6 +390,8 @@ impl DashboardServiceImpl {
             title: row.get("title"),
             description: row.get("description"),
             image_url: row.get("image_url"),
+            thumbnail_url: row.get("thumbnail_url"),
+            medium_url: row.get("medium_url"),
             service_type: row.get("service_type"),
             created_at: row.get("created_at"),
         })
@@ -219,17 +404,29 @@ impl DashboardServiceImpl {
         let mua_id = self.mua_repository.get_mua_by_user_id(pool, user_id).await?
             .ok_or_else(|| anyhow::anyhow!("MUA profile not found"))?;
 
-        // Delete portfolio item
-        let result = sqlx::query("DELETE FROM portfolio_items WHERE id = $1 AND mua_id = $2")
-            .bind(item_id)
-            .bind(mua_id)
-            .execute(pool)
-            .await?;
+        // Delete portfolio item and queue its image for removal in one transaction
+        let mut tx = pool.begin().await?;
 
-        if result.rows_affected() == 0 {
-            return Err(anyhow::anyhow!("Portfolio item not found or you don't have permission to delete it"));
+        let row = sqlx::query(
+            "DELETE FROM portfolio_items WHERE id = $1 AND mua_id = $2 RETURNING image_url, thumbnail_url, medium_url"
+        )
+        .bind(item_id)
+        .bind(mua_id)
+        .fetch_optional(&mut tx)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("Portfolio item not found or you don't have permission to delete it"))?;
+
+        let image_urls = [
+            Some(row.get::<String, _>("image_url")),
+            row.get::<Option<String>, _>("thumbnail_url"),
+            row.get::<Option<String>, _>("medium_url"),
+        ];
+        for image_url in image_urls.into_iter().flatten() {
+            super::storage_outbox::enqueue_deletion(&mut tx, &image_url).await?;
         }
 
+        tx.commit().await?;
+
         Ok(serde_json::json!({
             "message": "Portfolio item deleted successfully"
         }))
@@ -304,13 +501,21 @@ impl DashboardServiceImpl {
         .fetch_all(pool)
         .await?;
 
-        let bookings = rows.into_iter().map(|row| RecentBooking {
-            id: row.get("id"),
-            customer_name: row.get("customer_name"),
-            service_type: row.get("service_type"),
-            event_date: row.get("event_date"),
-            status: row.get::<String, _>("status"),
-            price: row.get::<BigDecimal, _>("price"),
+        let booking_ids: Vec<Uuid> = rows.iter().map(|row| row.get("id")).collect();
+        let mut line_items = self.line_items_by_booking(pool, &booking_ids).await?;
+        let timezone = super::timezone_service::timezone_for_mua(pool, mua_id).await?;
+
+        let bookings = rows.into_iter().map(|row| {
+            let id: Uuid = row.get("id");
+            RecentBooking {
+                id,
+                customer_name: row.get("customer_name"),
+                service_type: row.get("service_type"),
+                event_date: super::timezone_service::local_time(row.get("event_date"), timezone),
+                status: row.get::<String, _>("status"),
+                price: row.get::<BigDecimal, _>("price"),
+                line_items: line_items.remove(&id).unwrap_or_default(),
+            }
         }).collect();
 
         Ok(bookings)
@@ -332,13 +537,21 @@ impl DashboardServiceImpl {
         .fetch_all(pool)
         .await?;
 
-        let bookings = rows.into_iter().map(|row| RecentBooking {
-            id: row.get("id"),
-            customer_name: row.get("customer_name"),
-            service_type: row.get("service_type"),
-            event_date: row.get("event_date"),
-            status: row.get::<String, _>("status"),
-            price: row.get::<BigDecimal, _>("price"),
+        let booking_ids: Vec<Uuid> = rows.iter().map(|row| row.get("id")).collect();
+        let mut line_items = self.line_items_by_booking(pool, &booking_ids).await?;
+        let timezone = super::timezone_service::timezone_for_mua(pool, mua_id).await?;
+
+        let bookings = rows.into_iter().map(|row| {
+            let id: Uuid = row.get("id");
+            RecentBooking {
+                id,
+                customer_name: row.get("customer_name"),
+                service_type: row.get("service_type"),
+                event_date: super::timezone_service::local_time(row.get("event_date"), timezone),
+                status: row.get::<String, _>("status"),
+                price: row.get::<BigDecimal, _>("price"),
+                line_items: line_items.remove(&id).unwrap_or_default(),
+            }
         }).collect();
 
         Ok(bookings)
@@ -355,6 +568,7 @@ impl DashboardServiceImpl {
         // Get MUA profile ID
         let mua_id = self.mua_repository.get_mua_by_user_id(pool, user_id).await?
             .ok_or_else(|| anyhow::anyhow!("MUA profile not found"))?;
+        let timezone = super::timezone_service::timezone_for_mua(pool, mua_id).await?;
 
         // Handle recurring slots with multiple days
         if request.recurring {
@@ -388,6 +602,7 @@ impl DashboardServiceImpl {
                         end_time: slot.end_time.to_string(),
                         day_of_week: slot.day_of_week,
                         specific_date: slot.specific_date,
+                        timezone: timezone.name().to_string(),
                         is_available: slot.is_available,
                         recurring: slot.recurring,
                         created_at: slot.created_at.unwrap_or_else(|| chrono::Utc::now()),
@@ -395,6 +610,8 @@ impl DashboardServiceImpl {
                     });
                 }
 
+                schedule_favorite_openings(pool, mua_id).await;
+
                 // Return the first created slot for now (could return all if needed)
                 if let Some(first_slot) = created_slots.into_iter().next() {
                     return Ok(first_slot);
@@ -405,17 +622,13 @@ impl DashboardServiceImpl {
         } else {
             // Handle specific date availability
             if let Some(date_str) = request.specific_date {
-                // Try multiple date formats
+                // A day on the MUA's calendar; full timestamps are read in their timezone
                 let specific_date = if let Ok(dt) = chrono::DateTime::parse_from_rfc3339(&date_str) {
-                    dt.with_timezone(&chrono::Utc)
+                    dt.with_timezone(&timezone).date_naive()
                 } else if let Ok(date) = chrono::NaiveDate::parse_from_str(&date_str, "%Y-%m-%d") {
-                    if let Some(datetime) = date.and_hms_opt(0, 0, 0) {
-                        chrono::DateTime::from_naive_utc_and_offset(datetime, chrono::Utc)
-                    } else {
-                        return Err(anyhow::anyhow!("Invalid date format: '{}'. Expected RFC3339, YYYY-MM-DD, or YYYY-MM-DD HH:MM:SS", date_str));
-                    }
+                    date
                 } else if let Ok(dt) = chrono::NaiveDateTime::parse_from_str(&date_str, "%Y-%m-%d %H:%M:%S") {
-                    chrono::DateTime::from_naive_utc_and_offset(dt, chrono::Utc)
+                    dt.date()
                 } else {
                     return Err(anyhow::anyhow!("Invalid date format: '{}'. Expected RFC3339, YYYY-MM-DD, or YYYY-MM-DD HH:MM:SS", date_str));
                 };
@@ -439,6 +652,8 @@ impl DashboardServiceImpl {
                 .fetch_one(pool)
                 .await?;
 
+                schedule_favorite_openings(pool, mua_id).await;
+
                 return Ok(AvailabilityResponse {
                     id: slot.id,
                     mua_id: slot.mua_id,
@@ -446,6 +661,7 @@ impl DashboardServiceImpl {
                     end_time: slot.end_time.to_string(),
                     day_of_week: slot.day_of_week,
                     specific_date: slot.specific_date,
+                    timezone: timezone.name().to_string(),
                     is_available: slot.is_available,
                     recurring: slot.recurring,
                     created_at: slot.created_at.unwrap_or_else(|| chrono::Utc::now()),
@@ -459,12 +675,100 @@ impl DashboardServiceImpl {
         Err(anyhow::anyhow!("Failed to create availability slot"))
     }
 
+    pub async fn get_weekly_schedule(&self, pool: &PgPool, auth_header: Option<String>) -> Result<WeeklySchedule> {
+        let user_id = super::user_service::get_user_id_from_auth_header(auth_header)?;
+
+        let mua_id = self.mua_repository.get_mua_by_user_id(pool, user_id).await?
+            .ok_or_else(|| anyhow::anyhow!("MUA profile not found"))?;
+
+        self.weekly_schedule_for_mua(pool, mua_id).await
+    }
+
+    /// Swap all of the MUA's recurring slots, including ones marked unavailable, for the
+    /// given week in one transaction. Slots for specific dates stay, and bookings already
+    /// made aren't checked against the new week.
+    pub async fn replace_weekly_schedule(
+        &self,
+        pool: &PgPool,
+        auth_header: Option<String>,
+        request: UpdateWeeklyScheduleRequest,
+    ) -> Result<WeeklySchedule> {
+        let user_id = super::user_service::get_user_id_from_auth_header(auth_header)?;
+
+        let mua_id = self.mua_repository.get_mua_by_user_id(pool, user_id).await?
+            .ok_or_else(|| anyhow::anyhow!("MUA profile not found"))?;
+
+        let week = request.normalize()?;
+
+        let mut tx = pool.begin().await?;
+
+        // The lock bookings take while checking availability, so none sees half a week
+        sqlx::query("SELECT id FROM mua_profiles WHERE id = $1 FOR UPDATE")
+            .bind(mua_id)
+            .execute(&mut tx)
+            .await?;
+
+        sqlx::query("DELETE FROM availability_slots WHERE mua_id = $1 AND recurring = true")
+            .bind(mua_id)
+            .execute(&mut tx)
+            .await?;
+
+        for (day_of_week, ranges) in week.iter().enumerate() {
+            for (start_time, end_time) in ranges {
+                sqlx::query(
+                    r#"
+                    INSERT INTO availability_slots (mua_id, start_time, end_time, day_of_week, is_available, recurring, created_at, updated_at)
+                    VALUES ($1, $2, $3, $4, true, true, NOW(), NOW())
+                    "#
+                )
+                .bind(mua_id)
+                .bind(start_time)
+                .bind(end_time)
+                .bind(day_of_week as i32)
+                .execute(&mut tx)
+                .await?;
+            }
+        }
+
+        tx.commit().await?;
+
+        schedule_favorite_openings(pool, mua_id).await;
+
+        self.weekly_schedule_for_mua(pool, mua_id).await
+    }
+
+    async fn weekly_schedule_for_mua(&self, pool: &PgPool, mua_id: Uuid) -> Result<WeeklySchedule> {
+        let timezone = super::timezone_service::timezone_for_mua(pool, mua_id).await?;
+
+        let rows = sqlx::query(
+            r#"
+            SELECT day_of_week, start_time, end_time FROM availability_slots
+            WHERE mua_id = $1 AND recurring = true AND is_available = true
+            ORDER BY day_of_week, start_time
+            "#
+        )
+        .bind(mua_id)
+        .fetch_all(pool)
+        .await?;
+
+        let mut week: [Vec<(NaiveTime, NaiveTime)>; 7] = Default::default();
+        for row in rows {
+            let day_of_week: Option<i32> = row.get("day_of_week");
+            if let Some(ranges) = day_of_week.and_then(|day| week.get_mut(day as usize)) {
+                ranges.push((row.get("start_time"), row.get("end_time")));
+            }
+        }
+
+        Ok(WeeklySchedule::new(timezone.name().to_string(), &week))
+    }
+
     pub async fn get_availability_slots(&self, pool: &PgPool, auth_header: Option<String>) -> Result<Vec<TimeSlotResponse>> {
         let user_id = super::user_service::get_user_id_from_auth_header(auth_header)?;
 
         // Get MUA profile ID
         let mua_id = self.mua_repository.get_mua_by_user_id(pool, user_id).await?
             .ok_or_else(|| anyhow::anyhow!("MUA profile not found"))?;
+        let timezone = super::timezone_service::timezone_for_mua(pool, mua_id).await?;
 
         // Get availability slots from database
         let rows = sqlx::query!(
@@ -486,7 +790,8 @@ impl DashboardServiceImpl {
             is_available: row.is_available,
             recurring: row.recurring,
             day_of_week: row.day_of_week,
-            specific_date: row.specific_date.map(|dt| dt.to_rfc3339()),
+            specific_date: row.specific_date.map(|date| date.format("%Y-%m-%d").to_string()),
+            timezone: timezone.name().to_string(),
         }).collect();
 
         Ok(slots)
@@ -499,6 +804,11 @@ impl DashboardServiceImpl {
         let mua_id = self.mua_repository.get_mua_by_user_id(pool, user_id).await?
             .ok_or_else(|| anyhow::anyhow!("MUA profile not found"))?;
 
+        self.calendar_bookings_for_mua(pool, mua_id, start_date, end_date).await
+    }
+
+    /// Bookings for the calendar view and the ICS feed; empty dates mean no range limit
+    pub async fn calendar_bookings_for_mua(&self, pool: &PgPool, mua_id: Uuid, start_date: &str, end_date: &str) -> Result<Vec<crate::models::availability::CalendarBooking>> {
         // If no date range provided, get all bookings
         let date_filter = if !start_date.is_empty() && !end_date.is_empty() {
             format!("AND b.event_date >= '{}' AND b.event_date <= '{}'", start_date, end_date)
@@ -518,7 +828,9 @@ impl DashboardServiceImpl {
                 b.status::text as status,
                 b.event_location as location,
                 b.description as notes,
-                b.price
+                b.price,
+                b.event_latitude,
+                b.event_longitude
             FROM bookings b
             JOIN users u ON b.customer_id = u.id
             WHERE b.mua_id = $1 {}
@@ -532,27 +844,84 @@ impl DashboardServiceImpl {
             .fetch_all(pool)
             .await?;
 
-        let bookings = rows.into_iter().map(|row| {
+        let booking_ids: Vec<Uuid> = rows.iter().map(|row| row.get("id")).collect();
+        let mut line_items = self.line_items_by_booking(pool, &booking_ids).await?;
+        let timezone = super::timezone_service::timezone_for_mua(pool, mua_id).await?;
+        let local_time = |row: &sqlx::postgres::PgRow, column: &str| {
+            super::timezone_service::local_time(row.get(column), timezone)
+        };
+
+        let coordinates: Vec<Option<(f64, f64)>> = rows.iter()
+            .map(|row| row.get::<Option<f64>, _>("event_latitude").zip(row.get::<Option<f64>, _>("event_longitude")))
+            .collect();
+
+        let mut bookings: Vec<crate::models::availability::CalendarBooking> = rows.into_iter().map(|row| {
+            let id: Uuid = row.get("id");
             let price_str: String = row.get::<BigDecimal, _>("price").to_string();
             crate::models::availability::CalendarBooking {
-                id: row.get("id"),
+                id,
                 customer_name: row.get("customer_name"),
                 customer_phone: row.get("customer_phone"),
                 service_type: row.get("service_type"),
-                start_time: row.get("start_time"),
-                end_time: row.get("end_time"),
+                start_time: local_time(&row, "start_time"),
+                end_time: local_time(&row, "end_time"),
                 status: row.get("status"),
                 location: row.get("location"),
                 notes: row.get("notes"),
                 price: price_str.parse::<f64>().unwrap_or(0.0),
+                line_items: line_items.remove(&id).unwrap_or_default(),
+                blocked_from: local_time(&row, "start_time"),
+                blocked_until: local_time(&row, "end_time"),
             }
         }).collect();
 
+        // Block out the buffer towards the nearest confirmed booking on either side,
+        // or the base buffer when there is none in range
+        let buffers = super::availability_service::booking_buffers_for_mua(pool, mua_id).await?;
+        let confirmed: Vec<usize> = (0..bookings.len()).filter(|&i| bookings[i].status == "confirmed").collect();
+        let distance = |a: usize, b: usize| match (coordinates[a], coordinates[b]) {
+            (Some((a_latitude, a_longitude)), Some((b_latitude, b_longitude))) => {
+                Some(super::travel_service::distance_km(a_latitude, a_longitude, b_latitude, b_longitude))
+            }
+            _ => None,
+        };
+
+        for i in 0..bookings.len() {
+            let previous = confirmed.iter().copied()
+                .filter(|&j| j != i && bookings[j].end_time <= bookings[i].start_time)
+                .max_by_key(|&j| bookings[j].end_time);
+            let next = confirmed.iter().copied()
+                .filter(|&j| j != i && bookings[j].start_time >= bookings[i].end_time)
+                .min_by_key(|&j| bookings[j].start_time);
+
+            let before = buffers.minutes_between(previous.and_then(|j| distance(j, i)));
+            let after = buffers.minutes_between(next.and_then(|j| distance(i, j)));
+            bookings[i].blocked_from = bookings[i].start_time - chrono::Duration::minutes(before);
+            bookings[i].blocked_until = bookings[i].end_time + chrono::Duration::minutes(after);
+        }
+
         Ok(bookings)
     }
+
+    /// Line items for the given bookings, so dashboards can show each service and head count
+    async fn line_items_by_booking(&self, pool: &PgPool, booking_ids: &[Uuid]) -> Result<HashMap<Uuid, Vec<BookingLineItem>>> {
+        let mut line_items: HashMap<Uuid, Vec<BookingLineItem>> = HashMap::new();
+        for item in self.booking_repository.find_line_items(pool, booking_ids).await? {
+            line_items.entry(item.booking_id).or_default().push(item);
+        }
+
+        Ok(line_items)
+    }
 }
 
 // Legacy functions for backward compatibility
+/// The schedule gained room; the change itself stands even if the check can't be queued
+async fn schedule_favorite_openings(pool: &PgPool, mua_id: Uuid) {
+    if let Err(e) = super::notification_service::schedule_favorite_openings(pool, mua_id).await {
+        eprintln!("Failed to schedule favorite openings notifications: {}", e);
+    }
+}
+
 pub async fn get_dashboard(pool: &PgPool, auth_header: Option<String>) -> Result<DashboardResponse> {
     let user_repository = crate::repository::UserRepositoryImpl::new();
     let mua_repository = crate::repository::MuaRepositoryImpl::new();
@@ -0,0 +1,187 @@
+use anyhow::Result;
+use chrono::{DateTime, NaiveDate, Utc};
+use sqlx::{PgPool, Postgres, QueryBuilder, Row};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+use crate::models::{Cursor, Favorite, FavoriteMua, MuaProfileResponse, Page, PageQuery, SaveFavoriteRequest, NEWEST_FIRST};
+use crate::repository::mua_repository::{mua_profile_from_row, push_pricing_join, MUA_PROFILE_COLUMNS};
+
+/// Save a MUA, or change the date a saved one is watched for.
+///
+/// If the MUA already has openings on that date there's nothing new to tell,
+/// so the customer is only notified about openings that appear later.
+pub async fn save_favorite(pool: &PgPool, user_id: Uuid, mua_id: Uuid, request: SaveFavoriteRequest) -> Result<Favorite> {
+    let event_date = request.event_date
+        .map(|date| {
+            NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d")
+                .map_err(|_| anyhow::anyhow!("Invalid date format: '{}'. Expected YYYY-MM-DD", date))
+        })
+        .transpose()?;
+
+    let mua_exists: bool = sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM mua_profiles WHERE id = $1)")
+        .bind(mua_id)
+        .fetch_one(pool)
+        .await?;
+    if !mua_exists {
+        return Err(anyhow::anyhow!("MUA not found"));
+    }
+
+    let already_open = match event_date {
+        Some(date) => !super::availability_service::bookable_slots(pool, mua_id, date, 1, None).await?.is_empty(),
+        None => false,
+    };
+
+    let row = sqlx::query(
+        r#"
+        INSERT INTO favorites (user_id, mua_id, event_date, openings_notified_at, created_at)
+        VALUES ($1, $2, $3, CASE WHEN $4 THEN NOW() END, NOW())
+        ON CONFLICT (user_id, mua_id) DO UPDATE
+        SET event_date = EXCLUDED.event_date,
+            openings_notified_at = CASE
+                WHEN favorites.event_date IS NOT DISTINCT FROM EXCLUDED.event_date THEN favorites.openings_notified_at
+                ELSE EXCLUDED.openings_notified_at
+            END
+        RETURNING mua_id, event_date, created_at
+        "#
+    )
+    .bind(user_id)
+    .bind(mua_id)
+    .bind(event_date)
+    .bind(already_open)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(Favorite {
+        mua_id: row.get("mua_id"),
+        event_date: row.get("event_date"),
+        created_at: row.get("created_at"),
+    })
+}
+
+pub async fn remove_favorite(pool: &PgPool, user_id: Uuid, mua_id: Uuid) -> Result<()> {
+    let result = sqlx::query("DELETE FROM favorites WHERE user_id = $1 AND mua_id = $2")
+        .bind(user_id)
+        .bind(mua_id)
+        .execute(pool)
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(anyhow::anyhow!("Favorite not found"));
+    }
+
+    Ok(())
+}
+
+/// The user's saved MUAs, most recently saved first
+pub async fn get_favorites(pool: &PgPool, user_id: Uuid, page_query: PageQuery) -> Result<Page<FavoriteMua>> {
+    let mut sql = QueryBuilder::<Postgres>::new("SELECT f.event_date, f.created_at AS saved_at, ");
+    sql.push(MUA_PROFILE_COLUMNS);
+    sql.push(
+        " FROM favorites f
+          JOIN mua_profiles mp ON mp.id = f.mua_id
+          JOIN users u ON mp.user_id = u.id"
+    );
+    push_pricing_join(&mut sql, None);
+    sql.push(" WHERE f.user_id = ");
+    sql.push_bind(user_id);
+    page_query.push_keyset(&mut sql, NEWEST_FIRST, "f.created_at", "f.mua_id", true)?;
+
+    let favorites = sql.build()
+        .fetch_all(pool)
+        .await?
+        .into_iter()
+        .map(|row| {
+            let mut mua = mua_profile_from_row(&row);
+            mua.is_favorite = Some(true);
+            FavoriteMua {
+                mua,
+                event_date: row.get("event_date"),
+                saved_at: row.get::<DateTime<Utc>, _>("saved_at"),
+            }
+        })
+        .collect();
+
+    let total: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM favorites WHERE user_id = $1")
+        .bind(user_id)
+        .fetch_one(pool)
+        .await?;
+
+    Ok(Page::keyset(favorites, total, page_query.limit(), |favorite| Cursor::new(NEWEST_FIRST, favorite.saved_at, favorite.mua.id)))
+}
+
+/// Fill in `is_favorite` for a signed-in user; anonymous requests leave it out
+pub async fn mark_favorites(pool: &PgPool, user_id: Option<Uuid>, muas: &mut [MuaProfileResponse]) -> Result<()> {
+    let Some(user_id) = user_id else {
+        return Ok(());
+    };
+
+    let mua_ids: Vec<Uuid> = muas.iter().map(|mua| mua.id).collect();
+    let saved: HashSet<Uuid> = sqlx::query_scalar("SELECT mua_id FROM favorites WHERE user_id = $1 AND mua_id = ANY($2)")
+        .bind(user_id)
+        .bind(&mua_ids)
+        .fetch_all(pool)
+        .await?
+        .into_iter()
+        .collect();
+
+    for mua in muas {
+        mua.is_favorite = Some(saved.contains(&mua.id));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn insert_user(pool: &PgPool, user_type: &str, full_name: &str) -> Uuid {
+        sqlx::query_scalar(
+            "INSERT INTO users (email, password_hash, user_type, full_name) VALUES ($1, 'x', $2::user_type, $3) RETURNING id"
+        )
+        .bind(format!("{}@test.muaconnect.dev", Uuid::new_v4()))
+        .bind(user_type)
+        .bind(full_name)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+    }
+
+    async fn insert_favorite(pool: &PgPool, customer_id: Uuid, full_name: &str, days_ago: i32) -> Uuid {
+        let user_id = insert_user(pool, "mua", full_name).await;
+        let mua_id: Uuid = sqlx::query_scalar("INSERT INTO mua_profiles (user_id, location) VALUES ($1, 'Jakarta') RETURNING id")
+            .bind(user_id)
+            .fetch_one(pool)
+            .await
+            .unwrap();
+
+        sqlx::query("INSERT INTO favorites (user_id, mua_id, created_at) VALUES ($1, $2, NOW() - make_interval(days => $3))")
+            .bind(customer_id)
+            .bind(mua_id)
+            .bind(days_ago)
+            .execute(pool)
+            .await
+            .unwrap();
+        mua_id
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn favorites_page_newest_first_with_their_profiles(pool: PgPool) {
+        let customer_id = insert_user(&pool, "customer", "Intan").await;
+        let oldest = insert_favorite(&pool, customer_id, "Putri Ayu", 3).await;
+        let newest = insert_favorite(&pool, customer_id, "Sari Dewi", 1).await;
+        let middle = insert_favorite(&pool, customer_id, "Rina Lestari", 2).await;
+
+        let first = get_favorites(&pool, customer_id, PageQuery { cursor: None, limit: Some(2) }).await.unwrap();
+        assert_eq!(first.total, 3);
+        assert_eq!(first.items.iter().map(|f| f.mua.id).collect::<Vec<_>>(), vec![newest, middle]);
+        assert_eq!(first.items[0].mua.user.full_name, "Sari Dewi");
+        assert_eq!(first.items[0].mua.is_favorite, Some(true));
+
+        let second = get_favorites(&pool, customer_id, PageQuery { cursor: first.next_cursor, limit: Some(2) }).await.unwrap();
+        assert_eq!(second.items.iter().map(|f| f.mua.id).collect::<Vec<_>>(), vec![oldest]);
+        assert_eq!(second.items[0].mua.user.full_name, "Putri Ayu");
+        assert!(second.next_cursor.is_none());
+    }
+}
@@ -0,0 +1,666 @@
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, TimeZone, Utc, Weekday};
+use chrono_tz::Tz;
+
+/// One VEVENT in a calendar we publish
+pub struct CalendarEvent {
+    pub uid: String,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub summary: String,
+    pub location: Option<String>,
+    pub description: Option<String>,
+    /// CONFIRMED or TENTATIVE
+    pub status: &'static str,
+}
+
+/// A VEVENT read from someone else's calendar, reduced to when it keeps the MUA busy
+#[derive(Debug, Clone)]
+pub struct BusyEvent {
+    pub uid: Option<String>,
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub summary: Option<String>,
+}
+
+/// Render `events` as an RFC 5545 VCALENDAR
+pub fn render_calendar(name: &str, events: &[CalendarEvent]) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//MUA Connect//Bookings//ID".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+        "METHOD:PUBLISH".to_string(),
+        format!("X-WR-CALNAME:{}", escape_text(name)),
+    ];
+
+    let now = format_timestamp(Utc::now());
+    for event in events {
+        lines.push("BEGIN:VEVENT".to_string());
+        lines.push(format!("UID:{}", event.uid));
+        lines.push(format!("DTSTAMP:{}", now));
+        lines.push(format!("DTSTART:{}", format_timestamp(event.start)));
+        lines.push(format!("DTEND:{}", format_timestamp(event.end)));
+        lines.push(format!("SUMMARY:{}", escape_text(&event.summary)));
+        if let Some(location) = &event.location {
+            lines.push(format!("LOCATION:{}", escape_text(location)));
+        }
+        if let Some(description) = &event.description {
+            lines.push(format!("DESCRIPTION:{}", escape_text(description)));
+        }
+        lines.push(format!("STATUS:{}", event.status));
+        lines.push("END:VEVENT".to_string());
+    }
+
+    lines.push("END:VCALENDAR".to_string());
+
+    lines.iter().map(|line| fold_line(line)).collect::<Vec<_>>().concat()
+}
+
+fn format_timestamp(timestamp: DateTime<Utc>) -> String {
+    timestamp.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// Escape a TEXT value (RFC 5545 3.3.11)
+fn escape_text(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace(['\n', '\r'], "\\n")
+}
+
+/// Break a content line into 75-octet chunks without splitting a UTF-8 character, ending in CRLF
+fn fold_line(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len() + 8);
+    let mut line_octets = 0;
+
+    for c in line.chars() {
+        if line_octets + c.len_utf8() > 75 {
+            folded.push_str("\r\n ");
+            line_octets = 1;
+        }
+        folded.push(c);
+        line_octets += c.len_utf8();
+    }
+
+    folded.push_str("\r\n");
+    folded
+}
+
+/// Pull the busy time out of an ICS document.
+///
+/// Cancelled and transparent (free) events are skipped, as are events whose
+/// times can't be read. Recurring events are expanded into their occurrences
+/// that overlap `from..until`, leaving out EXDATEs and occurrences replaced by
+/// a RECURRENCE-ID override. Times with a known IANA TZID are read in that zone;
+/// floating times, all-day dates and unknown TZIDs in `default_timezone`.
+pub fn parse_busy_events(ics: &str, default_timezone: Tz, from: DateTime<Utc>, until: DateTime<Utc>) -> Vec<BusyEvent> {
+    let mut components: Vec<Vec<(String, String)>> = Vec::new();
+    let mut current: Option<Vec<(String, String)>> = None;
+
+    for line in unfold_lines(ics) {
+        let Some((name_and_params, value)) = line.split_once(':') else {
+            continue;
+        };
+        let mut parts = name_and_params.split(';');
+        let name = parts.next().unwrap_or_default().to_ascii_uppercase();
+        // TZID values are case sensitive, so params keep their case
+        let params = parts.collect::<Vec<_>>().join(";");
+
+        match (name.as_str(), value.trim()) {
+            ("BEGIN", "VEVENT") => current = Some(Vec::new()),
+            ("END", "VEVENT") => components.extend(current.take()),
+            _ => {
+                if let Some(properties) = current.as_mut() {
+                    properties.push((format!("{};{}", name, params), value.trim().to_string()));
+                }
+            }
+        }
+    }
+
+    // Occurrences moved or cancelled on their own, which the series must not repeat
+    let overridden: Vec<(String, CalendarTime)> = components.iter()
+        .filter_map(|properties| {
+            let uid = find_property(properties, "UID")?.1.clone();
+            let (key, value) = find_property(properties, "RECURRENCE-ID")?;
+            Some((uid, parse_date_time(key, value, default_timezone)?))
+        })
+        .collect();
+
+    components.iter()
+        .flat_map(|properties| busy_events(properties, default_timezone, &overridden, from, until))
+        .collect()
+}
+
+fn find_property<'a>(properties: &'a [(String, String)], wanted: &str) -> Option<&'a (String, String)> {
+    properties.iter().find(|(key, _)| key.split(';').next() == Some(wanted))
+}
+
+fn busy_events(
+    properties: &[(String, String)],
+    default_timezone: Tz,
+    overridden: &[(String, CalendarTime)],
+    from: DateTime<Utc>,
+    until: DateTime<Utc>,
+) -> Vec<BusyEvent> {
+    let property = |wanted: &str| find_property(properties, wanted);
+
+    let status = property("STATUS").map(|(_, value)| value.to_ascii_uppercase());
+    let transparency = property("TRANSP").map(|(_, value)| value.to_ascii_uppercase());
+    if status.as_deref() == Some("CANCELLED") || transparency.as_deref() == Some("TRANSPARENT") {
+        return Vec::new();
+    }
+
+    let Some((start_key, start_value)) = property("DTSTART") else {
+        return Vec::new();
+    };
+    let Some(start) = parse_date_time(start_key, start_value, default_timezone) else {
+        return Vec::new();
+    };
+    let Some(start_utc) = start.to_utc() else {
+        return Vec::new();
+    };
+
+    let end = match (property("DTEND"), property("DURATION")) {
+        (Some((end_key, end_value)), _) => parse_date_time(end_key, end_value, default_timezone).and_then(CalendarTime::to_utc),
+        (None, Some((_, duration))) => parse_duration(duration).map(|duration| start_utc + duration),
+        (None, None) if start.all_day => Some(start_utc + Duration::days(1)),
+        (None, None) => None,
+    };
+    let Some(end_utc) = end.filter(|end| *end > start_utc) else {
+        return Vec::new();
+    };
+    let length = end_utc - start_utc;
+
+    let uid = property("UID").map(|(_, value)| value.clone());
+    let summary = property("SUMMARY").map(|(_, value)| unescape_text(value));
+    let busy_event = |start: DateTime<Utc>| BusyEvent { uid: uid.clone(), start, end: start + length, summary: summary.clone() };
+
+    // Overrides and one-off events stand for themselves
+    let rule = property("RRULE")
+        .filter(|_| property("RECURRENCE-ID").is_none())
+        .and_then(|(_, value)| RecurrenceRule::parse(value, start, default_timezone));
+    let Some(rule) = rule else {
+        return vec![busy_event(start_utc)];
+    };
+
+    let mut excluded: Vec<CalendarTime> = properties.iter()
+        .filter(|(key, _)| key.split(';').next() == Some("EXDATE"))
+        .flat_map(|(key, value)| value.split(',').filter_map(|value| parse_date_time(key, value.trim(), default_timezone)))
+        .collect();
+    if let Some(uid) = &uid {
+        excluded.extend(overridden.iter().filter(|(overridden_uid, _)| overridden_uid == uid).map(|(_, time)| *time));
+    }
+
+    rule.occurrences(start, until)
+        .into_iter()
+        .filter(|occurrence| !excluded.iter().any(|excluded| excluded.matches(occurrence)))
+        .filter_map(CalendarTime::to_utc)
+        .filter(|occurrence_start| *occurrence_start + length > from)
+        .map(busy_event)
+        .collect()
+}
+
+/// A DATE or DATE-TIME value as written, so recurrences can step in its own zone
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct CalendarTime {
+    local: NaiveDateTime,
+    /// None for UTC times
+    zone: Option<Tz>,
+    all_day: bool,
+}
+
+impl CalendarTime {
+    fn to_utc(self) -> Option<DateTime<Utc>> {
+        match self.zone {
+            Some(zone) => local_to_utc(self.local, zone),
+            None => Some(self.local.and_utc()),
+        }
+    }
+
+    fn at(self, local: NaiveDateTime) -> Self {
+        Self { local, ..self }
+    }
+
+    /// Whether an EXDATE or RECURRENCE-ID names `occurrence`; dates name the whole day
+    fn matches(&self, occurrence: &CalendarTime) -> bool {
+        if self.all_day || occurrence.all_day {
+            return self.local.date() == occurrence.local.date();
+        }
+        self.to_utc().is_some() && self.to_utc() == occurrence.to_utc()
+    }
+}
+
+fn parse_date_time(key: &str, value: &str, default_timezone: Tz) -> Option<CalendarTime> {
+    let upper_key = key.to_ascii_uppercase();
+    if (upper_key.contains("VALUE=DATE") && !upper_key.contains("VALUE=DATE-TIME")) || value.len() == 8 {
+        let date = NaiveDate::parse_from_str(value, "%Y%m%d").ok()?;
+        return Some(CalendarTime { local: date.and_hms_opt(0, 0, 0)?, zone: Some(default_timezone), all_day: true });
+    }
+
+    if let Some(utc) = value.strip_suffix('Z') {
+        let naive = NaiveDateTime::parse_from_str(utc, "%Y%m%dT%H%M%S").ok()?;
+        return Some(CalendarTime { local: naive, zone: None, all_day: false });
+    }
+
+    let naive = NaiveDateTime::parse_from_str(value, "%Y%m%dT%H%M%S").ok()?;
+    let timezone = key.split(';')
+        .find_map(|param| param.split_once('=').filter(|(name, _)| name.eq_ignore_ascii_case("TZID")))
+        .and_then(|(_, tzid)| tzid.trim_matches('"').parse::<Tz>().ok())
+        .unwrap_or(default_timezone);
+
+    Some(CalendarTime { local: naive, zone: Some(timezone), all_day: false })
+}
+
+/// Wall-clock time in `timezone`; times skipped by a DST jump don't exist and are dropped
+fn local_to_utc(naive: NaiveDateTime, timezone: Tz) -> Option<DateTime<Utc>> {
+    timezone.from_local_datetime(&naive).earliest().map(|time| time.with_timezone(&Utc))
+}
+
+/// Longest a series is walked, so a rule like FREQ=DAILY from decades ago can't stall an import
+const MAX_RECURRENCE_STEPS: usize = 20_000;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+/// The parts of an RRULE (RFC 5545 3.3.10) calendars use for appointments: FREQ from DAILY to
+/// YEARLY with INTERVAL, COUNT, UNTIL, BYDAY (with ordinals for MONTHLY and YEARLY), BYMONTHDAY
+/// and BYMONTH. Rules using anything else aren't expanded and only their first occurrence counts.
+#[derive(Debug)]
+struct RecurrenceRule {
+    frequency: Frequency,
+    interval: u32,
+    count: Option<usize>,
+    until: Option<CalendarTime>,
+    /// Weekdays with an optional ordinal, like the 2 in 2TU or -1 in -1FR
+    by_day: Vec<(Option<i32>, Weekday)>,
+    by_month_day: Vec<i32>,
+    by_month: Vec<u32>,
+}
+
+impl RecurrenceRule {
+    fn parse(value: &str, start: CalendarTime, default_timezone: Tz) -> Option<Self> {
+        let mut rule = Self {
+            frequency: Frequency::Daily,
+            interval: 1,
+            count: None,
+            until: None,
+            by_day: Vec::new(),
+            by_month_day: Vec::new(),
+            by_month: Vec::new(),
+        };
+        let mut frequency = None;
+
+        for part in value.split(';') {
+            let (name, value) = part.split_once('=')?;
+            match name.to_ascii_uppercase().as_str() {
+                "FREQ" => frequency = Some(match value.to_ascii_uppercase().as_str() {
+                    "DAILY" => Frequency::Daily,
+                    "WEEKLY" => Frequency::Weekly,
+                    "MONTHLY" => Frequency::Monthly,
+                    "YEARLY" => Frequency::Yearly,
+                    _ => return None,
+                }),
+                "INTERVAL" => rule.interval = value.parse().ok().filter(|interval| *interval > 0)?,
+                "COUNT" => rule.count = Some(value.parse().ok()?),
+                // A floating UNTIL is in the event's own zone
+                "UNTIL" => rule.until = Some(parse_date_time("UNTIL", value, start.zone.unwrap_or(default_timezone))?),
+                "BYDAY" => rule.by_day = value.split(',').map(parse_by_day).collect::<Option<_>>()?,
+                "BYMONTHDAY" => rule.by_month_day = value.split(',')
+                    .map(|day| day.parse().ok().filter(|day: &i32| (1..=31).contains(&day.abs())))
+                    .collect::<Option<_>>()?,
+                "BYMONTH" => rule.by_month = value.split(',')
+                    .map(|month| month.parse().ok().filter(|month| (1..=12).contains(month)))
+                    .collect::<Option<_>>()?,
+                "WKST" => {}
+                _ => return None,
+            }
+        }
+
+        rule.frequency = frequency?;
+        Some(rule)
+    }
+
+    /// Occurrence starts from `start`, which always counts as the first, up to `until`
+    fn occurrences(&self, start: CalendarTime, until: DateTime<Utc>) -> Vec<CalendarTime> {
+        if self.count == Some(0) || start.to_utc().is_none_or(|start| start >= until) {
+            return Vec::new();
+        }
+
+        let mut occurrences = vec![start];
+        let time = start.local.time();
+
+        for period in 0..MAX_RECURRENCE_STEPS {
+            let mut dates = self.dates_in_period(start.local.date(), period as i64 * self.interval as i64);
+            dates.sort();
+
+            for date in dates {
+                let occurrence = start.at(date.and_time(time));
+                if occurrence.local <= start.local {
+                    continue;
+                }
+                if self.count.is_some_and(|count| occurrences.len() >= count)
+                    || self.is_after_until(&occurrence)
+                    || occurrence.to_utc().is_some_and(|occurrence_start| occurrence_start >= until)
+                {
+                    return occurrences;
+                }
+                occurrences.push(occurrence);
+            }
+        }
+
+        occurrences
+    }
+
+    fn is_after_until(&self, occurrence: &CalendarTime) -> bool {
+        match &self.until {
+            Some(until) if until.all_day => occurrence.local.date() > until.local.date(),
+            Some(until) => match (occurrence.to_utc(), until.to_utc()) {
+                (Some(occurrence), Some(until)) => occurrence > until,
+                _ => false,
+            },
+            None => false,
+        }
+    }
+
+    /// Candidate dates in the period `offset` days, weeks, months or years after the start
+    fn dates_in_period(&self, start: NaiveDate, offset: i64) -> Vec<NaiveDate> {
+        let dates = match self.frequency {
+            Frequency::Daily => {
+                let date = start + Duration::days(offset);
+                let weekday_matches = self.by_day.is_empty() || self.by_day.iter().any(|(_, weekday)| *weekday == date.weekday());
+                let day_matches = self.by_month_day.is_empty() || self.by_month_day.iter().any(|day| month_day(date.year(), date.month(), *day) == Some(date));
+                if weekday_matches && day_matches { vec![date] } else { Vec::new() }
+            }
+            Frequency::Weekly => {
+                let week_start = start - Duration::days(start.weekday().num_days_from_monday() as i64) + Duration::weeks(offset);
+                if self.by_day.is_empty() {
+                    vec![week_start + Duration::days(start.weekday().num_days_from_monday() as i64)]
+                } else {
+                    self.by_day.iter()
+                        .map(|(_, weekday)| week_start + Duration::days(weekday.num_days_from_monday() as i64))
+                        .collect()
+                }
+            }
+            Frequency::Monthly => {
+                let months = start.year() as i64 * 12 + start.month0() as i64 + offset;
+                self.dates_in_month(months.div_euclid(12) as i32, months.rem_euclid(12) as u32 + 1, start)
+            }
+            Frequency::Yearly => {
+                let year = start.year() + offset as i32;
+                let months = if self.by_month.is_empty() { vec![start.month()] } else { self.by_month.clone() };
+                months.into_iter().flat_map(|month| self.dates_in_month(year, month, start)).collect()
+            }
+        };
+
+        dates.into_iter()
+            .filter(|date| self.by_month.is_empty() || self.by_month.contains(&date.month()))
+            .collect()
+    }
+
+    fn dates_in_month(&self, year: i32, month: u32, start: NaiveDate) -> Vec<NaiveDate> {
+        if !self.by_month_day.is_empty() {
+            return self.by_month_day.iter()
+                .filter_map(|day| month_day(year, month, *day))
+                .filter(|date| self.by_day.is_empty() || self.by_day.iter().any(|(_, weekday)| *weekday == date.weekday()))
+                .collect();
+        }
+
+        if !self.by_day.is_empty() {
+            return self.by_day.iter()
+                .flat_map(|(ordinal, weekday)| {
+                    let days: Vec<NaiveDate> = (1..=31)
+                        .filter_map(|day| NaiveDate::from_ymd_opt(year, month, day))
+                        .filter(|date| date.weekday() == *weekday)
+                        .collect();
+                    match ordinal {
+                        None => days,
+                        Some(nth) if *nth > 0 => days.get(*nth as usize - 1).copied().into_iter().collect(),
+                        Some(nth) => days.len().checked_sub(nth.unsigned_abs() as usize)
+                            .and_then(|index| days.get(index).copied())
+                            .into_iter()
+                            .collect(),
+                    }
+                })
+                .collect();
+        }
+
+        // Months without the start's day, like the 31st, are skipped
+        NaiveDate::from_ymd_opt(year, month, start.day()).into_iter().collect()
+    }
+}
+
+/// Day `day` of a month, counting back from its end when negative
+fn month_day(year: i32, month: u32, day: i32) -> Option<NaiveDate> {
+    if day > 0 {
+        return NaiveDate::from_ymd_opt(year, month, day as u32);
+    }
+    let first_of_next = if month == 12 {
+        NaiveDate::from_ymd_opt(year + 1, 1, 1)?
+    } else {
+        NaiveDate::from_ymd_opt(year, month + 1, 1)?
+    };
+    let date = first_of_next + Duration::days(day as i64);
+    (date.month() == month).then_some(date)
+}
+
+/// A BYDAY entry such as MO, 2TU or -1FR
+fn parse_by_day(value: &str) -> Option<(Option<i32>, Weekday)> {
+    let value = value.trim();
+    let split = value.len().checked_sub(2)?;
+    let (ordinal, day) = value.split_at(split);
+    let weekday = match day.to_ascii_uppercase().as_str() {
+        "MO" => Weekday::Mon,
+        "TU" => Weekday::Tue,
+        "WE" => Weekday::Wed,
+        "TH" => Weekday::Thu,
+        "FR" => Weekday::Fri,
+        "SA" => Weekday::Sat,
+        "SU" => Weekday::Sun,
+        _ => return None,
+    };
+    let ordinal = if ordinal.is_empty() {
+        None
+    } else {
+        Some(ordinal.trim_start_matches('+').parse().ok().filter(|nth: &i32| *nth != 0 && nth.abs() <= 53)?)
+    };
+    Some((ordinal, weekday))
+}
+
+/// A DURATION value such as `PT1H30M`, `P1D` or `P1W`
+fn parse_duration(value: &str) -> Option<Duration> {
+    let value = value.strip_prefix('+').unwrap_or(value);
+    if value.starts_with('-') {
+        return None;
+    }
+    let value = value.strip_prefix('P')?;
+
+    let mut total = Duration::zero();
+    let mut number = String::new();
+    for c in value.chars() {
+        match c {
+            '0'..='9' => number.push(c),
+            'T' => {}
+            unit => {
+                let amount: i64 = number.parse().ok()?;
+                number.clear();
+                total += match unit {
+                    'W' => Duration::weeks(amount),
+                    'D' => Duration::days(amount),
+                    'H' => Duration::hours(amount),
+                    'M' => Duration::minutes(amount),
+                    'S' => Duration::seconds(amount),
+                    _ => return None,
+                };
+            }
+        }
+    }
+
+    Some(total)
+}
+
+/// Join folded lines back together (RFC 5545 3.1)
+fn unfold_lines(ics: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+
+    for line in ics.split('\n') {
+        let line = line.strip_suffix('\r').unwrap_or(line);
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(continuation), Some(previous)) => previous.push_str(continuation),
+            _ => lines.push(line.to_string()),
+        }
+    }
+
+    lines
+}
+
+fn unescape_text(value: &str) -> String {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') | Some('N') => unescaped.push('\n'),
+            Some(other) => unescaped.push(other),
+            None => {}
+        }
+    }
+
+    unescaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono_tz::Asia::Jakarta;
+
+    fn utc(value: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(value).unwrap().with_timezone(&Utc)
+    }
+
+    fn calendar(events: &str) -> String {
+        format!("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n{}END:VCALENDAR\r\n", events)
+    }
+
+    fn starts(ics: &str, from: &str, until: &str) -> Vec<DateTime<Utc>> {
+        parse_busy_events(&calendar(ics), Jakarta, utc(from), utc(until))
+            .into_iter()
+            .map(|event| event.start)
+            .collect()
+    }
+
+    #[test]
+    fn reads_zones_durations_and_all_day_events() {
+        let events = parse_busy_events(&calendar(concat!(
+            "BEGIN:VEVENT\r\nUID:utc\r\nDTSTART:20260701T020000Z\r\nDTEND:20260701T030000Z\r\nSUMMARY:Fitting\\, bride\r\nEND:VEVENT\r\n",
+            "BEGIN:VEVENT\r\nUID:zoned\r\nDTSTART;TZID=Asia/Makassar:20260702T090000\r\nDURATION:PT90M\r\nEND:VEVENT\r\n",
+            "BEGIN:VEVENT\r\nUID:floating\r\nDTSTART:20260703T090000\r\nDTEND:20260703T100000\r\nEND:VEVENT\r\n",
+            "BEGIN:VEVENT\r\nUID:all-day\r\nDTSTART;VALUE=DATE:20260704\r\nEND:VEVENT\r\n",
+            "BEGIN:VEVENT\r\nUID:free\r\nDTSTART:20260705T020000Z\r\nDTEND:20260705T030000Z\r\nTRANSP:TRANSPARENT\r\nEND:VEVENT\r\n",
+            "BEGIN:VEVENT\r\nUID:cancelled\r\nDTSTART:20260706T020000Z\r\nDTEND:20260706T030000Z\r\nSTATUS:CANCELLED\r\nEND:VEVENT\r\n",
+        )), Jakarta, utc("2026-06-01T00:00:00Z"), utc("2027-06-01T00:00:00Z"));
+
+        let times: Vec<_> = events.iter().map(|event| (event.uid.as_deref().unwrap(), event.start, event.end)).collect();
+        assert_eq!(times, vec![
+            ("utc", utc("2026-07-01T02:00:00Z"), utc("2026-07-01T03:00:00Z")),
+            ("zoned", utc("2026-07-02T01:00:00Z"), utc("2026-07-02T02:30:00Z")),
+            ("floating", utc("2026-07-03T02:00:00Z"), utc("2026-07-03T03:00:00Z")),
+            ("all-day", utc("2026-07-03T17:00:00Z"), utc("2026-07-04T17:00:00Z")),
+        ]);
+        assert_eq!(events[0].summary.as_deref(), Some("Fitting, bride"));
+    }
+
+    #[test]
+    fn expands_weekly_rules_within_the_window() {
+        let ics = "BEGIN:VEVENT\r\nUID:class\r\nDTSTART:20260601T020000Z\r\nDTEND:20260601T040000Z\r\nRRULE:FREQ=WEEKLY;BYDAY=MO,TH\r\nEND:VEVENT\r\n";
+
+        assert_eq!(starts(ics, "2026-06-05T00:00:00Z", "2026-06-16T00:00:00Z"), vec![
+            utc("2026-06-08T02:00:00Z"),
+            utc("2026-06-11T02:00:00Z"),
+            utc("2026-06-15T02:00:00Z"),
+        ]);
+    }
+
+    #[test]
+    fn honours_count_until_and_interval() {
+        let counted = "BEGIN:VEVENT\r\nUID:a\r\nDTSTART:20260601T020000Z\r\nDURATION:PT1H\r\nRRULE:FREQ=DAILY;INTERVAL=2;COUNT=3\r\nEND:VEVENT\r\n";
+        assert_eq!(starts(counted, "2026-01-01T00:00:00Z", "2027-01-01T00:00:00Z"), vec![
+            utc("2026-06-01T02:00:00Z"),
+            utc("2026-06-03T02:00:00Z"),
+            utc("2026-06-05T02:00:00Z"),
+        ]);
+
+        let until = "BEGIN:VEVENT\r\nUID:b\r\nDTSTART:20260601T020000Z\r\nDURATION:PT1H\r\nRRULE:FREQ=WEEKLY;UNTIL=20260615T020000Z\r\nEND:VEVENT\r\n";
+        assert_eq!(starts(until, "2026-01-01T00:00:00Z", "2027-01-01T00:00:00Z"), vec![
+            utc("2026-06-01T02:00:00Z"),
+            utc("2026-06-08T02:00:00Z"),
+            utc("2026-06-15T02:00:00Z"),
+        ]);
+    }
+
+    #[test]
+    fn expands_monthly_and_yearly_rules() {
+        let last_friday = "BEGIN:VEVENT\r\nUID:a\r\nDTSTART:20260626T020000Z\r\nDURATION:PT1H\r\nRRULE:FREQ=MONTHLY;BYDAY=-1FR;COUNT=3\r\nEND:VEVENT\r\n";
+        assert_eq!(starts(last_friday, "2026-01-01T00:00:00Z", "2027-01-01T00:00:00Z"), vec![
+            utc("2026-06-26T02:00:00Z"),
+            utc("2026-07-31T02:00:00Z"),
+            utc("2026-08-28T02:00:00Z"),
+        ]);
+
+        // Months without a 31st are skipped rather than moved
+        let thirty_first = "BEGIN:VEVENT\r\nUID:b\r\nDTSTART:20260731T020000Z\r\nDURATION:PT1H\r\nRRULE:FREQ=MONTHLY;COUNT=3\r\nEND:VEVENT\r\n";
+        assert_eq!(starts(thirty_first, "2026-01-01T00:00:00Z", "2027-01-01T00:00:00Z"), vec![
+            utc("2026-07-31T02:00:00Z"),
+            utc("2026-08-31T02:00:00Z"),
+            utc("2026-10-31T02:00:00Z"),
+        ]);
+
+        let anniversary = "BEGIN:VEVENT\r\nUID:c\r\nDTSTART;VALUE=DATE:20250610\r\nRRULE:FREQ=YEARLY\r\nEND:VEVENT\r\n";
+        assert_eq!(starts(anniversary, "2026-01-01T00:00:00Z", "2028-01-01T00:00:00Z"), vec![
+            utc("2026-06-09T17:00:00Z"),
+            utc("2027-06-09T17:00:00Z"),
+        ]);
+    }
+
+    #[test]
+    fn leaves_out_exdates_and_overridden_occurrences() {
+        let ics = concat!(
+            "BEGIN:VEVENT\r\nUID:series\r\nDTSTART;TZID=Asia/Jakarta:20260601T090000\r\nDURATION:PT1H\r\n",
+            "RRULE:FREQ=DAILY;COUNT=5\r\nEXDATE;TZID=Asia/Jakarta:20260602T090000,20260603T090000\r\nEND:VEVENT\r\n",
+            "BEGIN:VEVENT\r\nUID:series\r\nRECURRENCE-ID;TZID=Asia/Jakarta:20260604T090000\r\n",
+            "DTSTART;TZID=Asia/Jakarta:20260604T150000\r\nDURATION:PT1H\r\nEND:VEVENT\r\n",
+        );
+
+        assert_eq!(starts(ics, "2026-01-01T00:00:00Z", "2027-01-01T00:00:00Z"), vec![
+            utc("2026-06-01T02:00:00Z"),
+            utc("2026-06-05T02:00:00Z"),
+            utc("2026-06-04T08:00:00Z"),
+        ]);
+    }
+
+    #[test]
+    fn steps_in_the_events_own_zone_across_dst() {
+        // 09:00 in New York stays 09:00 local when the clocks change on 8 March
+        let ics = "BEGIN:VEVENT\r\nUID:a\r\nDTSTART;TZID=America/New_York:20260305T090000\r\nDURATION:PT1H\r\nRRULE:FREQ=WEEKLY;COUNT=2\r\nEND:VEVENT\r\n";
+        assert_eq!(starts(ics, "2026-01-01T00:00:00Z", "2027-01-01T00:00:00Z"), vec![
+            utc("2026-03-05T14:00:00Z"),
+            utc("2026-03-12T13:00:00Z"),
+        ]);
+    }
+
+    #[test]
+    fn unsupported_rules_keep_only_the_first_occurrence() {
+        let ics = "BEGIN:VEVENT\r\nUID:a\r\nDTSTART:20260601T020000Z\r\nDURATION:PT1H\r\nRRULE:FREQ=HOURLY\r\nEND:VEVENT\r\n";
+        assert_eq!(starts(ics, "2026-01-01T00:00:00Z", "2027-01-01T00:00:00Z"), vec![utc("2026-06-01T02:00:00Z")]);
+    }
+}
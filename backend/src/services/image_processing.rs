@@ -0,0 +1,224 @@
+use anyhow::Result;
+use bytes::Bytes;
+use image::{DynamicImage, ImageDecoder, ImageFormat, ImageReader, Limits};
+use std::io::Cursor;
+use uuid::Uuid;
+
+use super::object_storage::ObjectStorage;
+
+/// Longest edge, in pixels, of each generated variant
+const THUMBNAIL_SIZE: u32 = 320;
+const MEDIUM_SIZE: u32 = 960;
+const FULL_SIZE: u32 = 2048;
+const AVATAR_SIZE: u32 = 512;
+
+/// Refuse to decode anything larger than this to keep memory bounded
+const MAX_DIMENSION: u32 = 12_000;
+
+const WEBP_QUALITY: f32 = 80.0;
+
+/// URLs of the stored WebP variants of one uploaded image
+#[derive(Debug, Clone)]
+pub struct ImageVariants {
+    pub thumbnail_url: String,
+    pub medium_url: String,
+    pub full_url: String,
+}
+
+impl ImageVariants {
+    pub fn urls(&self) -> [&str; 3] {
+        [&self.thumbnail_url, &self.medium_url, &self.full_url]
+    }
+}
+
+/// Decode an uploaded image, checking its magic bytes rather than the declared MIME type.
+///
+/// EXIF orientation is applied to the pixels; everything else in the metadata
+/// (including GPS) is dropped because variants are re-encoded from raw pixels.
+pub fn decode_image(data: &[u8]) -> Result<DynamicImage> {
+    let format = image::guess_format(data)
+        .map_err(|_| anyhow::anyhow!("Invalid image file. Only JPEG, PNG, GIF and WebP are allowed."))?;
+
+    if !matches!(format, ImageFormat::Jpeg | ImageFormat::Png | ImageFormat::Gif | ImageFormat::WebP) {
+        return Err(anyhow::anyhow!("Invalid image file. Only JPEG, PNG, GIF and WebP are allowed."));
+    }
+
+    let mut limits = Limits::default();
+    limits.max_image_width = Some(MAX_DIMENSION);
+    limits.max_image_height = Some(MAX_DIMENSION);
+
+    let mut reader = ImageReader::with_format(Cursor::new(data), format);
+    reader.limits(limits);
+
+    let mut decoder = reader.into_decoder()
+        .map_err(|e| anyhow::anyhow!("Failed to read image: {}", e))?;
+    let orientation = decoder.orientation()
+        .map_err(|e| anyhow::anyhow!("Failed to read image: {}", e))?;
+
+    let mut image = DynamicImage::from_decoder(decoder)
+        .map_err(|e| anyhow::anyhow!("Failed to decode image: {}", e))?;
+    image.apply_orientation(orientation);
+
+    Ok(image)
+}
+
+/// Downscale (never upscale) so the longest edge is at most `max_size`, then encode as WebP
+pub fn encode_webp(image: &DynamicImage, max_size: u32) -> Vec<u8> {
+    let resized = if image.width() > max_size || image.height() > max_size {
+        image.thumbnail(max_size, max_size)
+    } else {
+        image.clone()
+    };
+
+    let rgba = resized.to_rgba8();
+    webp::Encoder::from_rgba(rgba.as_raw(), rgba.width(), rgba.height())
+        .encode(WEBP_QUALITY)
+        .to_vec()
+}
+
+/// Generate and store thumbnail/medium/full variants under `folder`
+pub async fn store_portfolio_variants(storage: &dyn ObjectStorage, folder: &str, data: Vec<u8>) -> Result<ImageVariants> {
+    // Decoding and encoding are CPU bound, keep them off the async workers
+    let (thumbnail, medium, full) = tokio::task::spawn_blocking(move || -> Result<_> {
+        let image = decode_image(&data)?;
+        Ok((
+            encode_webp(&image, THUMBNAIL_SIZE),
+            encode_webp(&image, MEDIUM_SIZE),
+            encode_webp(&image, FULL_SIZE),
+        ))
+    })
+    .await??;
+
+    let base_key = format!("{}/{}", folder.trim_matches('/'), Uuid::new_v4());
+
+    Ok(ImageVariants {
+        thumbnail_url: storage.put_object(&format!("{}-thumb.webp", base_key), Bytes::from(thumbnail), "image/webp").await?,
+        medium_url: storage.put_object(&format!("{}-medium.webp", base_key), Bytes::from(medium), "image/webp").await?,
+        full_url: storage.put_object(&format!("{}-full.webp", base_key), Bytes::from(full), "image/webp").await?,
+    })
+}
+
+/// Process an avatar into a single WebP and store it under `folder`, returning its URL
+pub async fn store_avatar(storage: &dyn ObjectStorage, folder: &str, data: Vec<u8>) -> Result<String> {
+    let avatar = tokio::task::spawn_blocking(move || -> Result<_> {
+        let image = decode_image(&data)?;
+        Ok(encode_webp(&image, AVATAR_SIZE))
+    })
+    .await??;
+
+    let key = format!("{}/{}.webp", folder.trim_matches('/'), Uuid::new_v4());
+    storage.put_object(&key, Bytes::from(avatar), "image/webp").await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{Rgb, RgbImage};
+
+    /// Left half red, right half blue
+    fn two_tone(width: u32, height: u32) -> RgbImage {
+        RgbImage::from_fn(width, height, |x, _| if x < width / 2 { Rgb([255, 0, 0]) } else { Rgb([0, 0, 255]) })
+    }
+
+    fn jpeg(image: &RgbImage) -> Vec<u8> {
+        let mut data = Vec::new();
+        image.write_to(&mut Cursor::new(&mut data), ImageFormat::Jpeg).unwrap();
+        data
+    }
+
+    /// `jpeg` with an EXIF segment carrying `orientation` and a camera make, inserted after the SOI marker
+    fn jpeg_with_exif(image: &RgbImage, orientation: u16) -> Vec<u8> {
+        let mut tiff = b"MM\0\x2a\0\0\0\x08".to_vec();
+        tiff.extend_from_slice(&2u16.to_be_bytes());
+        // Orientation, a SHORT held in the entry itself
+        tiff.extend_from_slice(&[0x01, 0x12, 0x00, 0x03, 0, 0, 0, 1]);
+        tiff.extend_from_slice(&orientation.to_be_bytes());
+        tiff.extend_from_slice(&[0, 0]);
+        // Make, an ASCII string stored after the IFD
+        tiff.extend_from_slice(&[0x01, 0x0f, 0x00, 0x02, 0, 0, 0, 12, 0, 0, 0, 38]);
+        tiff.extend_from_slice(&[0, 0, 0, 0]);
+        tiff.extend_from_slice(b"SecretCam01\0");
+
+        let mut segment = b"Exif\0\0".to_vec();
+        segment.extend_from_slice(&tiff);
+
+        let data = jpeg(image);
+        let mut with_exif = data[..2].to_vec();
+        with_exif.extend_from_slice(&[0xFF, 0xE1]);
+        with_exif.extend_from_slice(&(segment.len() as u16 + 2).to_be_bytes());
+        with_exif.extend_from_slice(&segment);
+        with_exif.extend_from_slice(&data[2..]);
+        with_exif
+    }
+
+    fn webp_dimensions(data: &[u8]) -> (u32, u32) {
+        let image = image::load_from_memory_with_format(data, ImageFormat::WebP).unwrap();
+        (image.width(), image.height())
+    }
+
+    fn is_red(pixel: Rgb<u8>) -> bool {
+        pixel[0] > 200 && pixel[2] < 60
+    }
+
+    #[test]
+    fn rejects_bytes_that_are_not_an_allowed_image() {
+        for data in [&b"just some text"[..], b"%PDF-1.7\n", b"BM\x3a\0\0\0\0\0\0\0\x36\0\0\0", b""] {
+            let error = decode_image(data).unwrap_err();
+            assert!(error.to_string().starts_with("Invalid image file"), "{:?}: {}", data, error);
+        }
+    }
+
+    #[test]
+    fn rejects_a_truncated_image() {
+        // Its magic bytes still say PNG, so it gets as far as decoding
+        let mut png = Vec::new();
+        DynamicImage::ImageRgb8(two_tone(8, 8)).write_to(&mut Cursor::new(&mut png), ImageFormat::Png).unwrap();
+        assert!(decode_image(&png).is_ok());
+        assert!(decode_image(&png[..png.len() / 2]).is_err());
+    }
+
+    #[test]
+    fn applies_exif_orientation() {
+        let data = jpeg_with_exif(&two_tone(32, 16), 6);
+
+        // Rotated a quarter turn clockwise, the red left half ends up on top
+        let image = decode_image(&data).unwrap().to_rgb8();
+        assert_eq!(image.dimensions(), (16, 32));
+        assert!(is_red(*image.get_pixel(8, 4)));
+        assert!(!is_red(*image.get_pixel(8, 28)));
+    }
+
+    #[test]
+    fn webp_output_carries_no_metadata() {
+        let data = jpeg_with_exif(&two_tone(32, 16), 1);
+        assert!(data.windows(9).any(|window| window == b"SecretCam"));
+
+        let webp = encode_webp(&decode_image(&data).unwrap(), FULL_SIZE);
+        assert_eq!((&webp[..4], &webp[8..12]), (&b"RIFF"[..], &b"WEBP"[..]));
+        for marker in [&b"EXIF"[..], b"Exif", b"XMP ", b"SecretCam"] {
+            assert!(!webp.windows(marker.len()).any(|window| window == marker), "{:?} survived", marker);
+        }
+    }
+
+    #[test]
+    fn variants_shrink_to_their_longest_edge() {
+        let image = DynamicImage::ImageRgb8(two_tone(2400, 1200));
+
+        assert_eq!(webp_dimensions(&encode_webp(&image, THUMBNAIL_SIZE)), (320, 160));
+        assert_eq!(webp_dimensions(&encode_webp(&image, MEDIUM_SIZE)), (960, 480));
+        assert_eq!(webp_dimensions(&encode_webp(&image, FULL_SIZE)), (2048, 1024));
+        assert_eq!(webp_dimensions(&encode_webp(&image, AVATAR_SIZE)), (512, 256));
+
+        let portrait = DynamicImage::ImageRgb8(two_tone(600, 1200));
+        assert_eq!(webp_dimensions(&encode_webp(&portrait, THUMBNAIL_SIZE)), (160, 320));
+    }
+
+    #[test]
+    fn variants_never_upscale() {
+        let image = DynamicImage::ImageRgb8(two_tone(100, 50));
+
+        for size in [THUMBNAIL_SIZE, MEDIUM_SIZE, FULL_SIZE, AVATAR_SIZE] {
+            assert_eq!(webp_dimensions(&encode_webp(&image, size)), (100, 50));
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::{PgExecutor, Postgres, Row, Transaction};
+use uuid::Uuid;
+
+/// Work for the background job runner, stored as JSON in `jobs.payload`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Job {
+    /// Tell the MUA a customer requested a booking
+    BookingRequested { booking_id: Uuid },
+    /// Tell the customer the MUA accepted their booking
+    BookingConfirmed { booking_id: Uuid },
+    /// Tell both parties a request lapsed without an answer
+    BookingExpired { booking_id: Uuid },
+    /// Invite both parties to review a finished booking
+    BookingCompleted { booking_id: Uuid },
+    /// `event_date` is the date the reminder was scheduled for; if the booking
+    /// has moved since, the reminder is dropped
+    BookingReminder { booking_id: Uuid, event_date: DateTime<Utc>, hours_before: i64 },
+    /// Ask the other party to accept or decline a proposed new date
+    RescheduleRequested { reschedule_request_id: Uuid },
+    /// Tell the proposer whether their new date was accepted
+    RescheduleResponded { reschedule_request_id: Uuid },
+    /// Ask the other party to accept or counter a price offer
+    QuoteProposed { quote_id: Uuid },
+    /// Tell the MUA the customer agreed to their quote
+    QuoteAccepted { quote_id: Uuid },
+    /// Tell customers who saved this MUA that their date has opened up
+    FavoriteOpenings { mua_id: Uuid },
+    /// Send a cancelled booking's refund to the payment provider
+    IssueRefund { booking_id: Uuid },
+}
+
+impl Job {
+    pub fn job_type(&self) -> &'static str {
+        match self {
+            Job::BookingRequested { .. } => "booking_requested",
+            Job::BookingConfirmed { .. } => "booking_confirmed",
+            Job::BookingExpired { .. } => "booking_expired",
+            Job::BookingCompleted { .. } => "booking_completed",
+            Job::BookingReminder { .. } => "booking_reminder",
+            Job::RescheduleRequested { .. } => "reschedule_requested",
+            Job::RescheduleResponded { .. } => "reschedule_responded",
+            Job::QuoteProposed { .. } => "quote_proposed",
+            Job::QuoteAccepted { .. } => "quote_accepted",
+            Job::FavoriteOpenings { .. } => "favorite_openings",
+            Job::IssueRefund { .. } => "issue_refund",
+        }
+    }
+}
+
+/// A job row locked by the runner
+pub struct QueuedJob {
+    pub id: Uuid,
+    pub job_type: String,
+    pub payload: Value,
+    pub attempts: i32,
+    pub max_attempts: i32,
+}
+
+/// Schedule `job` to run at `run_at`; pass a transaction to enqueue atomically with other writes
+pub async fn enqueue<'e, E: PgExecutor<'e>>(executor: E, job: &Job, run_at: DateTime<Utc>) -> Result<Uuid> {
+    let id: Uuid = sqlx::query_scalar(
+        "INSERT INTO jobs (job_type, payload, run_at, created_at) VALUES ($1, $2, $3, NOW()) RETURNING id"
+    )
+    .bind(job.job_type())
+    .bind(serde_json::to_value(job)?)
+    .bind(run_at)
+    .fetch_one(executor)
+    .await?;
+
+    Ok(id)
+}
+
+/// Lock a batch of due jobs; other runners skip rows this transaction holds.
+///
+/// The lock leaves the key alone so jobs can record their notification deliveries while held.
+pub async fn lock_due_jobs(tx: &mut Transaction<'_, Postgres>, limit: i64) -> Result<Vec<QueuedJob>> {
+    let rows = sqlx::query(
+        r#"
+        SELECT id, job_type, payload, attempts, max_attempts FROM jobs
+        WHERE status = 'pending' AND run_at <= NOW()
+        ORDER BY run_at
+        LIMIT $1
+        FOR NO KEY UPDATE SKIP LOCKED
+        "#
+    )
+    .bind(limit)
+    .fetch_all(&mut *tx)
+    .await?;
+
+    Ok(rows.into_iter().map(|row| QueuedJob {
+        id: row.get("id"),
+        job_type: row.get("job_type"),
+        payload: row.get("payload"),
+        attempts: row.get("attempts"),
+        max_attempts: row.get("max_attempts"),
+    }).collect())
+}
+
+pub async fn complete_job(tx: &mut Transaction<'_, Postgres>, id: Uuid) -> Result<()> {
+    sqlx::query("UPDATE jobs SET status = 'done', attempts = attempts + 1, completed_at = NOW() WHERE id = $1")
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+
+    Ok(())
+}
+
+/// Record a failed attempt; retried with exponential backoff until `max_attempts` is used up
+pub async fn fail_job(tx: &mut Transaction<'_, Postgres>, job: &QueuedJob, error: &str) -> Result<()> {
+    let exhausted = job.attempts + 1 >= job.max_attempts;
+    let backoff_minutes = 2_i64.saturating_pow(job.attempts.clamp(0, 10) as u32);
+
+    sqlx::query(
+        r#"
+        UPDATE jobs
+        SET attempts = attempts + 1,
+            last_error = $2,
+            status = CASE WHEN $3 THEN 'failed' ELSE 'pending' END,
+            run_at = NOW() + make_interval(mins => $4)
+        WHERE id = $1
+        "#
+    )
+    .bind(job.id)
+    .bind(error)
+    .bind(exhausted)
+    .bind(backoff_minutes as i32)
+    .execute(&mut *tx)
+    .await?;
+
+    Ok(())
+}
@@ -0,0 +1,230 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use bytes::Bytes;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::env;
+use std::path::{Component, Path, PathBuf};
+use std::time::Duration;
+use tokio::io::AsyncReadExt;
+
+use super::object_storage::{self, ObjectMetadata, ObjectStorage};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Well-known signing secret, only used when explicitly allowed for development
+const DEV_SECRET: &str = "local-storage-dev-secret";
+
+/// Stores objects on local disk so uploads work without S3 credentials.
+///
+/// Files are served by the `/storage` static route, and presigned uploads are
+/// emulated with HMAC-signed `PUT /storage/{key}` URLs.
+pub struct LocalStorage {
+    root: PathBuf,
+    base_url: String,
+    secret: String,
+}
+
+impl LocalStorage {
+    pub fn new(root: impl Into<PathBuf>, base_url: &str, secret: &str) -> Self {
+        Self {
+            root: root.into(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            secret: secret.to_string(),
+        }
+    }
+
+    /// Signs uploads with `LOCAL_STORAGE_SECRET`, or `JWT_SECRET` if that isn't set.
+    ///
+    /// Without either this fails, unless `LOCAL_STORAGE_ALLOW_DEV_SECRET=true` opts in
+    /// to a fixed development secret that anyone could use to forge upload URLs.
+    pub fn from_env() -> Result<Self> {
+        let root = env::var("LOCAL_STORAGE_DIR")
+            .unwrap_or_else(|_| "./uploads".to_string());
+        let base_url = env::var("LOCAL_STORAGE_BASE_URL")
+            .unwrap_or_else(|_| "http://127.0.0.1:8080/storage".to_string());
+        let secret = env::var("LOCAL_STORAGE_SECRET")
+            .or_else(|_| env::var("JWT_SECRET"))
+            .ok()
+            .filter(|secret| !secret.is_empty());
+
+        let secret = match secret {
+            Some(secret) => secret,
+            None if env::var("LOCAL_STORAGE_ALLOW_DEV_SECRET").is_ok_and(|v| v == "true") => DEV_SECRET.to_string(),
+            None => return Err(anyhow::anyhow!(
+                "LOCAL_STORAGE_SECRET must be set for local storage (or LOCAL_STORAGE_ALLOW_DEV_SECRET=true in development)"
+            )),
+        };
+
+        Ok(Self::new(root, &base_url, &secret))
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Resolve a key to a path under the storage root, rejecting anything that could escape it
+    fn path_for(&self, key: &str) -> Result<PathBuf> {
+        let relative = Path::new(key);
+        let is_safe = !key.is_empty()
+            && relative.components().all(|c| matches!(c, Component::Normal(_)));
+
+        if !is_safe {
+            return Err(anyhow::anyhow!("Invalid object key: {}", key));
+        }
+
+        Ok(self.root.join(relative))
+    }
+
+    fn signature(&self, key: &str, content_type: &str, expires: i64) -> Result<HmacSha256> {
+        let mut mac = HmacSha256::new_from_slice(self.secret.as_bytes())
+            .map_err(|e| anyhow::anyhow!("Invalid storage secret: {}", e))?;
+        mac.update(format!("PUT\n{}\n{}\n{}", key, content_type, expires).as_bytes());
+        Ok(mac)
+    }
+
+    /// Check a presigned upload URL produced by `presign_upload`
+    pub fn verify_upload(&self, key: &str, content_type: &str, expires: i64, signature: &str) -> Result<()> {
+        if chrono::Utc::now().timestamp() > expires {
+            return Err(anyhow::anyhow!("Upload URL has expired"));
+        }
+
+        let signature = hex::decode(signature)
+            .map_err(|_| anyhow::anyhow!("Invalid upload signature"))?;
+
+        self.signature(key, content_type, expires)?
+            .verify_slice(&signature)
+            .map_err(|_| anyhow::anyhow!("Invalid upload signature"))
+    }
+}
+
+#[async_trait]
+impl ObjectStorage for LocalStorage {
+    async fn put_object(&self, key: &str, data: Bytes, _content_type: &str) -> Result<String> {
+        let path = self.path_for(key)?;
+
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::write(&path, &data).await?;
+
+        Ok(self.public_url(key))
+    }
+
+    async fn get_object(&self, key: &str) -> Result<Bytes> {
+        let path = self.path_for(key)?;
+        Ok(Bytes::from(tokio::fs::read(&path).await?))
+    }
+
+    async fn delete_object(&self, key: &str) -> Result<()> {
+        let path = self.path_for(key)?;
+
+        match tokio::fs::remove_file(&path).await {
+            Ok(()) => Ok(()),
+            // Deleting something that's already gone is not an error, same as S3
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn head_object(&self, key: &str) -> Result<Option<ObjectMetadata>> {
+        let path = self.path_for(key)?;
+
+        match tokio::fs::metadata(&path).await {
+            Ok(metadata) if metadata.is_file() => {
+                // Files on disk carry no content type and the key's extension only repeats
+                // what the uploader declared, so go by what the file actually starts with
+                let mut header = Vec::with_capacity(object_storage::SNIFF_LENGTH);
+                tokio::fs::File::open(&path).await?
+                    .take(object_storage::SNIFF_LENGTH as u64)
+                    .read_to_end(&mut header)
+                    .await?;
+
+                Ok(Some(ObjectMetadata {
+                    size: metadata.len(),
+                    content_type: object_storage::sniff_content_type(&header).map(|ct| ct.to_string()),
+                }))
+            }
+            Ok(_) => Ok(None),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn presign_upload(&self, key: &str, content_type: &str, expires_in: Duration) -> Result<String> {
+        self.path_for(key)?;
+
+        let expires = chrono::Utc::now().timestamp() + expires_in.as_secs() as i64;
+        let signature = hex::encode(self.signature(key, content_type, expires)?.finalize().into_bytes());
+
+        let url = reqwest::Url::parse_with_params(
+            &self.public_url(key),
+            &[
+                ("expires", expires.to_string()),
+                ("content_type", content_type.to_string()),
+                ("signature", signature),
+            ],
+        )?;
+
+        Ok(url.to_string())
+    }
+
+    fn public_url(&self, key: &str) -> String {
+        format!("{}/{}", self.base_url, key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PNG_HEADER: &[u8] = &[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A, 0, 0, 0, 0x0D];
+
+    fn storage() -> LocalStorage {
+        let root = env::temp_dir().join(format!("local-storage-{}", uuid::Uuid::new_v4()));
+        LocalStorage::new(root, "http://127.0.0.1:8080/storage", "test-secret")
+    }
+
+    #[tokio::test]
+    async fn reports_the_type_the_file_contents_have() {
+        let storage = storage();
+        storage.put_object("avatars/photo.png", Bytes::from_static(PNG_HEADER), "image/png").await.unwrap();
+
+        let metadata = storage.head_object("avatars/photo.png").await.unwrap().unwrap();
+        assert_eq!(metadata.size, PNG_HEADER.len() as u64);
+        assert_eq!(metadata.content_type.as_deref(), Some("image/png"));
+
+        tokio::fs::remove_dir_all(storage.root()).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn ignores_the_extension_of_a_mislabeled_file() {
+        let storage = storage();
+        storage.put_object("avatars/photo.jpg", Bytes::from_static(PNG_HEADER), "image/jpeg").await.unwrap();
+        storage.put_object("avatars/page.jpg", Bytes::from_static(b"<html><script>"), "image/jpeg").await.unwrap();
+
+        let png = storage.head_object("avatars/photo.jpg").await.unwrap().unwrap();
+        assert_eq!(png.content_type.as_deref(), Some("image/png"));
+        let html = storage.head_object("avatars/page.jpg").await.unwrap().unwrap();
+        assert_eq!(html.content_type, None);
+
+        tokio::fs::remove_dir_all(storage.root()).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn missing_objects_have_no_metadata() {
+        let storage = storage();
+        assert!(storage.head_object("avatars/missing.png").await.unwrap().is_none());
+    }
+
+    #[test]
+    fn sniffs_each_supported_image_type() {
+        assert_eq!(object_storage::sniff_content_type(&[0xFF, 0xD8, 0xFF, 0xE0]), Some("image/jpeg"));
+        assert_eq!(object_storage::sniff_content_type(PNG_HEADER), Some("image/png"));
+        assert_eq!(object_storage::sniff_content_type(b"GIF89a\x01\x00"), Some("image/gif"));
+        assert_eq!(object_storage::sniff_content_type(b"RIFF\x24\x00\x00\x00WEBPVP8 "), Some("image/webp"));
+        assert_eq!(object_storage::sniff_content_type(b"RIFF\x24\x00\x00\x00WAVEfmt "), None);
+        assert_eq!(object_storage::sniff_content_type(&[0xFF, 0xD8]), None);
+        assert_eq!(object_storage::sniff_content_type(b""), None);
+    }
+}
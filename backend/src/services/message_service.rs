@@ -0,0 +1,27 @@
+use anyhow::Result;
+use sqlx::{PgPool, Postgres, QueryBuilder};
+use uuid::Uuid;
+
+use crate::models::{Cursor, Message, Page, PageQuery, NEWEST_FIRST};
+use super::booking_service;
+
+/// A booking's chat, newest first, for either participant
+pub async fn get_messages(pool: &PgPool, auth_header: Option<String>, booking_id: Uuid, page_query: PageQuery) -> Result<Page<Message>> {
+    let user_id = super::user_service::get_user_id_from_auth_header(auth_header)?;
+    booking_service::load_participant_booking(pool, booking_id, user_id).await?;
+
+    let mut sql = QueryBuilder::<Postgres>::new("SELECT * FROM messages WHERE booking_id = ");
+    sql.push_bind(booking_id);
+    page_query.push_keyset(&mut sql, NEWEST_FIRST, "created_at", "id", true)?;
+
+    let messages = sql.build_query_as::<Message>()
+        .fetch_all(pool)
+        .await?;
+
+    let total: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM messages WHERE booking_id = $1")
+        .bind(booking_id)
+        .fetch_one(pool)
+        .await?;
+
+    Ok(Page::keyset(messages, total, page_query.limit(), |message| Cursor::new(NEWEST_FIRST, message.created_at, message.id)))
+}
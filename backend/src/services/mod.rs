@@ -2,8 +2,31 @@ pub mod auth_service;
 pub mod user_service;
 pub mod mua_service;
 pub mod booking_service;
+pub mod review_service;
 pub mod dashboard_service;
 pub mod s3_service;
+pub mod object_storage;
+pub mod local_storage;
+pub mod upload_session;
+pub mod storage_outbox;
+pub mod image_processing;
+pub mod job_queue;
+pub mod notifier;
+pub mod notification_service;
+pub mod payment_provider;
+pub mod cancellation_service;
+pub mod availability_service;
+pub mod reschedule_service;
+pub mod quote_service;
+pub mod travel_service;
+pub mod timezone_service;
+pub mod time_off_service;
+pub mod favorite_service;
+pub mod offering_service;
+pub mod message_service;
+pub mod ical;
+pub mod calendar_feed;
+pub mod calendar_import;
 pub mod traits;
 pub mod container;
 
@@ -12,6 +35,29 @@ pub use auth_service::*;
 pub use user_service::*;
 pub use mua_service::*;
 pub use booking_service::*;
+pub use review_service::*;
 pub use dashboard_service::*;
 pub use s3_service::*;
+pub use object_storage::*;
+pub use local_storage::*;
+pub use upload_session::*;
+pub use storage_outbox::*;
+pub use image_processing::*;
+pub use job_queue::*;
+pub use notifier::*;
+pub use notification_service::*;
+pub use payment_provider::*;
+pub use cancellation_service::*;
+pub use availability_service::*;
+pub use reschedule_service::*;
+pub use quote_service::*;
+pub use travel_service::*;
+pub use timezone_service::*;
+pub use time_off_service::*;
+pub use favorite_service::*;
+pub use offering_service::*;
+pub use message_service::*;
+pub use ical::*;
+pub use calendar_feed::*;
+pub use calendar_import::*;
 pub use container::*;
\ No newline at end of file
@@ -3,12 +3,14 @@ use async_trait::async_trait;
 use serde_json::Value;
 use uuid::Uuid;
 use sqlx::PgPool;
-use bytes::Bytes;
 
-use crate::models::{MuaProfileResponse, SearchMuasRequest, CreateMuaProfileRequest};
+use crate::models::{MuaProfileResponse, Page, SearchMuasRequest, CreateMuaProfileRequest};
 use crate::repository::traits::{UserRepository, MuaRepository};
 use super::traits::MuaService;
-use super::S3Service;
+use super::image_processing;
+use super::object_storage;
+use super::storage_outbox;
+use super::upload_session;
 
 pub struct MuaServiceImpl {
     user_repository: Box<dyn UserRepository>,
@@ -23,7 +25,7 @@ impl MuaServiceImpl {
 
 #[async_trait]
 impl MuaService for MuaServiceImpl {
-    async fn search_muas(&self, pool: &PgPool, params: SearchMuasRequest) -> Result<Vec<MuaProfileResponse>> {
+    async fn search_muas(&self, pool: &PgPool, params: SearchMuasRequest) -> Result<Page<MuaProfileResponse>> {
         self.mua_repository.search_muas(pool, &params).await
     }
 
@@ -42,22 +44,56 @@ impl MuaService for MuaServiceImpl {
             return Err(anyhow::anyhow!("MUA profile already exists for this user"));
         }
 
-        // Handle profile picture upload if provided
-        if let Some(profile_picture_base64) = &profile_data.profile_picture_base64 {
-            let s3_service = S3Service::new().await?;
-            let (mime_type, image_bytes) = s3_service.validate_image_base64(profile_picture_base64)?;
-            let profile_picture_url = s3_service.upload_image(
-                Bytes::from(image_bytes),
-                &mime_type,
-                "profile-pictures"
-            ).await?;
-
-            profile_data.profile_picture_url = Some(profile_picture_url);
-            profile_data.profile_picture_base64 = None;
+        if let Some(timezone) = &profile_data.timezone {
+            profile_data.timezone = Some(super::timezone_service::parse_timezone(timezone)?.name().to_string());
         }
 
+        // Handle profile picture upload if provided, either inline or through a presigned URL.
+        // Both are processed before the profile exists so a bad image doesn't leave half a profile.
+        let mut presigned_upload = None;
+        let profile_picture_url = if let Some(profile_picture_base64) = profile_data.profile_picture_base64.take() {
+            let storage = object_storage::from_env().await?;
+            let (_mime_type, image_bytes) = object_storage::validate_image_base64(&profile_picture_base64)?;
+            Some(image_processing::store_avatar(
+                storage.as_ref(),
+                "profile-pictures",
+                image_bytes
+            ).await?)
+        } else if let Some(image_url) = profile_data.profile_picture_url.take().filter(|url| !url.trim().is_empty()) {
+            let storage = object_storage::from_env().await?;
+            let (object_key, avatar_url) = upload_session::attach_avatar_upload(pool, storage.as_ref(), user_id, &image_url).await?;
+            presigned_upload = Some((image_url, object_key));
+            Some(avatar_url)
+        } else {
+            None
+        };
+
         // Create the MUA profile
-        let mua_profile = self.mua_repository.create_mua_profile(pool, user_id, profile_data).await?;
+        let created = async {
+            let mut mua_profile = self.mua_repository.create_mua_profile(pool, user_id, profile_data).await?;
+            if let Some(profile_picture_url) = &profile_picture_url {
+                super::user_service::replace_profile_picture(pool, user_id, Some(profile_picture_url)).await?;
+                mua_profile.user.profile_picture_url = Some(profile_picture_url.clone());
+            }
+            Ok::<_, anyhow::Error>(mua_profile)
+        }
+        .await;
+
+        let mua_profile = match (created, presigned_upload) {
+            (Ok(mua_profile), Some((image_url, _))) => {
+                upload_session::finish_upload(pool, &image_url).await?;
+                mua_profile
+            }
+            (Ok(mua_profile), None) => mua_profile,
+            (Err(e), Some((_, object_key))) => {
+                if let Some(avatar_url) = &profile_picture_url {
+                    upload_session::discard_avatar_upload(pool, &object_key, avatar_url).await?;
+                }
+                return Err(e);
+            }
+            (Err(e), None) => return Err(e),
+        };
+
         Ok(mua_profile)
     }
 
@@ -73,41 +109,61 @@ impl MuaService for MuaServiceImpl {
         if let Some(image_data) = portfolio_data.get("image_base64").and_then(|v| v.as_str()) {
             println!("DEBUG: Processing image data for portfolio");
 
-            // Initialize S3 service
-            let s3_service = S3Service::new().await?;
-            println!("DEBUG: S3 service initialized");
+            // Initialize storage backend
+            let storage = object_storage::from_env().await?;
+            println!("DEBUG: Storage backend initialized");
 
-            // Validate and upload image
-            let (mime_type, image_bytes) = s3_service.validate_image_base64(image_data)?;
+            // Validate, resize and upload image
+            let (mime_type, image_bytes) = object_storage::validate_image_base64(image_data)?;
             println!("DEBUG: Image validated, mime_type: {}, size: {} bytes", mime_type, image_bytes.len());
 
-            let image_url = match s3_service.upload_image(
-                Bytes::from(image_bytes),
-                &mime_type,
-                "portfolio"
-            ).await {
-                Ok(url) => {
-                    println!("DEBUG: Image uploaded to: {}", url);
-                    url
-                }
+            let variants = image_processing::store_portfolio_variants(
+                storage.as_ref(),
+                &format!("portfolio/{}", user_id),
+                image_bytes
+            ).await?;
+            println!("DEBUG: Image variants uploaded to: {}", variants.full_url);
+
+            // Replace base64 data with the stored variants' URLs
+            processed_data["image_url"] = serde_json::Value::String(variants.full_url.clone());
+            processed_data["thumbnail_url"] = serde_json::Value::String(variants.thumbnail_url.clone());
+            processed_data["medium_url"] = serde_json::Value::String(variants.medium_url.clone());
+            processed_data.as_object_mut().unwrap().remove("image_base64");
+
+            return match self.mua_repository.create_portfolio_item(pool, mua_id, &processed_data).await {
+                Ok(item) => Ok(item),
                 Err(e) => {
-                    println!("DEBUG: S3 upload failed: {}, using fallback URL", e);
-                    // Fallback: generate a placeholder URL for testing
-                    format!("https://picsum.photos/seed/portfolio-fallback/400/300.jpg")
+                    storage_outbox::enqueue_deletions(pool, &variants.urls()).await?;
+                    Err(e)
                 }
             };
-
-            // Replace base64 data with S3 URL
-            processed_data["image_url"] = serde_json::Value::String(image_url);
-            processed_data.as_object_mut().unwrap().remove("image_base64");
         }
 
-        self.mua_repository.create_portfolio_item(pool, mua_id, &processed_data).await
+        // Otherwise the image was uploaded through a presigned URL, make sure it's really there
+        let image_url = portfolio_data.get("image_url").and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("image_url or image_base64 is required"))?;
+        let storage = object_storage::from_env().await?;
+        let (object_key, variants) = upload_session::attach_portfolio_upload(pool, storage.as_ref(), user_id, image_url).await?;
+
+        processed_data["image_url"] = serde_json::Value::String(variants.full_url.clone());
+        processed_data["thumbnail_url"] = serde_json::Value::String(variants.thumbnail_url.clone());
+        processed_data["medium_url"] = serde_json::Value::String(variants.medium_url.clone());
+
+        match self.mua_repository.create_portfolio_item(pool, mua_id, &processed_data).await {
+            Ok(item) => {
+                upload_session::finish_upload(pool, image_url).await?;
+                Ok(item)
+            }
+            Err(e) => {
+                upload_session::discard_portfolio_upload(pool, &object_key, &variants).await?;
+                Err(e)
+            }
+        }
     }
 }
 
 // Legacy functions for backward compatibility
-pub async fn search_muas(pool: &PgPool, params: SearchMuasRequest) -> Result<Vec<MuaProfileResponse>> {
+pub async fn search_muas(pool: &PgPool, params: SearchMuasRequest) -> Result<Page<MuaProfileResponse>> {
     let user_repository = crate::repository::UserRepositoryImpl::new();
     let mua_repository = crate::repository::MuaRepositoryImpl::new();
     let mua_service = MuaServiceImpl::new(
@@ -0,0 +1,613 @@
+use anyhow::Result;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use chrono_tz::Tz;
+use sqlx::{PgPool, Postgres, Row, Transaction};
+use uuid::Uuid;
+
+use crate::models::{Booking, BookingQuote, RescheduleRequest, RescheduleStatus};
+use super::job_queue::{self, Job};
+use super::notifier::{Notification, Notifier, Recipient};
+
+/// Reminders go out this many hours before the event
+const REMINDER_HOURS: [i64; 2] = [24, 2];
+
+/// Booking details needed to write a notification, loaded fresh when the job runs
+struct BookingContext {
+    customer_id: Uuid,
+    status: String,
+    service_type: String,
+    event_date: DateTime<Utc>,
+    event_location: String,
+    duration_hours: i32,
+    review_window_closes_at: Option<DateTime<Utc>>,
+    /// The MUA's, which event times are written in
+    timezone: Tz,
+    customer: Recipient,
+    mua: Recipient,
+}
+
+/// Queue the "new request" notification for the MUA
+pub async fn schedule_booking_requested(pool: &PgPool, booking: &Booking) -> Result<()> {
+    job_queue::enqueue(pool, &Job::BookingRequested { booking_id: booking.id }, Utc::now()).await?;
+    Ok(())
+}
+
+/// Queue the confirmation for the customer plus reminders ahead of the event
+pub async fn schedule_booking_confirmed(pool: &PgPool, booking: &Booking) -> Result<()> {
+    let mut tx = pool.begin().await?;
+
+    job_queue::enqueue(&mut tx, &Job::BookingConfirmed { booking_id: booking.id }, Utc::now()).await?;
+    schedule_reminders(&mut tx, booking.id, booking.event_date).await?;
+
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Queue a check of the MUA's saved-by customers after their schedule gains room
+pub async fn schedule_favorite_openings(pool: &PgPool, mua_id: Uuid) -> Result<()> {
+    job_queue::enqueue(pool, &Job::FavoriteOpenings { mua_id }, Utc::now()).await?;
+    Ok(())
+}
+
+/// Queue reminders ahead of `event_date`; reminders queued for an earlier date drop themselves
+pub async fn schedule_reminders(tx: &mut Transaction<'_, Postgres>, booking_id: Uuid, event_date: DateTime<Utc>) -> Result<()> {
+    for hours_before in REMINDER_HOURS {
+        let run_at = event_date - Duration::hours(hours_before);
+        if run_at <= Utc::now() {
+            continue;
+        }
+
+        let reminder = Job::BookingReminder {
+            booking_id,
+            event_date,
+            hours_before,
+        };
+        job_queue::enqueue(&mut *tx, &reminder, run_at).await?;
+    }
+
+    Ok(())
+}
+
+/// Run a notification job, sending through every configured channel.
+///
+/// Deliveries are recorded against `job_id`, so when a retry runs only the
+/// channels and recipients that failed last time are sent to again.
+pub async fn handle_job(pool: &PgPool, notifiers: &[Box<dyn Notifier>], job_id: Uuid, job: &Job) -> Result<()> {
+    match job {
+        Job::BookingRequested { booking_id } => {
+            let Some(booking) = load_booking_context(pool, *booking_id).await? else {
+                return Ok(());
+            };
+
+            let notification = Notification {
+                recipient: booking.mua.clone(),
+                subject: "Permintaan booking baru".to_string(),
+                body: format!(
+                    "Halo {},\n\n{} mengajukan booking {} pada {} di {} ({} jam).\nSilakan konfirmasi atau tolak permintaan ini di dasbor MUA Connect.",
+                    booking.mua.name,
+                    booking.customer.name,
+                    booking.service_type,
+                    format_event_date(booking.event_date, booking.timezone),
+                    booking.event_location,
+                    booking.duration_hours
+                ),
+            };
+            deliver(pool, notifiers, job_id, &[notification]).await
+        }
+        Job::BookingConfirmed { booking_id } => {
+            let Some(booking) = load_booking_context(pool, *booking_id).await? else {
+                return Ok(());
+            };
+
+            let notification = Notification {
+                recipient: booking.customer.clone(),
+                subject: "Booking kamu sudah dikonfirmasi".to_string(),
+                body: format!(
+                    "Halo {},\n\n{} telah mengonfirmasi booking {} pada {} di {}.",
+                    booking.customer.name,
+                    booking.mua.name,
+                    booking.service_type,
+                    format_event_date(booking.event_date, booking.timezone),
+                    booking.event_location
+                ),
+            };
+            deliver(pool, notifiers, job_id, &[notification]).await
+        }
+        Job::BookingExpired { booking_id } => {
+            let Some(booking) = load_booking_context(pool, *booking_id).await? else {
+                return Ok(());
+            };
+
+            let customer_notification = Notification {
+                recipient: booking.customer.clone(),
+                subject: "Permintaan booking kedaluwarsa".to_string(),
+                body: format!(
+                    "Halo {},\n\nPermintaan booking {} dengan {} pada {} tidak mendapat respons sebelum batas waktu, jadi permintaan ini dibatalkan otomatis. Silakan cari MUA lain atau ajukan ulang.",
+                    booking.customer.name,
+                    booking.service_type,
+                    booking.mua.name,
+                    format_event_date(booking.event_date, booking.timezone)
+                ),
+            };
+
+            let mua_notification = Notification {
+                recipient: booking.mua.clone(),
+                subject: "Permintaan booking kedaluwarsa".to_string(),
+                body: format!(
+                    "Halo {},\n\nPermintaan booking {} dari {} pada {} dibatalkan otomatis karena tidak ada respons sebelum batas waktu.",
+                    booking.mua.name,
+                    booking.service_type,
+                    booking.customer.name,
+                    format_event_date(booking.event_date, booking.timezone)
+                ),
+            };
+            deliver(pool, notifiers, job_id, &[customer_notification, mua_notification]).await
+        }
+        Job::BookingCompleted { booking_id } => {
+            let Some(booking) = load_booking_context(pool, *booking_id).await? else {
+                return Ok(());
+            };
+
+            // Marked as no-show or otherwise changed before the job ran
+            let Some(review_window_closes_at) = booking.review_window_closes_at.filter(|_| booking.status == "completed") else {
+                return Ok(());
+            };
+
+            let notifications: Vec<Notification> = [(&booking.customer, &booking.mua), (&booking.mua, &booking.customer)]
+                .into_iter()
+                .map(|(recipient, counterpart)| Notification {
+                    recipient: recipient.clone(),
+                    subject: format!("Bagaimana booking {} kamu?", booking.service_type),
+                    body: format!(
+                        "Halo {},\n\nBooking {} dengan {} pada {} telah selesai. Bagikan ulasanmu di MUA Connect sebelum {}.",
+                        recipient.name,
+                        booking.service_type,
+                        counterpart.name,
+                        format_event_date(booking.event_date, booking.timezone),
+                        format_event_date(review_window_closes_at, booking.timezone)
+                    ),
+                })
+                .collect();
+            deliver(pool, notifiers, job_id, &notifications).await
+        }
+        Job::BookingReminder { booking_id, event_date, hours_before } => {
+            let Some(booking) = load_booking_context(pool, *booking_id).await? else {
+                return Ok(());
+            };
+
+            // Cancelled or rescheduled since the reminder was queued
+            if booking.status != "confirmed" || booking.event_date != *event_date {
+                return Ok(());
+            }
+
+            let when = if *hours_before >= 24 {
+                format!("{} hari lagi", hours_before / 24)
+            } else {
+                format!("{} jam lagi", hours_before)
+            };
+
+            let notifications: Vec<Notification> = [(&booking.customer, &booking.mua), (&booking.mua, &booking.customer)]
+                .into_iter()
+                .map(|(recipient, counterpart)| Notification {
+                    recipient: recipient.clone(),
+                    subject: format!("Pengingat: booking {} {}", booking.service_type, when),
+                    body: format!(
+                        "Halo {},\n\nBooking {} dengan {} dimulai {} pada {} di {}.",
+                        recipient.name,
+                        booking.service_type,
+                        counterpart.name,
+                        when,
+                        format_event_date(booking.event_date, booking.timezone),
+                        booking.event_location
+                    ),
+                })
+                .collect();
+            deliver(pool, notifiers, job_id, &notifications).await
+        }
+        Job::RescheduleRequested { reschedule_request_id } => {
+            let Some((reschedule, booking)) = load_reschedule_context(pool, *reschedule_request_id).await? else {
+                return Ok(());
+            };
+
+            let (recipient, proposer) = if reschedule.requested_by == booking.customer_id {
+                (&booking.mua, &booking.customer)
+            } else {
+                (&booking.customer, &booking.mua)
+            };
+
+            let notification = Notification {
+                recipient: recipient.clone(),
+                subject: "Permintaan jadwal ulang booking".to_string(),
+                body: format!(
+                    "Halo {},\n\n{} ingin memindahkan booking {} dari {} ke {} ({} jam).{}\nSilakan terima atau tolak permintaan ini di MUA Connect.",
+                    recipient.name,
+                    proposer.name,
+                    booking.service_type,
+                    format_event_date(reschedule.previous_event_date, booking.timezone),
+                    format_event_date(reschedule.proposed_event_date, booking.timezone),
+                    reschedule.proposed_duration_hours,
+                    reschedule.reason.map(|reason| format!("\nAlasan: {}", reason)).unwrap_or_default()
+                ),
+            };
+            deliver(pool, notifiers, job_id, &[notification]).await
+        }
+        Job::RescheduleResponded { reschedule_request_id } => {
+            let Some((reschedule, booking)) = load_reschedule_context(pool, *reschedule_request_id).await? else {
+                return Ok(());
+            };
+
+            let (proposer, responder) = if reschedule.requested_by == booking.customer_id {
+                (&booking.customer, &booking.mua)
+            } else {
+                (&booking.mua, &booking.customer)
+            };
+
+            let (subject, outcome) = match reschedule.status {
+                RescheduleStatus::Accepted => (
+                    "Jadwal ulang booking diterima",
+                    format!("menerima permintaanmu. Booking {} sekarang pada {}.", booking.service_type, format_event_date(reschedule.proposed_event_date, booking.timezone)),
+                ),
+                RescheduleStatus::Declined => (
+                    "Jadwal ulang booking ditolak",
+                    format!("menolak permintaanmu. Booking {} tetap pada {}.", booking.service_type, format_event_date(booking.event_date, booking.timezone)),
+                ),
+                // Nothing to tell anyone about a proposal that was withdrawn or is still open
+                _ => return Ok(()),
+            };
+
+            let notification = Notification {
+                recipient: proposer.clone(),
+                subject: subject.to_string(),
+                body: format!("Halo {},\n\n{} {}", proposer.name, responder.name, outcome),
+            };
+            deliver(pool, notifiers, job_id, &[notification]).await
+        }
+        Job::QuoteProposed { quote_id } => {
+            let Some((quote, booking)) = load_quote_context(pool, *quote_id).await? else {
+                return Ok(());
+            };
+
+            let (recipient, proposer) = if quote.proposed_by == booking.customer_id {
+                (&booking.mua, &booking.customer)
+            } else {
+                (&booking.customer, &booking.mua)
+            };
+
+            let notification = Notification {
+                recipient: recipient.clone(),
+                subject: format!("Penawaran harga untuk booking {}", booking.service_type),
+                body: format!(
+                    "Halo {},\n\n{} mengajukan harga Rp{}{} untuk booking {} pada {}.{}\nTerima atau ajukan penawaran balik di MUA Connect.",
+                    recipient.name,
+                    proposer.name,
+                    quote.price,
+                    quote.deposit_amount.as_ref().map(|deposit| format!(" (DP Rp{})", deposit)).unwrap_or_default(),
+                    booking.service_type,
+                    format_event_date(booking.event_date, booking.timezone),
+                    quote.notes.as_ref().map(|notes| format!("\nCatatan: {}", notes)).unwrap_or_default()
+                ),
+            };
+            deliver(pool, notifiers, job_id, &[notification]).await
+        }
+        Job::QuoteAccepted { quote_id } => {
+            let Some((quote, booking)) = load_quote_context(pool, *quote_id).await? else {
+                return Ok(());
+            };
+
+            let notification = Notification {
+                recipient: booking.mua.clone(),
+                subject: "Penawaran harga diterima".to_string(),
+                body: format!(
+                    "Halo {},\n\n{} menerima penawaran Rp{} untuk booking {} pada {}. Booking ini sekarang terkonfirmasi.",
+                    booking.mua.name,
+                    booking.customer.name,
+                    quote.price,
+                    booking.service_type,
+                    format_event_date(booking.event_date, booking.timezone)
+                ),
+            };
+            deliver(pool, notifiers, job_id, &[notification]).await
+        }
+        Job::FavoriteOpenings { mua_id } => {
+            let timezone = match super::timezone_service::timezone_for_mua(pool, *mua_id).await {
+                Ok(timezone) => timezone,
+                Err(e) if e.to_string().contains("not found") => return Ok(()),
+                Err(e) => return Err(e),
+            };
+            let today = Utc::now().with_timezone(&timezone).date_naive();
+
+            let watchers = sqlx::query(
+                r#"
+                SELECT f.user_id, f.event_date, u.full_name, u.email, u.phone_number, m.full_name AS mua_name
+                FROM favorites f
+                JOIN users u ON u.id = f.user_id
+                JOIN mua_profiles mp ON mp.id = f.mua_id
+                JOIN users m ON m.id = mp.user_id
+                WHERE f.mua_id = $1 AND f.event_date >= $2 AND f.openings_notified_at IS NULL
+                "#
+            )
+            .bind(mua_id)
+            .bind(today)
+            .fetch_all(pool)
+            .await?;
+
+            let mut errors = Vec::new();
+
+            for watcher in watchers {
+                let user_id: Uuid = watcher.get("user_id");
+                let event_date: NaiveDate = watcher.get("event_date");
+                if super::availability_service::bookable_slots(pool, *mua_id, event_date, 1, None).await?.is_empty() {
+                    continue;
+                }
+
+                // Claim before sending so overlapping jobs don't notify twice
+                let claimed = sqlx::query(
+                    "UPDATE favorites SET openings_notified_at = NOW() WHERE user_id = $1 AND mua_id = $2 AND event_date = $3 AND openings_notified_at IS NULL"
+                )
+                .bind(user_id)
+                .bind(mua_id)
+                .bind(event_date)
+                .execute(pool)
+                .await?;
+                if claimed.rows_affected() == 0 {
+                    continue;
+                }
+
+                let recipient = Recipient {
+                    name: watcher.get("full_name"),
+                    email: watcher.get("email"),
+                    phone_number: watcher.get("phone_number"),
+                };
+                let mua_name: String = watcher.get("mua_name");
+                let notification = Notification {
+                    recipient: recipient.clone(),
+                    subject: "MUA favoritmu punya slot kosong".to_string(),
+                    body: format!(
+                        "Halo {},\n\n{} sekarang punya slot kosong pada {}. Booking sekarang di MUA Connect sebelum diambil orang lain.",
+                        recipient.name,
+                        mua_name,
+                        event_date.format("%d/%m/%Y")
+                    ),
+                };
+
+                // Released so the retry picks this watcher up again; the rest are done
+                if let Err(e) = deliver(pool, notifiers, job_id, &[notification]).await {
+                    sqlx::query("UPDATE favorites SET openings_notified_at = NULL WHERE user_id = $1 AND mua_id = $2 AND event_date = $3")
+                        .bind(user_id)
+                        .bind(mua_id)
+                        .bind(event_date)
+                        .execute(pool)
+                        .await?;
+                    errors.push(e.to_string());
+                }
+            }
+
+            if errors.is_empty() {
+                Ok(())
+            } else {
+                Err(anyhow::anyhow!("{}", errors.join("; ")))
+            }
+        }
+        Job::IssueRefund { .. } => Err(anyhow::anyhow!("{} is not a notification job", job.job_type())),
+    }
+}
+
+/// Send each notification on every channel, skipping the pairs an earlier attempt of
+/// `job_id` already delivered. Every pair is tried even after a failure; the job then
+/// fails so it's retried for the ones that didn't go out.
+async fn deliver(pool: &PgPool, notifiers: &[Box<dyn Notifier>], job_id: Uuid, notifications: &[Notification]) -> Result<()> {
+    let delivered: Vec<(String, String)> = sqlx::query_as(
+        "SELECT channel, recipient FROM notification_deliveries WHERE job_id = $1"
+    )
+    .bind(job_id)
+    .fetch_all(pool)
+    .await?;
+
+    let mut errors = Vec::new();
+
+    for notification in notifications {
+        for notifier in notifiers {
+            let channel = notifier.channel();
+            let recipient = &notification.recipient.email;
+            if delivered.iter().any(|(done_channel, done_recipient)| done_channel == channel && done_recipient == recipient) {
+                continue;
+            }
+
+            match notifier.send(notification).await {
+                Ok(()) => {
+                    sqlx::query(
+                        "INSERT INTO notification_deliveries (job_id, channel, recipient) VALUES ($1, $2, $3) ON CONFLICT DO NOTHING"
+                    )
+                    .bind(job_id)
+                    .bind(channel)
+                    .bind(recipient)
+                    .execute(pool)
+                    .await?;
+                }
+                Err(e) => errors.push(format!("{} to {}: {}", channel, recipient, e)),
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(anyhow::anyhow!("Notification delivery failed ({})", errors.join("; ")))
+    }
+}
+
+/// On the MUA's wall clock, since that's where the event happens, e.g. `14/02/2026 09:00 WIB`
+fn format_event_date(event_date: DateTime<Utc>, timezone: Tz) -> String {
+    event_date.with_timezone(&timezone).format("%d/%m/%Y %H:%M %Z").to_string()
+}
+
+async fn load_booking_context(pool: &PgPool, booking_id: Uuid) -> Result<Option<BookingContext>> {
+    let row = sqlx::query(
+        r#"
+        SELECT b.customer_id, b.status::text as status, b.service_type, b.event_date, b.event_location, b.duration_hours,
+               b.review_window_closes_at, mp.timezone,
+               c.full_name as customer_name, c.email as customer_email, c.phone_number as customer_phone,
+               m.full_name as mua_name, m.email as mua_email, m.phone_number as mua_phone
+        FROM bookings b
+        JOIN users c ON c.id = b.customer_id
+        JOIN mua_profiles mp ON mp.id = b.mua_id
+        JOIN users m ON m.id = mp.user_id
+        WHERE b.id = $1
+        "#
+    )
+    .bind(booking_id)
+    .fetch_optional(pool)
+    .await?;
+
+    let Some(row) = row else {
+        return Ok(None);
+    };
+
+    Ok(Some(BookingContext {
+        customer_id: row.get("customer_id"),
+        status: row.get("status"),
+        service_type: row.get("service_type"),
+        event_date: row.get("event_date"),
+        event_location: row.get("event_location"),
+        duration_hours: row.get("duration_hours"),
+        review_window_closes_at: row.get("review_window_closes_at"),
+        timezone: super::timezone_service::parse_timezone(row.get("timezone"))?,
+        customer: Recipient {
+            name: row.get("customer_name"),
+            email: row.get("customer_email"),
+            phone_number: row.get("customer_phone"),
+        },
+        mua: Recipient {
+            name: row.get("mua_name"),
+            email: row.get("mua_email"),
+            phone_number: row.get("mua_phone"),
+        },
+    }))
+}
+
+async fn load_reschedule_context(pool: &PgPool, reschedule_request_id: Uuid) -> Result<Option<(RescheduleRequest, BookingContext)>> {
+    let reschedule = sqlx::query_as::<_, RescheduleRequest>("SELECT * FROM reschedule_requests WHERE id = $1")
+        .bind(reschedule_request_id)
+        .fetch_optional(pool)
+        .await?;
+
+    let Some(reschedule) = reschedule else {
+        return Ok(None);
+    };
+
+    Ok(load_booking_context(pool, reschedule.booking_id).await?.map(|booking| (reschedule, booking)))
+}
+
+async fn load_quote_context(pool: &PgPool, quote_id: Uuid) -> Result<Option<(BookingQuote, BookingContext)>> {
+    let quote = sqlx::query_as::<_, BookingQuote>("SELECT * FROM booking_quotes WHERE id = $1")
+        .bind(quote_id)
+        .fetch_optional(pool)
+        .await?;
+
+    let Some(quote) = quote else {
+        return Ok(None);
+    };
+
+    Ok(load_booking_context(pool, quote.booking_id).await?.map(|booking| (quote, booking)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_trait::async_trait;
+    use std::sync::{Arc, Mutex};
+
+    /// What a test channel sent, and which recipient it currently can't reach
+    #[derive(Default)]
+    struct Outbox {
+        unreachable: Mutex<Option<String>>,
+        sent: Mutex<Vec<String>>,
+    }
+
+    struct TestNotifier {
+        channel: &'static str,
+        outbox: Arc<Outbox>,
+    }
+
+    #[async_trait]
+    impl Notifier for TestNotifier {
+        fn channel(&self) -> &'static str {
+            self.channel
+        }
+
+        async fn send(&self, notification: &Notification) -> Result<()> {
+            let email = &notification.recipient.email;
+            if self.outbox.unreachable.lock().unwrap().as_ref() == Some(email) {
+                return Err(anyhow::anyhow!("mailbox unavailable"));
+            }
+            self.outbox.sent.lock().unwrap().push(email.clone());
+            Ok(())
+        }
+    }
+
+    fn take_sent(outbox: &Outbox) -> Vec<String> {
+        let mut sent = std::mem::take(&mut *outbox.sent.lock().unwrap());
+        sent.sort();
+        sent
+    }
+
+    async fn insert_user(pool: &PgPool, email: &str, user_type: &str) -> Uuid {
+        sqlx::query_scalar(
+            "INSERT INTO users (email, password_hash, user_type, full_name) VALUES ($1, 'x', $2::user_type, $1) RETURNING id"
+        )
+        .bind(email)
+        .bind(user_type)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn retries_only_the_deliveries_that_failed(pool: PgPool) {
+        let customer_id = insert_user(&pool, "customer@test.muaconnect.dev", "customer").await;
+        let mua_user_id = insert_user(&pool, "mua@test.muaconnect.dev", "mua").await;
+        let mua_id: Uuid = sqlx::query_scalar("INSERT INTO mua_profiles (user_id, location) VALUES ($1, 'Jakarta') RETURNING id")
+            .bind(mua_user_id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        let booking_id: Uuid = sqlx::query_scalar(
+            r#"
+            INSERT INTO bookings (customer_id, mua_id, service_type, event_date, event_location, duration_hours, price)
+            VALUES ($1, $2, 'Bridal Makeup', NOW() + INTERVAL '3 days', 'Jakarta', 3, 1500000)
+            RETURNING id
+            "#
+        )
+        .bind(customer_id)
+        .bind(mua_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+        let job = Job::BookingExpired { booking_id };
+        let job_id = job_queue::enqueue(&pool, &job, Utc::now()).await.unwrap();
+
+        let email = Arc::new(Outbox::default());
+        let sms = Arc::new(Outbox::default());
+        *sms.unreachable.lock().unwrap() = Some("customer@test.muaconnect.dev".to_string());
+        let notifiers: Vec<Box<dyn Notifier>> = vec![
+            Box::new(TestNotifier { channel: "email", outbox: email.clone() }),
+            Box::new(TestNotifier { channel: "sms", outbox: sms.clone() }),
+        ];
+
+        // Every other delivery still goes out when one fails
+        let error = handle_job(&pool, &notifiers, job_id, &job).await.unwrap_err().to_string();
+        assert!(error.contains("sms to customer@test.muaconnect.dev"), "{}", error);
+        assert_eq!(take_sent(&email), vec!["customer@test.muaconnect.dev", "mua@test.muaconnect.dev"]);
+        assert_eq!(take_sent(&sms), vec!["mua@test.muaconnect.dev"]);
+
+        // The retry sends the one that failed and nothing else
+        *sms.unreachable.lock().unwrap() = None;
+        handle_job(&pool, &notifiers, job_id, &job).await.unwrap();
+        assert!(take_sent(&email).is_empty());
+        assert_eq!(take_sent(&sms), vec!["customer@test.muaconnect.dev"]);
+
+        handle_job(&pool, &notifiers, job_id, &job).await.unwrap();
+        assert!(take_sent(&email).is_empty() && take_sent(&sms).is_empty());
+    }
+}
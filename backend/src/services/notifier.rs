@@ -0,0 +1,170 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use lettre::message::header::ContentType;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+use std::env;
+
+/// Who a notification goes to; channels use whichever contact detail they need
+#[derive(Debug, Clone)]
+pub struct Recipient {
+    pub name: String,
+    pub email: String,
+    pub phone_number: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Notification {
+    pub recipient: Recipient,
+    pub subject: String,
+    pub body: String,
+}
+
+/// A delivery channel for notifications
+#[async_trait]
+pub trait Notifier: Send + Sync {
+    fn channel(&self) -> &'static str;
+    async fn send(&self, notification: &Notification) -> Result<()>;
+}
+
+/// Prints notifications instead of delivering them, the default for development
+pub struct LogNotifier;
+
+#[async_trait]
+impl Notifier for LogNotifier {
+    fn channel(&self) -> &'static str {
+        "log"
+    }
+
+    async fn send(&self, notification: &Notification) -> Result<()> {
+        println!(
+            "📨 Notification to {} <{}>: {}\n{}",
+            notification.recipient.name,
+            notification.recipient.email,
+            notification.subject,
+            notification.body
+        );
+        Ok(())
+    }
+}
+
+/// Sends plain-text email over SMTP
+pub struct EmailNotifier {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: String,
+}
+
+impl EmailNotifier {
+    pub fn from_env() -> Result<Self> {
+        let host = env::var("SMTP_HOST")
+            .map_err(|_| anyhow::anyhow!("SMTP_HOST must be set for email notifications"))?;
+        let port = env::var("SMTP_PORT")
+            .ok()
+            .and_then(|v| v.parse::<u16>().ok())
+            .unwrap_or(587);
+        let from = env::var("SMTP_FROM")
+            .unwrap_or_else(|_| "MUA Connect <no-reply@muaconnect.id>".to_string());
+
+        let mut builder = AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&host)?
+            .port(port);
+
+        if let (Ok(username), Ok(password)) = (env::var("SMTP_USERNAME"), env::var("SMTP_PASSWORD")) {
+            builder = builder.credentials(Credentials::new(username, password));
+        }
+
+        Ok(Self {
+            transport: builder.build(),
+            from,
+        })
+    }
+}
+
+#[async_trait]
+impl Notifier for EmailNotifier {
+    fn channel(&self) -> &'static str {
+        "email"
+    }
+
+    async fn send(&self, notification: &Notification) -> Result<()> {
+        let message = Message::builder()
+            .from(self.from.parse()?)
+            .to(format!("{} <{}>", notification.recipient.name, notification.recipient.email).parse()?)
+            .subject(&notification.subject)
+            .header(ContentType::TEXT_PLAIN)
+            .body(notification.body.clone())?;
+
+        self.transport.send(message).await?;
+        Ok(())
+    }
+}
+
+/// Posts messages to a WhatsApp-style HTTP gateway as `{"to": ..., "message": ...}`
+pub struct WhatsAppNotifier {
+    client: reqwest::Client,
+    api_url: String,
+    api_token: String,
+}
+
+impl WhatsAppNotifier {
+    pub fn from_env() -> Result<Self> {
+        let api_url = env::var("WHATSAPP_API_URL")
+            .map_err(|_| anyhow::anyhow!("WHATSAPP_API_URL must be set for WhatsApp notifications"))?;
+        let api_token = env::var("WHATSAPP_API_TOKEN")
+            .map_err(|_| anyhow::anyhow!("WHATSAPP_API_TOKEN must be set for WhatsApp notifications"))?;
+
+        Ok(Self {
+            client: reqwest::Client::new(),
+            api_url,
+            api_token,
+        })
+    }
+}
+
+#[async_trait]
+impl Notifier for WhatsAppNotifier {
+    fn channel(&self) -> &'static str {
+        "whatsapp"
+    }
+
+    async fn send(&self, notification: &Notification) -> Result<()> {
+        // Not everyone has a phone number on file, email still covers them
+        let Some(phone_number) = &notification.recipient.phone_number else {
+            return Ok(());
+        };
+
+        let response = self.client
+            .post(&self.api_url)
+            .bearer_auth(&self.api_token)
+            .json(&serde_json::json!({
+                "to": phone_number,
+                "message": format!("*{}*\n\n{}", notification.subject, notification.body),
+            }))
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(anyhow::anyhow!("WhatsApp gateway returned {}", response.status()));
+        }
+
+        Ok(())
+    }
+}
+
+/// Channels listed in `NOTIFICATION_CHANNELS` (comma separated: log, email, whatsapp), defaults to log
+pub fn notifiers_from_env() -> Result<Vec<Box<dyn Notifier>>> {
+    let channels = env::var("NOTIFICATION_CHANNELS").unwrap_or_else(|_| "log".to_string());
+
+    channels
+        .split(',')
+        .map(|channel| channel.trim().to_lowercase())
+        .filter(|channel| !channel.is_empty())
+        .map(|channel| -> Result<Box<dyn Notifier>> {
+            match channel.as_str() {
+                "log" => Ok(Box::new(LogNotifier)),
+                "email" => Ok(Box::new(EmailNotifier::from_env()?)),
+                "whatsapp" => Ok(Box::new(WhatsAppNotifier::from_env()?)),
+                other => Err(anyhow::anyhow!("Unknown notification channel: {}", other)),
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,132 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use base64::{Engine as _, engine::general_purpose};
+use bytes::Bytes;
+use std::env;
+use std::time::Duration;
+use uuid::Uuid;
+
+use super::local_storage::LocalStorage;
+use super::s3_service::S3Service;
+
+/// Maximum accepted size for a single image upload (10MB)
+pub const MAX_IMAGE_SIZE: usize = 10 * 1024 * 1024;
+
+/// What a `HEAD` on a stored object reports
+#[derive(Debug, Clone)]
+pub struct ObjectMetadata {
+    pub size: u64,
+    pub content_type: Option<String>,
+}
+
+/// Storage backend for uploaded images.
+///
+/// Objects are addressed by key (e.g. `portfolio/<uuid>.jpg`); the public URL
+/// handed to clients is always derived from the key by the backend.
+#[async_trait]
+pub trait ObjectStorage: Send + Sync {
+    /// Store an object and return its public URL
+    async fn put_object(&self, key: &str, data: Bytes, content_type: &str) -> Result<String>;
+    async fn get_object(&self, key: &str) -> Result<Bytes>;
+    async fn delete_object(&self, key: &str) -> Result<()>;
+    /// Returns `None` if no object exists under `key`
+    async fn head_object(&self, key: &str) -> Result<Option<ObjectMetadata>>;
+    /// Generate a URL the client can `PUT` the object to directly
+    async fn presign_upload(&self, key: &str, content_type: &str, expires_in: Duration) -> Result<String>;
+    fn public_url(&self, key: &str) -> String;
+
+    /// Reverse of `public_url`, returns `None` for URLs this backend doesn't serve
+    fn key_from_url(&self, url: &str) -> Option<String> {
+        url.strip_prefix(&self.public_url(""))
+            .filter(|key| !key.is_empty())
+            .map(|key| key.to_string())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StorageBackend {
+    S3,
+    Local,
+}
+
+/// Selected with `STORAGE_BACKEND=local|s3`, defaults to S3
+pub fn storage_backend() -> StorageBackend {
+    match env::var("STORAGE_BACKEND").map(|v| v.to_lowercase()).as_deref() {
+        Ok("local") => StorageBackend::Local,
+        _ => StorageBackend::S3,
+    }
+}
+
+pub async fn from_env() -> Result<Box<dyn ObjectStorage>> {
+    match storage_backend() {
+        StorageBackend::Local => Ok(Box::new(LocalStorage::from_env()?)),
+        StorageBackend::S3 => Ok(Box::new(S3Service::new().await?)),
+    }
+}
+
+pub fn file_extension(content_type: &str) -> Result<&'static str> {
+    match content_type {
+        "image/jpeg" => Ok("jpg"),
+        "image/jpg" => Ok("jpg"),
+        "image/png" => Ok("png"),
+        "image/gif" => Ok("gif"),
+        "image/webp" => Ok("webp"),
+        _ => Err(anyhow::anyhow!("Unsupported image format: {}", content_type)),
+    }
+}
+
+/// Bytes `sniff_content_type` needs from the start of a file
+pub const SNIFF_LENGTH: usize = 12;
+
+/// The image type a file's first bytes say it is, used where the backend doesn't keep a content type
+pub fn sniff_content_type(header: &[u8]) -> Option<&'static str> {
+    match header {
+        [0xFF, 0xD8, 0xFF, ..] => Some("image/jpeg"),
+        [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A, ..] => Some("image/png"),
+        [b'G', b'I', b'F', b'8', b'7' | b'9', b'a', ..] => Some("image/gif"),
+        [b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'E', b'B', b'P', ..] => Some("image/webp"),
+        _ => None,
+    }
+}
+
+/// Build a fresh, collision-free key for an upload in `folder`
+pub fn generate_object_key(folder: &str, content_type: &str) -> Result<String> {
+    let file_extension = file_extension(content_type)?;
+    Ok(format!("{}/{}.{}", folder.trim_matches('/'), Uuid::new_v4(), file_extension))
+}
+
+pub fn validate_image_base64(base64_data: &str) -> Result<(String, Vec<u8>)> {
+    let (mime_type, base64_content) = if base64_data.starts_with("data:") {
+        let parts: Vec<&str> = base64_data.split(',').collect();
+        if parts.len() != 2 {
+            return Err(anyhow::anyhow!("Invalid base64 data URL format"));
+        }
+
+        let mime_part = parts[0];
+        let mime_type = mime_part
+            .split(':')
+            .nth(1)
+            .and_then(|s| s.split(';').next())
+            .unwrap_or("image/jpeg");
+
+        (mime_type.to_string(), parts[1])
+    } else {
+        ("image/jpeg".to_string(), base64_data)
+    };
+
+    // Validate MIME type
+    if !mime_type.starts_with("image/") {
+        return Err(anyhow::anyhow!("Invalid file type: {}. Only images are allowed.", mime_type));
+    }
+
+    // Decode and validate file size (max 10MB)
+    let image_data = general_purpose::STANDARD
+        .decode(base64_content)
+        .map_err(|e| anyhow::anyhow!("Failed to decode base64: {}", e))?;
+
+    if image_data.len() > MAX_IMAGE_SIZE {
+        return Err(anyhow::anyhow!("File too large. Maximum size is 10MB."));
+    }
+
+    Ok((mime_type, image_data))
+}
@@ -0,0 +1,144 @@
+use anyhow::Result;
+use sqlx::types::BigDecimal;
+use sqlx::PgPool;
+use std::str::FromStr;
+use uuid::Uuid;
+
+use crate::models::{Page, PageNumberQuery, SaveServiceOfferingRequest, ServiceOffering, MAX_OFFERING_CATEGORY_LENGTH, MAX_OFFERING_NAME_LENGTH, MAX_OFFERING_PRICE};
+
+async fn mua_for_user(pool: &PgPool, user_id: Uuid) -> Result<Uuid> {
+    sqlx::query_scalar("SELECT id FROM mua_profiles WHERE user_id = $1")
+        .bind(user_id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("MUA profile not found"))
+}
+
+/// The request trimmed and checked, with the price parsed
+fn validate(request: SaveServiceOfferingRequest) -> Result<(String, String, Option<String>, BigDecimal, i32)> {
+    let category = request.category.trim().to_string();
+    if category.is_empty() || category.chars().count() > MAX_OFFERING_CATEGORY_LENGTH {
+        return Err(anyhow::anyhow!("Invalid service: category must be 1 to {} characters", MAX_OFFERING_CATEGORY_LENGTH));
+    }
+
+    let name = request.name.trim().to_string();
+    if name.is_empty() || name.chars().count() > MAX_OFFERING_NAME_LENGTH {
+        return Err(anyhow::anyhow!("Invalid service: name must be 1 to {} characters", MAX_OFFERING_NAME_LENGTH));
+    }
+
+    let description = request.description
+        .map(|description| description.trim().to_string())
+        .filter(|description| !description.is_empty());
+
+    let price = BigDecimal::from_str(request.price.trim())
+        .map_err(|_| anyhow::anyhow!("Invalid price format"))?
+        .round(2);
+    if price < BigDecimal::default() {
+        return Err(anyhow::anyhow!("Invalid price: cannot be negative"));
+    }
+    if price >= BigDecimal::from(MAX_OFFERING_PRICE) {
+        return Err(anyhow::anyhow!("Invalid price: must be below {}", MAX_OFFERING_PRICE));
+    }
+
+    if request.duration_minutes <= 0 {
+        return Err(anyhow::anyhow!("Invalid service: duration_minutes must be positive"));
+    }
+
+    Ok((category, name, description, price, request.duration_minutes))
+}
+
+/// What a MUA offers, grouped by category and cheapest first
+pub async fn offerings_for_mua(pool: &PgPool, mua_id: Uuid, page_query: PageNumberQuery) -> Result<Page<ServiceOffering>> {
+    let mua_exists: bool = sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM mua_profiles WHERE id = $1)")
+        .bind(mua_id)
+        .fetch_one(pool)
+        .await?;
+    if !mua_exists {
+        return Err(anyhow::anyhow!("MUA not found"));
+    }
+
+    let offerings = sqlx::query_as::<_, ServiceOffering>(
+        "SELECT * FROM service_offerings WHERE mua_id = $1 ORDER BY lower(category), price, name, id LIMIT $2 OFFSET $3"
+    )
+    .bind(mua_id)
+    .bind(page_query.limit())
+    .bind(page_query.offset())
+    .fetch_all(pool)
+    .await?;
+
+    let total: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM service_offerings WHERE mua_id = $1")
+        .bind(mua_id)
+        .fetch_one(pool)
+        .await?;
+
+    Ok(Page::numbered(offerings, total, page_query.page(), page_query.limit()))
+}
+
+pub async fn get_offerings(pool: &PgPool, user_id: Uuid, page_query: PageNumberQuery) -> Result<Page<ServiceOffering>> {
+    let mua_id = mua_for_user(pool, user_id).await?;
+    offerings_for_mua(pool, mua_id, page_query).await
+}
+
+pub async fn create_offering(pool: &PgPool, user_id: Uuid, request: SaveServiceOfferingRequest) -> Result<ServiceOffering> {
+    let mua_id = mua_for_user(pool, user_id).await?;
+    let (category, name, description, price, duration_minutes) = validate(request)?;
+
+    let offering = sqlx::query_as::<_, ServiceOffering>(
+        r#"
+        INSERT INTO service_offerings (mua_id, category, name, description, price, duration_minutes, created_at, updated_at)
+        VALUES ($1, $2, $3, $4, $5, $6, NOW(), NOW())
+        RETURNING *
+        "#
+    )
+    .bind(mua_id)
+    .bind(&category)
+    .bind(&name)
+    .bind(&description)
+    .bind(&price)
+    .bind(duration_minutes)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(offering)
+}
+
+/// Replace an offering; bookings already made keep the price they were made at
+pub async fn update_offering(pool: &PgPool, user_id: Uuid, offering_id: Uuid, request: SaveServiceOfferingRequest) -> Result<ServiceOffering> {
+    let mua_id = mua_for_user(pool, user_id).await?;
+    let (category, name, description, price, duration_minutes) = validate(request)?;
+
+    sqlx::query_as::<_, ServiceOffering>(
+        r#"
+        UPDATE service_offerings
+        SET category = $3, name = $4, description = $5, price = $6, duration_minutes = $7, updated_at = NOW()
+        WHERE id = $1 AND mua_id = $2
+        RETURNING *
+        "#
+    )
+    .bind(offering_id)
+    .bind(mua_id)
+    .bind(&category)
+    .bind(&name)
+    .bind(&description)
+    .bind(&price)
+    .bind(duration_minutes)
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| anyhow::anyhow!("Service not found"))
+}
+
+pub async fn delete_offering(pool: &PgPool, user_id: Uuid, offering_id: Uuid) -> Result<()> {
+    let mua_id = mua_for_user(pool, user_id).await?;
+
+    let result = sqlx::query("DELETE FROM service_offerings WHERE id = $1 AND mua_id = $2")
+        .bind(offering_id)
+        .bind(mua_id)
+        .execute(pool)
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(anyhow::anyhow!("Service not found"));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,101 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use sqlx::types::BigDecimal;
+use std::env;
+use uuid::Uuid;
+
+#[derive(Debug, Clone)]
+pub struct RefundRequest {
+    pub booking_id: Uuid,
+    pub amount: BigDecimal,
+    pub currency: String,
+}
+
+/// Moves money back to customers
+#[async_trait]
+pub trait PaymentProvider: Send + Sync {
+    fn name(&self) -> &'static str;
+    /// Issue a refund, returning the provider's reference for it
+    async fn refund(&self, request: &RefundRequest) -> Result<String>;
+}
+
+/// Prints refunds instead of issuing them, the default for development
+pub struct LogPaymentProvider;
+
+#[async_trait]
+impl PaymentProvider for LogPaymentProvider {
+    fn name(&self) -> &'static str {
+        "log"
+    }
+
+    async fn refund(&self, request: &RefundRequest) -> Result<String> {
+        println!("💸 Refund of {} {} for booking {}", request.amount, request.currency, request.booking_id);
+        Ok(format!("log-{}", request.booking_id))
+    }
+}
+
+/// Posts refunds to an HTTP payment gateway as `{"booking_id": ..., "amount": ..., "currency": ...}`
+/// and expects `{"reference": ...}` back. The booking id doubles as the idempotency key, so
+/// retried jobs can't refund twice.
+pub struct HttpPaymentProvider {
+    client: reqwest::Client,
+    api_url: String,
+    api_token: String,
+}
+
+impl HttpPaymentProvider {
+    pub fn from_env() -> Result<Self> {
+        let api_url = env::var("PAYMENT_API_URL")
+            .map_err(|_| anyhow::anyhow!("PAYMENT_API_URL must be set for the http payment provider"))?;
+        let api_token = env::var("PAYMENT_API_TOKEN")
+            .map_err(|_| anyhow::anyhow!("PAYMENT_API_TOKEN must be set for the http payment provider"))?;
+
+        Ok(Self {
+            client: reqwest::Client::new(),
+            api_url,
+            api_token,
+        })
+    }
+}
+
+#[async_trait]
+impl PaymentProvider for HttpPaymentProvider {
+    fn name(&self) -> &'static str {
+        "http"
+    }
+
+    async fn refund(&self, request: &RefundRequest) -> Result<String> {
+        let response = self.client
+            .post(format!("{}/refunds", self.api_url.trim_end_matches('/')))
+            .bearer_auth(&self.api_token)
+            .header("Idempotency-Key", format!("refund-{}", request.booking_id))
+            .json(&serde_json::json!({
+                "booking_id": request.booking_id,
+                "amount": request.amount.to_string(),
+                "currency": request.currency,
+            }))
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(anyhow::anyhow!("Payment gateway returned {}", response.status()));
+        }
+
+        let body: serde_json::Value = response.json().await?;
+        body.get("reference")
+            .and_then(|reference| reference.as_str())
+            .map(|reference| reference.to_string())
+            .ok_or_else(|| anyhow::anyhow!("Payment gateway response is missing a refund reference"))
+    }
+}
+
+/// The provider named in `PAYMENT_PROVIDER` (log or http), defaults to log
+pub fn payment_provider_from_env() -> Result<Box<dyn PaymentProvider>> {
+    let provider = env::var("PAYMENT_PROVIDER").unwrap_or_else(|_| "log".to_string());
+
+    match provider.trim().to_lowercase().as_str() {
+        "log" => Ok(Box::new(LogPaymentProvider)),
+        "http" => Ok(Box::new(HttpPaymentProvider::from_env()?)),
+        other => Err(anyhow::anyhow!("Unknown payment provider: {}", other)),
+    }
+}
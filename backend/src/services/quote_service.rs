@@ -0,0 +1,355 @@
+use anyhow::Result;
+use chrono::Utc;
+use sqlx::{PgExecutor, PgPool, Postgres, QueryBuilder, Transaction, types::BigDecimal};
+use std::str::FromStr;
+use uuid::Uuid;
+
+use crate::models::{Booking, BookingQuote, BookingStatus, CreateQuoteRequest, Cursor, Page, PageQuery, QuoteStatus, OLDEST_FIRST};
+use super::availability_service;
+use super::booking_service;
+use super::job_queue::{self, Job};
+use super::notification_service;
+
+/// The offer currently waiting for an answer, if any
+pub async fn find_open_quote<'e, E: PgExecutor<'e>>(executor: E, booking_id: Uuid) -> Result<Option<BookingQuote>> {
+    let quote = sqlx::query_as::<_, BookingQuote>(
+        "SELECT * FROM booking_quotes WHERE booking_id = $1 AND status = 'open'"
+    )
+    .bind(booking_id)
+    .fetch_optional(executor)
+    .await?;
+
+    Ok(quote)
+}
+
+/// Close any open offer once the booking can no longer be agreed on
+pub async fn close_open_quotes<'e, E: PgExecutor<'e>>(executor: E, booking_id: Uuid) -> Result<()> {
+    sqlx::query("UPDATE booking_quotes SET status = 'closed', responded_at = NOW() WHERE booking_id = $1 AND status = 'open'")
+        .bind(booking_id)
+        .execute(executor)
+        .await?;
+
+    Ok(())
+}
+
+/// Make an offer on a pending booking.
+///
+/// The MUA opens the negotiation in reply to the customer's requested price; after
+/// that each side can counter the other's open offer. Each offer gives the other
+/// party a fresh response window.
+pub async fn create_quote(
+    pool: &PgPool,
+    auth_header: Option<String>,
+    booking_id: Uuid,
+    request: CreateQuoteRequest,
+) -> Result<BookingQuote> {
+    let user_id = super::user_service::get_user_id_from_auth_header(auth_header)?;
+    let (booking, mua_user_id) = booking_service::load_participant_booking(pool, booking_id, user_id).await?;
+
+    if booking.status != BookingStatus::Pending {
+        return Err(anyhow::anyhow!("Only pending bookings can be negotiated"));
+    }
+
+    let price = BigDecimal::from_str(&request.price)
+        .map_err(|_| anyhow::anyhow!("Invalid price format"))?;
+    let deposit_amount = request.deposit_amount.as_ref()
+        .map(|s| BigDecimal::from_str(s))
+        .transpose()
+        .map_err(|_| anyhow::anyhow!("Invalid deposit amount format"))?;
+
+    if price <= BigDecimal::default() {
+        return Err(anyhow::anyhow!("Invalid price: must be greater than zero"));
+    }
+    if deposit_amount.as_ref().is_some_and(|deposit| *deposit < BigDecimal::default() || *deposit > price) {
+        return Err(anyhow::anyhow!("Invalid deposit amount: must be between zero and the price"));
+    }
+
+    let mut tx = pool.begin().await?;
+
+    let open_quote = sqlx::query_as::<_, BookingQuote>(
+        "SELECT * FROM booking_quotes WHERE booking_id = $1 AND status = 'open' FOR UPDATE"
+    )
+    .bind(booking_id)
+    .fetch_optional(&mut tx)
+    .await?;
+
+    match &open_quote {
+        Some(quote) if quote.proposed_by == user_id => {
+            return Err(anyhow::anyhow!("Your last offer is still waiting for a response"));
+        }
+        Some(quote) => {
+            sqlx::query("UPDATE booking_quotes SET status = 'countered', responded_at = NOW() WHERE id = $1")
+                .bind(quote.id)
+                .execute(&mut tx)
+                .await?;
+        }
+        None if user_id != mua_user_id => {
+            return Err(anyhow::anyhow!("Unauthorized: the MUA has to quote first"));
+        }
+        None => {}
+    }
+
+    let quote = sqlx::query_as::<_, BookingQuote>(
+        r#"
+        INSERT INTO booking_quotes (booking_id, proposed_by, price, deposit_amount, notes, created_at)
+        VALUES ($1, $2, $3, $4, $5, NOW())
+        RETURNING *
+        "#
+    )
+    .bind(booking_id)
+    .bind(user_id)
+    .bind(&price)
+    .bind(&deposit_amount)
+    .bind(request.notes.as_deref())
+    .fetch_one(&mut tx)
+    .await?;
+
+    sqlx::query(
+        r#"
+        UPDATE bookings
+        SET response_deadline = LEAST(NOW() + make_interval(hours => $2), event_date), updated_at = NOW()
+        WHERE id = $1
+        "#
+    )
+    .bind(booking_id)
+    .bind(booking_service::default_response_hours() as i32)
+    .execute(&mut tx)
+    .await?;
+
+    job_queue::enqueue(&mut tx, &Job::QuoteProposed { quote_id: quote.id }, Utc::now()).await?;
+
+    tx.commit().await?;
+    Ok(quote)
+}
+
+/// A booking's offers in the order they were made
+pub async fn get_quotes(pool: &PgPool, auth_header: Option<String>, booking_id: Uuid, page_query: PageQuery) -> Result<Page<BookingQuote>> {
+    let user_id = super::user_service::get_user_id_from_auth_header(auth_header)?;
+    booking_service::load_participant_booking(pool, booking_id, user_id).await?;
+
+    let mut sql = QueryBuilder::<Postgres>::new("SELECT * FROM booking_quotes WHERE booking_id = ");
+    sql.push_bind(booking_id);
+    page_query.push_keyset(&mut sql, OLDEST_FIRST, "created_at", "id", false)?;
+
+    let quotes = sql.build_query_as::<BookingQuote>()
+        .fetch_all(pool)
+        .await?;
+
+    let total: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM booking_quotes WHERE booking_id = $1")
+        .bind(booking_id)
+        .fetch_one(pool)
+        .await?;
+
+    Ok(Page::keyset(quotes, total, page_query.limit(), |quote| Cursor::new(OLDEST_FIRST, quote.created_at, quote.id)))
+}
+
+/// Confirm a pending booking at the quote's price.
+///
+/// The line items keep their list prices; the difference the quote makes is recorded
+/// as an adjustment so items, travel fee and adjustment still add up to the price. A
+/// quote without a deposit keeps the booking's, so a deposit already paid still counts.
+/// Like a confirmation, it can't revive a request whose response deadline has passed.
+async fn confirm_at_quoted_price(tx: &mut Transaction<'_, Postgres>, booking_id: Uuid, quote: &BookingQuote) -> Result<Booking> {
+    let confirmed = sqlx::query_as::<_, Booking>(
+        r#"
+        UPDATE bookings
+        SET price = $2,
+            price_adjustment = $2 - travel_fee - COALESCE(
+                (SELECT SUM(li.person_count * li.unit_price) FROM booking_line_items li WHERE li.booking_id = bookings.id), 0
+            ),
+            deposit_amount = COALESCE($3, deposit_amount),
+            status = 'confirmed',
+            updated_at = NOW()
+        WHERE id = $1
+          AND status = 'pending'
+          AND (response_deadline IS NULL OR response_deadline > NOW())
+        RETURNING *
+        "#
+    )
+    .bind(booking_id)
+    .bind(&quote.price)
+    .bind(&quote.deposit_amount)
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    if let Some(confirmed) = confirmed {
+        return Ok(confirmed);
+    }
+
+    let is_pending: bool = sqlx::query_scalar("SELECT status = 'pending' FROM bookings WHERE id = $1")
+        .bind(booking_id)
+        .fetch_one(&mut *tx)
+        .await?;
+    if is_pending {
+        Err(anyhow::anyhow!("Invalid status: the response deadline for this booking has passed"))
+    } else {
+        Err(anyhow::anyhow!("Only pending bookings can be negotiated"))
+    }
+}
+
+/// Agree to the other party's open offer, which confirms the booking at that price
+pub async fn accept_quote(
+    pool: &PgPool,
+    auth_header: Option<String>,
+    booking_id: Uuid,
+    quote_id: Uuid,
+) -> Result<BookingQuote> {
+    let user_id = super::user_service::get_user_id_from_auth_header(auth_header)?;
+    let (booking, mua_user_id) = booking_service::load_participant_booking(pool, booking_id, user_id).await?;
+
+    let mut tx = pool.begin().await?;
+
+    // Takes the MUA lock before the booking row is touched, the same order confirmations use
+    availability_service::ensure_slot_available(
+        &mut tx,
+        booking.mua_id,
+        booking.event_date,
+        booking.duration_hours,
+        booking.event_coordinates(),
+        Some(booking_id)
+    ).await?;
+
+    let quote = sqlx::query_as::<_, BookingQuote>(
+        "SELECT * FROM booking_quotes WHERE id = $1 AND booking_id = $2 FOR UPDATE"
+    )
+    .bind(quote_id)
+    .bind(booking_id)
+    .fetch_optional(&mut tx)
+    .await?
+    .ok_or_else(|| anyhow::anyhow!("Quote not found"))?;
+
+    if quote.status != QuoteStatus::Open {
+        return Err(anyhow::anyhow!("This quote has already been answered"));
+    }
+    if quote.proposed_by == user_id {
+        return Err(anyhow::anyhow!("Unauthorized: the other party has to accept this quote"));
+    }
+
+    let confirmed = confirm_at_quoted_price(&mut tx, booking_id, &quote).await?;
+
+    let quote = sqlx::query_as::<_, BookingQuote>(
+        "UPDATE booking_quotes SET status = 'accepted', responded_at = NOW() WHERE id = $1 RETURNING *"
+    )
+    .bind(quote_id)
+    .fetch_one(&mut tx)
+    .await?;
+
+    booking_service::record_status_change(
+        &mut tx,
+        booking_id,
+        Some(BookingStatus::Pending),
+        BookingStatus::Confirmed,
+        Some(user_id),
+        Some("quote accepted")
+    ).await?;
+
+    // The customer accepting tells the MUA; the customer hears through the usual confirmation
+    if quote.proposed_by == mua_user_id {
+        job_queue::enqueue(&mut tx, &Job::QuoteAccepted { quote_id }, Utc::now()).await?;
+    }
+
+    tx.commit().await?;
+
+    if let Err(e) = notification_service::schedule_booking_confirmed(pool, &confirmed).await {
+        eprintln!("Failed to schedule booking confirmation notifications: {}", e);
+    }
+
+    Ok(quote)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn insert_user(pool: &PgPool, user_type: &str) -> Uuid {
+        sqlx::query_scalar(
+            "INSERT INTO users (email, password_hash, user_type, full_name) VALUES ($1, 'x', $2::user_type, 'Test') RETURNING id"
+        )
+        .bind(format!("{}@test.muaconnect.dev", Uuid::new_v4()))
+        .bind(user_type)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+    }
+
+    /// A pending booking whose customer already paid a 300000 deposit, and an open quote on it
+    async fn insert_quoted_booking(pool: &PgPool, quote_deposit: Option<&str>) -> (Uuid, BookingQuote) {
+        let customer_id = insert_user(pool, "customer").await;
+        let mua_user_id = insert_user(pool, "mua").await;
+        let mua_id: Uuid = sqlx::query_scalar("INSERT INTO mua_profiles (user_id, location) VALUES ($1, 'Jakarta') RETURNING id")
+            .bind(mua_user_id)
+            .fetch_one(pool)
+            .await
+            .unwrap();
+        let booking_id: Uuid = sqlx::query_scalar(
+            r#"
+            INSERT INTO bookings (customer_id, mua_id, service_type, event_date, event_location, duration_hours, price, status, deposit_amount, deposit_paid)
+            VALUES ($1, $2, 'Party Makeup', NOW() + INTERVAL '5 days', 'Jakarta', 2, 800000, 'pending', 300000, true)
+            RETURNING id
+            "#
+        )
+        .bind(customer_id)
+        .bind(mua_id)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+
+        let quote = sqlx::query_as::<_, BookingQuote>(
+            "INSERT INTO booking_quotes (booking_id, proposed_by, price, deposit_amount) VALUES ($1, $2, 900000, $3::numeric) RETURNING *"
+        )
+        .bind(booking_id)
+        .bind(mua_user_id)
+        .bind(quote_deposit)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+
+        (booking_id, quote)
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn a_quote_without_a_deposit_keeps_the_deposit_already_paid(pool: PgPool) {
+        let (booking_id, quote) = insert_quoted_booking(&pool, None).await;
+
+        let mut tx = pool.begin().await.unwrap();
+        let booking = confirm_at_quoted_price(&mut tx, booking_id, &quote).await.unwrap();
+        tx.commit().await.unwrap();
+
+        assert_eq!(booking.status, BookingStatus::Confirmed);
+        assert_eq!(booking.price, BigDecimal::from(900_000));
+        assert_eq!(booking.deposit_amount, Some(BigDecimal::from(300_000)));
+        assert_eq!(booking.amount_paid(), BigDecimal::from(300_000));
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn a_quote_with_a_deposit_sets_it(pool: PgPool) {
+        let (booking_id, quote) = insert_quoted_booking(&pool, Some("450000")).await;
+
+        let mut tx = pool.begin().await.unwrap();
+        let booking = confirm_at_quoted_price(&mut tx, booking_id, &quote).await.unwrap();
+
+        assert_eq!(booking.deposit_amount, Some(BigDecimal::from(450_000)));
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn a_quote_cannot_confirm_a_request_past_its_response_deadline(pool: PgPool) {
+        let (booking_id, quote) = insert_quoted_booking(&pool, None).await;
+        sqlx::query("UPDATE bookings SET response_deadline = NOW() - INTERVAL '1 minute' WHERE id = $1")
+            .bind(booking_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let mut tx = pool.begin().await.unwrap();
+        let error = confirm_at_quoted_price(&mut tx, booking_id, &quote).await.unwrap_err();
+        assert_eq!(error.to_string(), "Invalid status: the response deadline for this booking has passed");
+        drop(tx);
+
+        let status: BookingStatus = sqlx::query_scalar("SELECT status FROM bookings WHERE id = $1")
+            .bind(booking_id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(status, BookingStatus::Pending);
+    }
+}
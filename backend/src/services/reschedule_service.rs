@@ -0,0 +1,221 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Postgres, QueryBuilder};
+use uuid::Uuid;
+
+use crate::models::{Booking, BookingStatus, CreateRescheduleRequest, Cursor, Page, PageQuery, RescheduleRequest, RescheduleStatus, NEWEST_FIRST};
+use super::availability_service;
+use super::booking_service;
+use super::job_queue::{self, Job};
+use super::notification_service;
+
+/// Propose a new date (and optionally duration) for a pending or confirmed booking
+pub async fn create_reschedule_request(
+    pool: &PgPool,
+    auth_header: Option<String>,
+    booking_id: Uuid,
+    request: CreateRescheduleRequest,
+) -> Result<RescheduleRequest> {
+    let user_id = super::user_service::get_user_id_from_auth_header(auth_header)?;
+    let (booking, _) = booking_service::load_participant_booking(pool, booking_id, user_id).await?;
+
+    if !matches!(booking.status, BookingStatus::Pending | BookingStatus::Confirmed) {
+        return Err(anyhow::anyhow!("Only pending or confirmed bookings can be rescheduled"));
+    }
+
+    let proposed_event_date = DateTime::parse_from_rfc3339(&request.event_date)
+        .map_err(|_| anyhow::anyhow!("Invalid date format"))?
+        .with_timezone(&Utc);
+    let proposed_duration_hours = request.duration_hours.unwrap_or(booking.duration_hours);
+
+    if proposed_event_date <= Utc::now() {
+        return Err(anyhow::anyhow!("Invalid date: the new date must be in the future"));
+    }
+    if proposed_duration_hours <= 0 {
+        return Err(anyhow::anyhow!("Invalid duration: must be at least one hour"));
+    }
+    if proposed_event_date == booking.event_date && proposed_duration_hours == booking.duration_hours {
+        return Err(anyhow::anyhow!("Invalid date: the booking is already at that time"));
+    }
+
+    let mut tx = pool.begin().await?;
+
+    let reschedule = sqlx::query_as::<_, RescheduleRequest>(
+        r#"
+        INSERT INTO reschedule_requests (
+            booking_id, requested_by, proposed_event_date, proposed_duration_hours,
+            previous_event_date, previous_duration_hours, reason, created_at
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, NOW())
+        ON CONFLICT (booking_id) WHERE status = 'pending' DO NOTHING
+        RETURNING *
+        "#
+    )
+    .bind(booking_id)
+    .bind(user_id)
+    .bind(proposed_event_date)
+    .bind(proposed_duration_hours)
+    .bind(booking.event_date)
+    .bind(booking.duration_hours)
+    .bind(request.reason.as_deref())
+    .fetch_optional(&mut tx)
+    .await?
+    .ok_or_else(|| anyhow::anyhow!("A reschedule request is already pending for this booking"))?;
+
+    job_queue::enqueue(&mut tx, &Job::RescheduleRequested { reschedule_request_id: reschedule.id }, Utc::now()).await?;
+
+    tx.commit().await?;
+    Ok(reschedule)
+}
+
+/// A booking's reschedule requests, newest first
+pub async fn get_reschedule_requests(pool: &PgPool, auth_header: Option<String>, booking_id: Uuid, page_query: PageQuery) -> Result<Page<RescheduleRequest>> {
+    let user_id = super::user_service::get_user_id_from_auth_header(auth_header)?;
+    booking_service::load_participant_booking(pool, booking_id, user_id).await?;
+
+    let mut sql = QueryBuilder::<Postgres>::new("SELECT * FROM reschedule_requests WHERE booking_id = ");
+    sql.push_bind(booking_id);
+    page_query.push_keyset(&mut sql, NEWEST_FIRST, "created_at", "id", true)?;
+
+    let requests = sql.build_query_as::<RescheduleRequest>()
+        .fetch_all(pool)
+        .await?;
+
+    let total: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM reschedule_requests WHERE booking_id = $1")
+        .bind(booking_id)
+        .fetch_one(pool)
+        .await?;
+
+    Ok(Page::keyset(requests, total, page_query.limit(), |request| Cursor::new(NEWEST_FIRST, request.created_at, request.id)))
+}
+
+/// Move the booking to the proposed date if the MUA is still free then.
+///
+/// The booking keeps its id, price and payment state; only the date and duration change.
+pub async fn accept_reschedule_request(
+    pool: &PgPool,
+    auth_header: Option<String>,
+    booking_id: Uuid,
+    reschedule_request_id: Uuid,
+) -> Result<RescheduleRequest> {
+    let user_id = super::user_service::get_user_id_from_auth_header(auth_header)?;
+    let (booking, _) = booking_service::load_participant_booking(pool, booking_id, user_id).await?;
+
+    let mut tx = pool.begin().await?;
+
+    let reschedule = lock_pending_request(&mut tx, booking_id, reschedule_request_id).await?;
+    if reschedule.requested_by == user_id {
+        return Err(anyhow::anyhow!("Unauthorized: the other party has to accept this request"));
+    }
+    if reschedule.proposed_event_date <= Utc::now() {
+        return Err(anyhow::anyhow!("Invalid date: the proposed date has already passed"));
+    }
+
+    // Takes the MUA lock before the booking row is touched, the same order confirmations use
+    availability_service::ensure_slot_available(
+        &mut tx,
+        booking.mua_id,
+        reschedule.proposed_event_date,
+        reschedule.proposed_duration_hours,
+        booking.event_coordinates(),
+        Some(booking_id)
+    ).await?;
+
+    // Only move the booking if nothing else changed it since the request was made
+    let moved = sqlx::query_as::<_, Booking>(
+        r#"
+        UPDATE bookings
+        SET event_date = $2,
+            duration_hours = $3,
+            response_deadline = LEAST(response_deadline, $2),
+            updated_at = NOW()
+        WHERE id = $1 AND status IN ('pending', 'confirmed') AND event_date = $4 AND duration_hours = $5
+        RETURNING *
+        "#
+    )
+    .bind(booking_id)
+    .bind(reschedule.proposed_event_date)
+    .bind(reschedule.proposed_duration_hours)
+    .bind(reschedule.previous_event_date)
+    .bind(reschedule.previous_duration_hours)
+    .fetch_optional(&mut tx)
+    .await?
+    .ok_or_else(|| anyhow::anyhow!("The booking has changed since this request was made"))?;
+
+    let reschedule = respond(&mut tx, reschedule_request_id, user_id, RescheduleStatus::Accepted).await?;
+
+    if moved.status == BookingStatus::Confirmed {
+        notification_service::schedule_reminders(&mut tx, booking_id, moved.event_date).await?;
+    }
+    job_queue::enqueue(&mut tx, &Job::RescheduleResponded { reschedule_request_id }, Utc::now()).await?;
+    // The old time is free again
+    job_queue::enqueue(&mut tx, &Job::FavoriteOpenings { mua_id: booking.mua_id }, Utc::now()).await?;
+
+    tx.commit().await?;
+    Ok(reschedule)
+}
+
+/// Decline the other party's proposal, or withdraw your own
+pub async fn decline_reschedule_request(
+    pool: &PgPool,
+    auth_header: Option<String>,
+    booking_id: Uuid,
+    reschedule_request_id: Uuid,
+) -> Result<RescheduleRequest> {
+    let user_id = super::user_service::get_user_id_from_auth_header(auth_header)?;
+    booking_service::load_participant_booking(pool, booking_id, user_id).await?;
+
+    let mut tx = pool.begin().await?;
+
+    let reschedule = lock_pending_request(&mut tx, booking_id, reschedule_request_id).await?;
+    let status = if reschedule.requested_by == user_id {
+        RescheduleStatus::Withdrawn
+    } else {
+        RescheduleStatus::Declined
+    };
+
+    let reschedule = respond(&mut tx, reschedule_request_id, user_id, status).await?;
+    job_queue::enqueue(&mut tx, &Job::RescheduleResponded { reschedule_request_id }, Utc::now()).await?;
+
+    tx.commit().await?;
+    Ok(reschedule)
+}
+
+async fn lock_pending_request(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    booking_id: Uuid,
+    reschedule_request_id: Uuid,
+) -> Result<RescheduleRequest> {
+    let reschedule = sqlx::query_as::<_, RescheduleRequest>(
+        "SELECT * FROM reschedule_requests WHERE id = $1 AND booking_id = $2 FOR UPDATE"
+    )
+    .bind(reschedule_request_id)
+    .bind(booking_id)
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or_else(|| anyhow::anyhow!("Reschedule request not found"))?;
+
+    if reschedule.status != RescheduleStatus::Pending {
+        return Err(anyhow::anyhow!("This reschedule request has already been answered"));
+    }
+
+    Ok(reschedule)
+}
+
+async fn respond(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    reschedule_request_id: Uuid,
+    user_id: Uuid,
+    status: RescheduleStatus,
+) -> Result<RescheduleRequest> {
+    let reschedule = sqlx::query_as::<_, RescheduleRequest>(
+        "UPDATE reschedule_requests SET status = $2, responded_by = $3, responded_at = NOW() WHERE id = $1 RETURNING *"
+    )
+    .bind(reschedule_request_id)
+    .bind(status)
+    .bind(user_id)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    Ok(reschedule)
+}
@@ -0,0 +1,90 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use sqlx::{PgPool, Row};
+use uuid::Uuid;
+
+use crate::models::{CreateReviewRequest, Review};
+
+/// Leave a review for the other party of a completed booking while its review window is open
+pub async fn create_review(pool: &PgPool, auth_header: Option<String>, review_data: CreateReviewRequest) -> Result<Review> {
+    let reviewer_id = super::user_service::get_user_id_from_auth_header(auth_header)?;
+
+    if !(1..=5).contains(&review_data.rating) {
+        return Err(anyhow::anyhow!("Invalid rating: must be between 1 and 5"));
+    }
+
+    let booking = sqlx::query(
+        r#"
+        SELECT b.status::text as status, b.customer_id, b.review_window_closes_at, mp.user_id as mua_user_id
+        FROM bookings b
+        JOIN mua_profiles mp ON mp.id = b.mua_id
+        WHERE b.id = $1
+        "#
+    )
+    .bind(review_data.booking_id)
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| anyhow::anyhow!("Booking not found"))?;
+
+    let status: String = booking.get("status");
+    let customer_id: Uuid = booking.get("customer_id");
+    let mua_user_id: Uuid = booking.get("mua_user_id");
+    let review_window_closes_at: Option<DateTime<Utc>> = booking.get("review_window_closes_at");
+
+    let reviewee_id = if reviewer_id == customer_id {
+        mua_user_id
+    } else if reviewer_id == mua_user_id {
+        customer_id
+    } else {
+        return Err(anyhow::anyhow!("Unauthorized"));
+    };
+
+    if status != "completed" {
+        return Err(anyhow::anyhow!("Only completed bookings can be reviewed"));
+    }
+
+    if review_window_closes_at.is_none_or(|closes_at| closes_at <= Utc::now()) {
+        return Err(anyhow::anyhow!("The review window for this booking has closed"));
+    }
+
+    let mut tx = pool.begin().await?;
+
+    let review = sqlx::query_as::<_, Review>(
+        r#"
+        INSERT INTO reviews (booking_id, reviewer_id, reviewee_id, rating, comment, created_at)
+        VALUES ($1, $2, $3, $4, $5, NOW())
+        ON CONFLICT (booking_id, reviewer_id) DO NOTHING
+        RETURNING id, booking_id, reviewer_id, reviewee_id, rating, comment, created_at
+        "#
+    )
+    .bind(review_data.booking_id)
+    .bind(reviewer_id)
+    .bind(reviewee_id)
+    .bind(review_data.rating)
+    .bind(review_data.comment.as_deref())
+    .fetch_optional(&mut tx)
+    .await?
+    .ok_or_else(|| anyhow::anyhow!("You have already reviewed this booking"))?;
+
+    // Keep the denormalized rating on the MUA profile in step; a no-op when the customer is reviewed
+    sqlx::query(
+        r#"
+        UPDATE mua_profiles
+        SET average_rating = stats.average_rating,
+            total_reviews = stats.total_reviews,
+            updated_at = NOW()
+        FROM (
+            SELECT ROUND(AVG(rating)::numeric, 2) AS average_rating, COUNT(id)::int AS total_reviews
+            FROM reviews
+            WHERE reviewee_id = $1
+        ) stats
+        WHERE mua_profiles.user_id = $1
+        "#
+    )
+    .bind(reviewee_id)
+    .execute(&mut tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(review)
+}
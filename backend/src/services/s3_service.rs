@@ -1,16 +1,18 @@
 use aws_config::SdkConfig;
 use aws_sdk_s3::{Client, primitives::ByteStream, config::Region, types::ObjectCannedAcl, presigning::PresigningConfig};
 use aws_credential_types::Credentials;
+use async_trait::async_trait;
 use bytes::Bytes;
-use base64::{Engine as _, engine::general_purpose};
 use anyhow::Result;
 use std::env;
 use std::time::Duration;
-use uuid::Uuid;
+
+use super::object_storage::{ObjectMetadata, ObjectStorage};
 
 pub struct S3Service {
     client: Client,
     bucket_name: String,
+    s3_api_url: String,
 }
 
 impl S3Service {
@@ -65,88 +67,51 @@ impl S3Service {
         Ok(Self {
             client,
             bucket_name,
+            s3_api_url: s3_api_url.trim_end_matches('/').to_string(),
         })
     }
+}
 
-    pub async fn upload_image(
-        &self,
-        image_data: Bytes,
-        content_type: &str,
-        folder: &str,
-    ) -> Result<String> {
-        let file_extension = self.get_file_extension(content_type)?;
-        let file_name = format!("{}.{}", Uuid::new_v4(), file_extension);
-        let key = format!("{}/{}", folder, file_name);
-
+#[async_trait]
+impl ObjectStorage for S3Service {
+    async fn put_object(&self, key: &str, data: Bytes, content_type: &str) -> Result<String> {
         println!("DEBUG: S3 upload - bucket: {}, key: {}, content_type: {}", self.bucket_name, key, content_type);
 
-        let body = ByteStream::from(image_data);
-
         let result = self.client
             .put_object()
             .bucket(&self.bucket_name)
-            .key(&key)
-            .body(body)
+            .key(key)
+            .body(ByteStream::from(data))
             .content_type(content_type)
             .send()
             .await;
 
-        match result {
-            Ok(_) => {
-                println!("DEBUG: S3 upload successful");
-            }
-            Err(e) => {
-                println!("DEBUG: S3 upload failed with AWS error: {:?}", e);
-                return Err(anyhow::anyhow!("S3 upload error: {}", e));
-            }
+        if let Err(e) = result {
+            println!("DEBUG: S3 upload failed with AWS error: {:?}", e);
+            return Err(anyhow::anyhow!("S3 upload error: {}", e));
         }
 
-        // Construct the public URL
-        let s3_api_url = env::var("S3_API_URL")?;
-        let public_url = format!("{}/{}", s3_api_url, key);
-        println!("DEBUG: Constructed public URL: {}", public_url);
-
-        Ok(public_url)
+        Ok(self.public_url(key))
     }
 
-    pub async fn upload_base64_image(
-        &self,
-        base64_data: &str,
-        folder: &str,
-    ) -> Result<String> {
-        // Remove data URL prefix if present
-        let (mime_type, base64_content) = if base64_data.starts_with("data:") {
-            let parts: Vec<&str> = base64_data.split(',').collect();
-            if parts.len() != 2 {
-                return Err(anyhow::anyhow!("Invalid base64 data URL format"));
-            }
-
-            let mime_part = parts[0];
-            let mime_type = mime_part
-                .split(':')
-                .nth(1)
-                .and_then(|s| s.split(';').next())
-                .unwrap_or("image/jpeg");
-
-            (mime_type.to_string(), parts[1])
-        } else {
-            ("image/jpeg".to_string(), base64_data)
-        };
+    async fn get_object(&self, key: &str) -> Result<Bytes> {
+        let output = self.client
+            .get_object()
+            .bucket(&self.bucket_name)
+            .key(key)
+            .send()
+            .await
+            .map_err(|e| anyhow::anyhow!("S3 download error: {}", e))?;
 
-        let image_data = general_purpose::STANDARD
-            .decode(base64_content)
-            .map_err(|e| anyhow::anyhow!("Failed to decode base64: {}", e))?;
+        let data = output.body
+            .collect()
+            .await
+            .map_err(|e| anyhow::anyhow!("S3 download error: {}", e))?;
 
-        self.upload_image(Bytes::from(image_data), &mime_type, folder).await
+        Ok(data.into_bytes())
     }
 
-    pub async fn delete_image(&self, image_url: &str) -> Result<()> {
-        // Extract key from URL
-        let key = image_url
-            .split('/')
-            .last()
-            .ok_or_else(|| anyhow::anyhow!("Invalid image URL"))?;
-
+    async fn delete_object(&self, key: &str) -> Result<()> {
         self.client
             .delete_object()
             .bucket(&self.bucket_name)
@@ -157,131 +122,50 @@ impl S3Service {
         Ok(())
     }
 
-    fn get_file_extension(&self, content_type: &str) -> Result<&str> {
-        match content_type {
-            "image/jpeg" => Ok("jpg"),
-            "image/jpg" => Ok("jpg"),
-            "image/png" => Ok("png"),
-            "image/gif" => Ok("gif"),
-            "image/webp" => Ok("webp"),
-            _ => Err(anyhow::anyhow!("Unsupported image format: {}", content_type)),
-        }
-    }
+    async fn head_object(&self, key: &str) -> Result<Option<ObjectMetadata>> {
+        let result = self.client
+            .head_object()
+            .bucket(&self.bucket_name)
+            .key(key)
+            .send()
+            .await;
 
-    pub fn validate_image_base64(&self, base64_data: &str) -> Result<(String, Vec<u8>)> {
-        let (mime_type, base64_content) = if base64_data.starts_with("data:") {
-            let parts: Vec<&str> = base64_data.split(',').collect();
-            if parts.len() != 2 {
-                return Err(anyhow::anyhow!("Invalid base64 data URL format"));
+        match result {
+            Ok(output) => Ok(Some(ObjectMetadata {
+                size: output.content_length().max(0) as u64,
+                content_type: output.content_type().map(|ct| ct.to_string()),
+            })),
+            Err(e) => {
+                let e = e.into_service_error();
+                if e.is_not_found() {
+                    Ok(None)
+                } else {
+                    Err(anyhow::anyhow!("S3 head error: {}", e))
+                }
             }
-
-            let mime_part = parts[0];
-            let mime_type = mime_part
-                .split(':')
-                .nth(1)
-                .and_then(|s| s.split(';').next())
-                .unwrap_or("image/jpeg");
-
-            (mime_type.to_string(), parts[1])
-        } else {
-            ("image/jpeg".to_string(), base64_data)
-        };
-
-        // Validate MIME type
-        if !mime_type.starts_with("image/") {
-            return Err(anyhow::anyhow!("Invalid file type: {}. Only images are allowed.", mime_type));
         }
-
-        // Decode and validate file size (max 10MB)
-        let image_data = general_purpose::STANDARD
-            .decode(base64_content)
-            .map_err(|e| anyhow::anyhow!("Failed to decode base64: {}", e))?;
-
-        if image_data.len() > 10 * 1024 * 1024 {
-            return Err(anyhow::anyhow!("File too large. Maximum size is 10MB."));
-        }
-
-        Ok((mime_type, image_data))
     }
 
-    /// Generate a presigned URL for direct upload to S3
-    /// Valid for 1 hour (3600 seconds)
-    pub async fn get_presigned_upload_url(
-        &self,
-        file_name: &str,
-        content_type: &str,
-        folder: &str,
-    ) -> Result<(String, String)> {
-        let file_extension = self.get_file_extension(content_type)?;
-        let unique_file_name = format!("{}.{}", Uuid::new_v4(), file_extension);
-        let key = format!("{}/{}", folder, unique_file_name);
-
+    async fn presign_upload(&self, key: &str, content_type: &str, expires_in: Duration) -> Result<String> {
         println!("DEBUG: Generating presigned URL - bucket: {}, key: {}, content_type: {}",
                 self.bucket_name, key, content_type);
 
-        let presigned_config = PresigningConfig::expires_in(Duration::from_secs(3600)) // 1 hour
+        let presigned_config = PresigningConfig::expires_in(expires_in)
             .map_err(|e| anyhow::anyhow!("Failed to create presigning config: {}", e))?;
 
         let presigned_request = self.client
             .put_object()
             .bucket(&self.bucket_name)
-            .key(&key)
+            .key(key)
             .content_type(content_type)
             .presigned(presigned_config)
             .await
             .map_err(|e| anyhow::anyhow!("Failed to generate presigned URL: {}", e))?;
 
-        let presigned_url = presigned_request.uri().to_string();
-
-        // Construct the final public URL that will be accessible after upload
-        let s3_api_url = env::var("S3_API_URL")?;
-        let public_url = format!("{}/{}", s3_api_url, key);
-
-        println!("DEBUG: Generated presigned URL and public URL");
-        println!("DEBUG: Public URL will be: {}", public_url);
-
-        Ok((presigned_url, public_url))
+        Ok(presigned_request.uri().to_string())
     }
 
-    /// Generate a presigned URL for direct upload to S3 with custom filename
-    /// Valid for 1 hour (3600 seconds)
-    pub async fn get_presigned_upload_url_with_filename(
-        &self,
-        file_name: &str,
-        content_type: &str,
-        folder: &str,
-    ) -> Result<(String, String)> {
-        let file_extension = self.get_file_extension(content_type)?;
-        let sanitized_filename = format!("{}.{}",
-            file_name.trim_end_matches(&format!(".{}", file_extension)),
-            file_extension
-        );
-        let key = format!("{}/{}", folder, sanitized_filename);
-
-        println!("DEBUG: Generating presigned URL with custom filename - bucket: {}, key: {}, content_type: {}",
-                self.bucket_name, key, content_type);
-
-        let presigned_config = PresigningConfig::expires_in(Duration::from_secs(3600)) // 1 hour
-            .map_err(|e| anyhow::anyhow!("Failed to create presigning config: {}", e))?;
-
-        let presigned_request = self.client
-            .put_object()
-            .bucket(&self.bucket_name)
-            .key(&key)
-            .content_type(content_type)
-            .presigned(presigned_config)
-            .await
-            .map_err(|e| anyhow::anyhow!("Failed to generate presigned URL: {}", e))?;
-
-        let presigned_url = presigned_request.uri().to_string();
-
-        // Construct the final public URL that will be accessible after upload
-        let s3_api_url = env::var("S3_API_URL")?;
-        let public_url = format!("{}/{}", s3_api_url, key);
-
-        println!("DEBUG: Generated presigned URL and public URL");
-        println!("DEBUG: Public URL will be: {}", public_url);
-
-        Ok((presigned_url, public_url))
+    fn public_url(&self, key: &str) -> String {
+        format!("{}/{}", self.s3_api_url, key)
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,103 @@
+use anyhow::Result;
+use sqlx::{PgPool, Postgres, Row, Transaction};
+use uuid::Uuid;
+
+/// A pending delete picked up by the storage deletion worker
+pub struct StorageDeletion {
+    pub id: Uuid,
+    pub object_url: String,
+    pub attempts: i32,
+}
+
+/// Queue the object behind `object_url` for deletion.
+///
+/// Must run in the transaction that removes the last reference to the URL, so the
+/// object is only deleted if that change commits. URLs not served by our storage
+/// (e.g. placeholder images) are dropped by the worker.
+pub async fn enqueue_deletion(tx: &mut Transaction<'_, Postgres>, object_url: &str) -> Result<()> {
+    sqlx::query("INSERT INTO storage_deletions (object_url, created_at) VALUES ($1, NOW())")
+        .bind(object_url)
+        .execute(&mut *tx)
+        .await?;
+
+    Ok(())
+}
+
+/// Queue several objects at once when there's no surrounding transaction to join,
+/// e.g. cleaning up after a failed request
+pub async fn enqueue_deletions(pool: &PgPool, object_urls: &[&str]) -> Result<()> {
+    let mut tx = pool.begin().await?;
+    for object_url in object_urls {
+        enqueue_deletion(&mut tx, object_url).await?;
+    }
+    tx.commit().await?;
+
+    Ok(())
+}
+
+/// Lock a batch of due deletions; other workers skip rows this transaction holds
+pub async fn lock_due_deletions(tx: &mut Transaction<'_, Postgres>, limit: i64) -> Result<Vec<StorageDeletion>> {
+    let rows = sqlx::query(
+        r#"
+        SELECT id, object_url, attempts FROM storage_deletions
+        WHERE next_attempt_at <= NOW()
+        ORDER BY next_attempt_at
+        LIMIT $1
+        FOR UPDATE SKIP LOCKED
+        "#
+    )
+    .bind(limit)
+    .fetch_all(&mut *tx)
+    .await?;
+
+    Ok(rows.into_iter().map(|row| StorageDeletion {
+        id: row.get("id"),
+        object_url: row.get("object_url"),
+        attempts: row.get("attempts"),
+    }).collect())
+}
+
+/// Whether anything still points at the URL, e.g. a profile picture reusing a portfolio image
+pub async fn is_still_referenced(tx: &mut Transaction<'_, Postgres>, object_url: &str) -> Result<bool> {
+    let referenced: bool = sqlx::query_scalar(
+        r#"
+        SELECT EXISTS (SELECT 1 FROM portfolio_items WHERE $1 IN (image_url, thumbnail_url, medium_url))
+            OR EXISTS (SELECT 1 FROM users WHERE profile_picture_url = $1)
+        "#
+    )
+    .bind(object_url)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    Ok(referenced)
+}
+
+pub async fn complete_deletion(tx: &mut Transaction<'_, Postgres>, id: Uuid) -> Result<()> {
+    sqlx::query("DELETE FROM storage_deletions WHERE id = $1")
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+
+    Ok(())
+}
+
+/// Record a failed attempt and back off exponentially, capped at a day
+pub async fn fail_deletion(tx: &mut Transaction<'_, Postgres>, id: Uuid, attempts: i32, error: &str) -> Result<()> {
+    let backoff_minutes = 2_i64.saturating_pow(attempts.clamp(0, 11) as u32).min(24 * 60);
+
+    sqlx::query(
+        r#"
+        UPDATE storage_deletions
+        SET attempts = attempts + 1, last_error = $2, next_attempt_at = NOW() + make_interval(mins => $3)
+        WHERE id = $1
+        "#
+    )
+    .bind(id)
+    .bind(error)
+    .bind(backoff_minutes as i32)
+    .execute(&mut *tx)
+    .await?;
+
+    Ok(())
+}
+
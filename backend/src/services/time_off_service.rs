@@ -0,0 +1,195 @@
+use anyhow::Result;
+use chrono::{DateTime, NaiveDate, Utc};
+use chrono_tz::Tz;
+use sqlx::{PgPool, Row};
+use uuid::Uuid;
+
+use crate::models::{CreateTimeOffRequest, Page, PageNumberQuery, TimeOff, TimeOffConflict, TimeOffCreated, MAX_TIME_OFF_REASON_LENGTH};
+use super::timezone_service;
+
+/// The instants time off from `start_date` through `end_date` covers in `timezone`
+pub fn period_bounds(start_date: NaiveDate, end_date: NaiveDate, timezone: Tz) -> (DateTime<Utc>, DateTime<Utc>) {
+    let start = timezone_service::start_of_day(start_date, timezone);
+    let end = end_date.succ_opt()
+        .map(|day_after| timezone_service::start_of_day(day_after, timezone))
+        .unwrap_or(DateTime::<Utc>::MAX_UTC);
+
+    (start, end)
+}
+
+async fn mua_for_user(pool: &PgPool, user_id: Uuid) -> Result<(Uuid, Tz)> {
+    let row = sqlx::query("SELECT id, timezone FROM mua_profiles WHERE user_id = $1")
+        .bind(user_id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("MUA profile not found"))?;
+
+    Ok((row.get("id"), timezone_service::parse_timezone(row.get("timezone"))?))
+}
+
+fn parse_date(value: &str) -> Result<NaiveDate> {
+    NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d")
+        .map_err(|_| anyhow::anyhow!("Invalid date format: '{}'. Expected YYYY-MM-DD", value))
+}
+
+/// Time off that hasn't ended yet, soonest first
+pub async fn get_time_off(pool: &PgPool, user_id: Uuid, page_query: PageNumberQuery) -> Result<Page<TimeOff>> {
+    let (mua_id, timezone) = mua_for_user(pool, user_id).await?;
+    let today = Utc::now().with_timezone(&timezone).date_naive();
+
+    let time_off = sqlx::query_as::<_, TimeOff>(
+        "SELECT * FROM time_off WHERE mua_id = $1 AND end_date >= $2 ORDER BY start_date, end_date, id LIMIT $3 OFFSET $4"
+    )
+    .bind(mua_id)
+    .bind(today)
+    .bind(page_query.limit())
+    .bind(page_query.offset())
+    .fetch_all(pool)
+    .await?;
+
+    let total: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM time_off WHERE mua_id = $1 AND end_date >= $2")
+        .bind(mua_id)
+        .bind(today)
+        .fetch_one(pool)
+        .await?;
+
+    Ok(Page::numbered(time_off, total, page_query.page(), page_query.limit()))
+}
+
+/// Block out whole days. Confirmed bookings inside the range aren't touched,
+/// they're listed so the MUA can move or cancel them.
+pub async fn create_time_off(pool: &PgPool, user_id: Uuid, request: CreateTimeOffRequest) -> Result<TimeOffCreated> {
+    let (mua_id, timezone) = mua_for_user(pool, user_id).await?;
+
+    let start_date = parse_date(&request.start_date)?;
+    let end_date = parse_date(&request.end_date)?;
+    if end_date < start_date {
+        return Err(anyhow::anyhow!("Invalid time off: end_date is before start_date"));
+    }
+    if end_date < Utc::now().with_timezone(&timezone).date_naive() {
+        return Err(anyhow::anyhow!("Invalid time off: it has already ended"));
+    }
+
+    let reason = request.reason
+        .map(|reason| reason.trim().to_string())
+        .filter(|reason| !reason.is_empty());
+    if reason.as_ref().is_some_and(|reason| reason.chars().count() > MAX_TIME_OFF_REASON_LENGTH) {
+        return Err(anyhow::anyhow!("Invalid time off: reason is longer than {} characters", MAX_TIME_OFF_REASON_LENGTH));
+    }
+
+    let time_off = sqlx::query_as::<_, TimeOff>(
+        r#"
+        INSERT INTO time_off (mua_id, start_date, end_date, reason, created_at)
+        VALUES ($1, $2, $3, $4, NOW())
+        RETURNING *
+        "#
+    )
+    .bind(mua_id)
+    .bind(start_date)
+    .bind(end_date)
+    .bind(&reason)
+    .fetch_one(pool)
+    .await?;
+
+    let (starts_at, ends_at) = period_bounds(start_date, end_date, timezone);
+    let conflicting_bookings = sqlx::query(
+        r#"
+        SELECT b.id, u.full_name AS customer_name, b.service_type, b.event_date, b.duration_hours
+        FROM bookings b
+        JOIN users u ON u.id = b.customer_id
+        WHERE b.mua_id = $1
+          AND b.status = 'confirmed'
+          AND b.event_date < $3
+          AND b.event_date + make_interval(hours => b.duration_hours) > $2
+        ORDER BY b.event_date
+        "#
+    )
+    .bind(mua_id)
+    .bind(starts_at)
+    .bind(ends_at)
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|row| TimeOffConflict {
+        booking_id: row.get("id"),
+        customer_name: row.get("customer_name"),
+        service_type: row.get("service_type"),
+        event_date: timezone_service::local_time(row.get("event_date"), timezone),
+        duration_hours: row.get("duration_hours"),
+    })
+    .collect();
+
+    Ok(TimeOffCreated { time_off, conflicting_bookings })
+}
+
+pub async fn delete_time_off(pool: &PgPool, user_id: Uuid, time_off_id: Uuid) -> Result<()> {
+    let (mua_id, _) = mua_for_user(pool, user_id).await?;
+
+    let result = sqlx::query("DELETE FROM time_off WHERE id = $1 AND mua_id = $2")
+        .bind(time_off_id)
+        .bind(mua_id)
+        .execute(pool)
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(anyhow::anyhow!("Time off not found"));
+    }
+
+    if let Err(e) = super::notification_service::schedule_favorite_openings(pool, mua_id).await {
+        eprintln!("Failed to schedule favorite openings notifications: {}", e);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(value: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(value, "%Y-%m-%d").unwrap()
+    }
+
+    fn utc(value: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(value).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn covers_whole_local_days_through_the_end_date() {
+        let (start, end) = period_bounds(date("2026-06-10"), date("2026-06-12"), chrono_tz::Asia::Jakarta);
+
+        assert_eq!(start, utc("2026-06-09T17:00:00Z"));
+        assert_eq!(end, utc("2026-06-12T17:00:00Z"));
+    }
+
+    #[test]
+    fn single_day_follows_the_mua_timezone() {
+        let (start, end) = period_bounds(date("2026-06-10"), date("2026-06-10"), chrono_tz::Asia::Jayapura);
+
+        assert_eq!(start, utc("2026-06-09T15:00:00Z"));
+        assert_eq!(end - start, chrono::Duration::hours(24));
+    }
+
+    #[test]
+    fn days_shortened_by_daylight_saving_stay_whole() {
+        let (start, end) = period_bounds(date("2026-03-29"), date("2026-03-29"), chrono_tz::Europe::Berlin);
+
+        assert_eq!(start, utc("2026-03-28T23:00:00Z"));
+        assert_eq!(end, utc("2026-03-29T22:00:00Z"));
+    }
+
+    #[test]
+    fn day_without_a_midnight_starts_when_the_clocks_resume() {
+        // Havana skips from 00:00 to 01:00 when daylight saving starts
+        let (start, end) = period_bounds(date("2026-03-08"), date("2026-03-08"), chrono_tz::America::Havana);
+
+        assert_eq!(start, utc("2026-03-08T05:00:00Z"));
+        assert_eq!(end, utc("2026-03-09T04:00:00Z"));
+    }
+
+    #[test]
+    fn last_representable_day_ends_at_the_end_of_time() {
+        let (_, end) = period_bounds(NaiveDate::MAX, NaiveDate::MAX, chrono_tz::UTC);
+        assert_eq!(end, DateTime::<Utc>::MAX_UTC);
+    }
+}
@@ -0,0 +1,82 @@
+use anyhow::Result;
+use chrono::{DateTime, Duration, FixedOffset, NaiveDate, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use sqlx::{PgExecutor, PgPool, Row};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use crate::models::MuaTimezone;
+
+/// Zone for MUAs that never picked one, matching the column default
+pub const DEFAULT_TIMEZONE: Tz = chrono_tz::Asia::Jakarta;
+
+/// Parse an IANA zone name such as `Asia/Makassar`
+pub fn parse_timezone(name: &str) -> Result<Tz> {
+    name.trim().parse::<Tz>()
+        .map_err(|_| anyhow::anyhow!("Invalid timezone: {} is not an IANA timezone name", name))
+}
+
+/// `time` on the wall clock of `timezone`, with its UTC offset spelled out
+pub fn local_time(time: DateTime<Utc>, timezone: Tz) -> DateTime<FixedOffset> {
+    time.with_timezone(&timezone).fixed_offset()
+}
+
+/// The instant `date` begins in `timezone`; if a DST jump skips midnight, the first hour after it
+pub fn start_of_day(date: NaiveDate, timezone: Tz) -> DateTime<Utc> {
+    let midnight = date.and_time(NaiveTime::MIN);
+
+    timezone.from_local_datetime(&midnight).earliest()
+        .or_else(|| timezone.from_local_datetime(&(midnight + Duration::hours(1))).earliest())
+        .map(|start| start.with_timezone(&Utc))
+        .unwrap_or_else(|| midnight.and_utc())
+}
+
+pub async fn timezone_for_mua<'e, E: PgExecutor<'e>>(executor: E, mua_id: Uuid) -> Result<Tz> {
+    let timezone: String = sqlx::query_scalar("SELECT timezone FROM mua_profiles WHERE id = $1")
+        .bind(mua_id)
+        .fetch_optional(executor)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("MUA profile not found"))?;
+
+    parse_timezone(&timezone)
+}
+
+/// Zones of the given MUAs, for rendering several bookings at once
+pub async fn timezones_for_muas(pool: &PgPool, mua_ids: &[Uuid]) -> Result<HashMap<Uuid, Tz>> {
+    let rows = sqlx::query("SELECT id, timezone FROM mua_profiles WHERE id = ANY($1)")
+        .bind(mua_ids)
+        .fetch_all(pool)
+        .await?;
+
+    rows.into_iter()
+        .map(|row| Ok((row.get("id"), parse_timezone(row.get("timezone"))?)))
+        .collect()
+}
+
+pub async fn get_timezone(pool: &PgPool, user_id: Uuid) -> Result<MuaTimezone> {
+    let timezone: String = sqlx::query_scalar("SELECT timezone FROM mua_profiles WHERE user_id = $1")
+        .bind(user_id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("MUA profile not found"))?;
+
+    Ok(MuaTimezone::new(parse_timezone(&timezone)?))
+}
+
+/// Existing slots keep their wall-clock times and are read in the new zone from now on;
+/// bookings stay at the instant they were made for
+pub async fn update_timezone(pool: &PgPool, user_id: Uuid, timezone: &str) -> Result<MuaTimezone> {
+    let timezone = parse_timezone(timezone)?;
+
+    let result = sqlx::query("UPDATE mua_profiles SET timezone = $1, updated_at = NOW() WHERE user_id = $2")
+        .bind(timezone.name())
+        .bind(user_id)
+        .execute(pool)
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(anyhow::anyhow!("MUA profile not found"));
+    }
+
+    Ok(MuaTimezone::new(timezone))
+}
@@ -7,7 +7,7 @@ use sqlx::PgPool;
 use crate::models::{
     CreateUserRequest, LoginRequest, AuthResponse, UserResponse,
     SearchMuasRequest, MuaProfileResponse, CreateMuaProfileRequest, CreateBookingRequest,
-    BookingResponse, UpdateBookingStatusRequest
+    BookingResponse, UpdateBookingStatusRequest, Page, BookingListQuery, BookingDetail
 };
 use crate::repository::traits::{UserRepository, MuaRepository, BookingRepository};
 
@@ -26,7 +26,7 @@ pub trait UserService: Send + Sync {
 
 #[async_trait]
 pub trait MuaService: Send + Sync {
-    async fn search_muas(&self, pool: &PgPool, params: SearchMuasRequest) -> Result<Vec<MuaProfileResponse>>;
+    async fn search_muas(&self, pool: &PgPool, params: SearchMuasRequest) -> Result<Page<MuaProfileResponse>>;
     async fn get_mua_by_id(&self, pool: &PgPool, mua_id: Uuid) -> Result<MuaProfileResponse>;
     async fn create_profile(&self, pool: &PgPool, auth_header: Option<String>, profile_data: CreateMuaProfileRequest) -> Result<MuaProfileResponse>;
     async fn create_portfolio_item(&self, pool: &PgPool, auth_header: Option<String>, portfolio_data: Value) -> Result<Value>;
@@ -41,7 +41,9 @@ pub trait BookingService: Send + Sync {
         booking_data: CreateBookingRequest,
     ) -> Result<BookingResponse>;
 
-    async fn get_user_bookings(&self, pool: &PgPool, auth_header: Option<String>) -> Result<Vec<BookingResponse>>;
+    async fn get_user_bookings(&self, pool: &PgPool, auth_header: Option<String>, query: BookingListQuery) -> Result<Page<BookingResponse>>;
+
+    async fn get_booking_detail(&self, pool: &PgPool, auth_header: Option<String>, booking_id: Uuid) -> Result<BookingDetail>;
 
     async fn update_booking_status(
         &self,
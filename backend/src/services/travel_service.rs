@@ -0,0 +1,151 @@
+use anyhow::Result;
+use sqlx::{PgPool, Row, types::BigDecimal};
+use std::str::FromStr;
+use uuid::Uuid;
+
+use crate::models::{TravelFee, TravelPricing, UpdateTravelPricingRequest};
+
+const EARTH_RADIUS_KM: f64 = 6371.0;
+
+/// Great-circle distance between two coordinates in km
+pub fn distance_km(from_latitude: f64, from_longitude: f64, to_latitude: f64, to_longitude: f64) -> f64 {
+    let delta_latitude = (to_latitude - from_latitude).to_radians();
+    let delta_longitude = (to_longitude - from_longitude).to_radians();
+
+    let a = (delta_latitude / 2.0).sin().powi(2)
+        + from_latitude.to_radians().cos() * to_latitude.to_radians().cos() * (delta_longitude / 2.0).sin().powi(2);
+
+    2.0 * EARTH_RADIUS_KM * a.sqrt().asin()
+}
+
+pub async fn travel_pricing_for_mua(pool: &PgPool, mua_id: Uuid) -> Result<TravelPricing> {
+    let row = sqlx::query(
+        "SELECT travel_free_radius_km, travel_fee_per_km, travel_max_distance_km FROM mua_profiles WHERE id = $1"
+    )
+    .bind(mua_id)
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| anyhow::anyhow!("MUA profile not found"))?;
+
+    Ok(TravelPricing {
+        free_radius_km: row.get("travel_free_radius_km"),
+        fee_per_km: row.get("travel_fee_per_km"),
+        max_distance_km: row.get("travel_max_distance_km"),
+    })
+}
+
+/// Validate and store a MUA's travel pricing; applies to bookings made from now on
+pub async fn update_travel_pricing(pool: &PgPool, user_id: Uuid, request: UpdateTravelPricingRequest) -> Result<TravelPricing> {
+    let pricing = TravelPricing {
+        free_radius_km: request.free_radius_km,
+        fee_per_km: BigDecimal::from_str(&request.fee_per_km)
+            .map_err(|_| anyhow::anyhow!("Invalid travel pricing: fee_per_km must be a number"))?
+            .round(2),
+        max_distance_km: request.max_distance_km,
+    };
+    pricing.validate()?;
+
+    let result = sqlx::query(
+        r#"
+        UPDATE mua_profiles
+        SET travel_free_radius_km = $1, travel_fee_per_km = $2, travel_max_distance_km = $3, updated_at = NOW()
+        WHERE user_id = $4
+        "#
+    )
+    .bind(pricing.free_radius_km)
+    .bind(&pricing.fee_per_km)
+    .bind(pricing.max_distance_km)
+    .bind(user_id)
+    .execute(pool)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(anyhow::anyhow!("MUA profile not found"));
+    }
+
+    Ok(pricing)
+}
+
+/// Work out the travel fee for an event at the given coordinates.
+///
+/// Without coordinates on both sides there's nothing to measure, so travel is free;
+/// the customer has to give them when the MUA charges for travel or limits the distance.
+pub async fn travel_fee_for_booking(
+    pool: &PgPool,
+    mua_id: Uuid,
+    event_latitude: Option<f64>,
+    event_longitude: Option<f64>,
+) -> Result<TravelFee> {
+    let pricing = travel_pricing_for_mua(pool, mua_id).await?;
+
+    let event_coordinates = match (event_latitude, event_longitude) {
+        (Some(latitude), Some(longitude)) => {
+            if !(-90.0..=90.0).contains(&latitude) || !(-180.0..=180.0).contains(&longitude) {
+                return Err(anyhow::anyhow!("Invalid event coordinates"));
+            }
+            Some((latitude, longitude))
+        }
+        (None, None) => None,
+        _ => return Err(anyhow::anyhow!("Invalid event coordinates: both latitude and longitude are required")),
+    };
+
+    let mua_location = sqlx::query(
+        "SELECT latitude::float8 AS latitude, longitude::float8 AS longitude FROM mua_profiles WHERE id = $1"
+    )
+    .bind(mua_id)
+    .fetch_one(pool)
+    .await?;
+    let mua_coordinates = match (
+        mua_location.get::<Option<f64>, _>("latitude"),
+        mua_location.get::<Option<f64>, _>("longitude"),
+    ) {
+        (Some(latitude), Some(longitude)) => Some((latitude, longitude)),
+        _ => None,
+    };
+
+    let Some((event_latitude, event_longitude)) = event_coordinates else {
+        if pricing.needs_event_location() && mua_coordinates.is_some() {
+            return Err(anyhow::anyhow!("Invalid event coordinates: this MUA needs the event location to price travel"));
+        }
+        return Ok(TravelFee::default());
+    };
+
+    let Some((mua_latitude, mua_longitude)) = mua_coordinates else {
+        return Ok(TravelFee::default());
+    };
+
+    let distance = distance_km(mua_latitude, mua_longitude, event_latitude, event_longitude);
+    let fee = pricing.fee_for(distance)?;
+
+    Ok(TravelFee {
+        distance_km: Some((distance * 100.0).round() / 100.0),
+        fee,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const JAKARTA: (f64, f64) = (-6.2088, 106.8456);
+    const BANDUNG: (f64, f64) = (-6.9175, 107.6191);
+    const DENPASAR: (f64, f64) = (-8.6705, 115.2126);
+
+    fn distance(from: (f64, f64), to: (f64, f64)) -> f64 {
+        distance_km(from.0, from.1, to.0, to.1)
+    }
+
+    #[test]
+    fn measures_great_circle_distances() {
+        assert!((distance(JAKARTA, BANDUNG) - 116.2).abs() < 0.5, "{}", distance(JAKARTA, BANDUNG));
+        assert!((distance(JAKARTA, DENPASAR) - 962.2).abs() < 0.5, "{}", distance(JAKARTA, DENPASAR));
+        // One degree of longitude on the equator
+        assert!((distance((0.0, 0.0), (0.0, 1.0)) - 111.19).abs() < 0.01);
+    }
+
+    #[test]
+    fn distance_is_zero_at_the_same_place_and_symmetric() {
+        assert_eq!(distance(JAKARTA, JAKARTA), 0.0);
+        assert!((distance(JAKARTA, BANDUNG) - distance(BANDUNG, JAKARTA)).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,178 @@
+use anyhow::Result;
+use sqlx::{PgPool, Postgres, Row, Transaction};
+use uuid::Uuid;
+
+use super::image_processing::{self, ImageVariants};
+use super::object_storage::{self, ObjectStorage, MAX_IMAGE_SIZE};
+use super::storage_outbox;
+
+/// Record a presigned upload so it can be verified on attach and swept if never used
+pub async fn create_upload_session(pool: &PgPool, user_id: Uuid, object_key: &str, content_type: &str) -> Result<()> {
+    sqlx::query(
+        "INSERT INTO upload_sessions (object_key, user_id, content_type, created_at) VALUES ($1, $2, $3, NOW())"
+    )
+    .bind(object_key)
+    .bind(user_id)
+    .bind(content_type)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Verify that `image_url` points at an upload this user finished, and claim it.
+///
+/// The session is marked attached before the caller inserts the portfolio item so
+/// the same upload can't be used twice; call `release_upload` if that insert fails.
+pub async fn claim_upload(pool: &PgPool, storage: &dyn ObjectStorage, user_id: Uuid, image_url: &str) -> Result<String> {
+    let object_key = storage.key_from_url(image_url)
+        .ok_or_else(|| anyhow::anyhow!("Image must be uploaded through the upload endpoint"))?;
+
+    let session = sqlx::query(
+        "SELECT content_type FROM upload_sessions WHERE object_key = $1 AND user_id = $2 AND attached_at IS NULL"
+    )
+    .bind(&object_key)
+    .bind(user_id)
+    .fetch_optional(pool)
+    .await?
+    .ok_or_else(|| anyhow::anyhow!("Upload not found or already used"))?;
+
+    let expected_content_type: String = session.get("content_type");
+
+    let metadata = storage.head_object(&object_key).await?
+        .ok_or_else(|| anyhow::anyhow!("Uploaded image not found, the upload may not have finished"))?;
+
+    if metadata.size == 0 || metadata.size > MAX_IMAGE_SIZE as u64 {
+        return Err(anyhow::anyhow!("Invalid image size. Maximum size is 10MB."));
+    }
+
+    // Compare by extension so image/jpg and image/jpeg count as the same type
+    let actual_content_type = metadata.content_type.unwrap_or_else(|| "unrecognized".to_string());
+    let matches_session = match (
+        object_storage::file_extension(&expected_content_type),
+        object_storage::file_extension(&actual_content_type),
+    ) {
+        (Ok(expected), Ok(actual)) => expected == actual,
+        _ => false,
+    };
+
+    if !matches_session {
+        return Err(anyhow::anyhow!("Invalid file type: {}. Only images are allowed.", actual_content_type));
+    }
+
+    let result = sqlx::query(
+        "UPDATE upload_sessions SET attached_at = NOW() WHERE object_key = $1 AND user_id = $2 AND attached_at IS NULL"
+    )
+    .bind(&object_key)
+    .bind(user_id)
+    .execute(pool)
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(anyhow::anyhow!("Upload not found or already used"));
+    }
+
+    Ok(object_key)
+}
+
+/// Claim a presigned portfolio upload and turn it into processed WebP variants.
+///
+/// On success the caller stores the variants and then calls `finish_upload`,
+/// or `discard_portfolio_upload` if it couldn't.
+pub async fn attach_portfolio_upload(pool: &PgPool, storage: &dyn ObjectStorage, user_id: Uuid, image_url: &str) -> Result<(String, ImageVariants)> {
+    let object_key = claim_upload(pool, storage, user_id, image_url).await?;
+
+    let processed = async {
+        let data = storage.get_object(&object_key).await?;
+        image_processing::store_portfolio_variants(storage, &format!("portfolio/{}", user_id), data.to_vec()).await
+    }
+    .await;
+
+    match processed {
+        Ok(variants) => Ok((object_key, variants)),
+        Err(e) => {
+            release_upload(pool, &object_key).await?;
+            Err(e)
+        }
+    }
+}
+
+/// Claim a presigned avatar upload and store it as a processed WebP, returning its URL.
+///
+/// On success the caller points the user at the avatar and then calls `finish_upload`,
+/// or `discard_avatar_upload` if it couldn't.
+pub async fn attach_avatar_upload(pool: &PgPool, storage: &dyn ObjectStorage, user_id: Uuid, image_url: &str) -> Result<(String, String)> {
+    let object_key = claim_upload(pool, storage, user_id, image_url).await?;
+
+    let processed = async {
+        let data = storage.get_object(&object_key).await?;
+        image_processing::store_avatar(storage, "profile-pictures", data.to_vec()).await
+    }
+    .await;
+
+    match processed {
+        Ok(avatar_url) => Ok((object_key, avatar_url)),
+        Err(e) => {
+            release_upload(pool, &object_key).await?;
+            Err(e)
+        }
+    }
+}
+
+/// Give the upload back and clean up the avatar nobody will reference
+pub async fn discard_avatar_upload(pool: &PgPool, object_key: &str, avatar_url: &str) -> Result<()> {
+    release_upload(pool, object_key).await?;
+    storage_outbox::enqueue_deletions(pool, &[avatar_url]).await
+}
+
+/// The original upload still carries its EXIF data, so drop it once the processed images are in use
+pub async fn finish_upload(pool: &PgPool, image_url: &str) -> Result<()> {
+    storage_outbox::enqueue_deletions(pool, &[image_url]).await
+}
+
+/// Give the upload back and clean up variants nobody will reference
+pub async fn discard_portfolio_upload(pool: &PgPool, object_key: &str, variants: &ImageVariants) -> Result<()> {
+    release_upload(pool, object_key).await?;
+    storage_outbox::enqueue_deletions(pool, &variants.urls()).await
+}
+
+/// Undo `claim_upload` when the portfolio item couldn't be created
+pub async fn release_upload(pool: &PgPool, object_key: &str) -> Result<()> {
+    sqlx::query("UPDATE upload_sessions SET attached_at = NULL WHERE object_key = $1")
+        .bind(object_key)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+/// Unattached uploads older than `ttl_hours`, oldest first
+pub async fn find_orphaned_uploads(pool: &PgPool, ttl_hours: i64, limit: i64) -> Result<Vec<String>> {
+    let rows = sqlx::query(
+        r#"
+        SELECT object_key FROM upload_sessions
+        WHERE attached_at IS NULL AND created_at < NOW() - make_interval(hours => $1)
+        ORDER BY created_at
+        LIMIT $2
+        "#
+    )
+    .bind(ttl_hours as i32)
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows.into_iter().map(|row| row.get("object_key")).collect())
+}
+
+/// Remove an orphaned session; returns false if it got attached in the meantime.
+///
+/// Run inside the transaction that also deletes the object so a concurrent
+/// `claim_upload` blocks on the row lock instead of attaching a deleted file.
+pub async fn delete_orphaned_session(tx: &mut Transaction<'_, Postgres>, object_key: &str) -> Result<bool> {
+    let result = sqlx::query("DELETE FROM upload_sessions WHERE object_key = $1 AND attached_at IS NULL")
+        .bind(object_key)
+        .execute(&mut *tx)
+        .await?;
+
+    Ok(result.rows_affected() > 0)
+}
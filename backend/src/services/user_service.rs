@@ -28,8 +28,24 @@ impl UserService for UserServiceImpl {
         Ok(UserResponse::from(user))
     }
 
-    async fn update_profile(&self, pool: &PgPool, auth_header: Option<String>, profile_data: Value) -> Result<UserResponse> {
+    async fn update_profile(&self, pool: &PgPool, auth_header: Option<String>, mut profile_data: Value) -> Result<UserResponse> {
         let user_id = get_user_id_from_auth_header(auth_header)?;
+
+        // The picture goes through its own path so the upload is processed and the old object cleaned up
+        match profile_data.as_object_mut().and_then(|data| data.remove("profile_picture_url")) {
+            Some(Value::Null) => replace_profile_picture(pool, user_id, None).await?,
+            Some(Value::String(profile_picture_url)) => {
+                let current = self.user_repository.find_by_id(pool, user_id).await?
+                    .ok_or_else(|| anyhow::anyhow!("User not found"))?;
+
+                // Sending the current picture back with the rest of the profile leaves it alone
+                if current.profile_picture_url.as_deref() != Some(profile_picture_url.as_str()) {
+                    set_uploaded_profile_picture(pool, user_id, &profile_picture_url).await?;
+                }
+            }
+            _ => {}
+        }
+
         let updated_user = self.user_repository.update_user(pool, user_id, &profile_data).await?;
 
         Ok(UserResponse::from(updated_user))
@@ -49,6 +65,47 @@ pub fn get_user_id_from_auth_header(auth_header: Option<String>) -> Result<Uuid>
     super::auth_service::verify_jwt_token(token)
 }
 
+/// Process a presigned avatar upload like any other image (decoded, re-encoded, metadata
+/// stripped) and make it the user's profile picture, returning the processed URL
+pub async fn set_uploaded_profile_picture(pool: &PgPool, user_id: Uuid, image_url: &str) -> Result<String> {
+    let storage = super::object_storage::from_env().await?;
+    let (object_key, avatar_url) = super::upload_session::attach_avatar_upload(pool, storage.as_ref(), user_id, image_url).await?;
+
+    if let Err(e) = replace_profile_picture(pool, user_id, Some(&avatar_url)).await {
+        super::upload_session::discard_avatar_upload(pool, &object_key, &avatar_url).await?;
+        return Err(e);
+    }
+
+    super::upload_session::finish_upload(pool, image_url).await?;
+    Ok(avatar_url)
+}
+
+/// Point the user at a new profile picture, or none, and queue the previous one for deletion
+pub async fn replace_profile_picture(pool: &PgPool, user_id: Uuid, profile_picture_url: Option<&str>) -> Result<()> {
+    let mut tx = pool.begin().await?;
+
+    let old_url: Option<String> = sqlx::query_scalar(
+        "SELECT profile_picture_url FROM users WHERE id = $1 FOR UPDATE"
+    )
+    .bind(user_id)
+    .fetch_optional(&mut tx)
+    .await?
+    .ok_or_else(|| anyhow::anyhow!("User not found"))?;
+
+    sqlx::query("UPDATE users SET profile_picture_url = $1, updated_at = NOW() WHERE id = $2")
+        .bind(profile_picture_url)
+        .bind(user_id)
+        .execute(&mut tx)
+        .await?;
+
+    if let Some(old_url) = old_url.filter(|old| Some(old.as_str()) != profile_picture_url) {
+        super::storage_outbox::enqueue_deletion(&mut tx, &old_url).await?;
+    }
+
+    tx.commit().await?;
+    Ok(())
+}
+
 // Legacy functions for backward compatibility
 pub async fn get_user_profile(pool: &PgPool, auth_header: Option<String>) -> Result<UserResponse> {
     let user_id = get_user_id_from_auth_header(auth_header)?;
@@ -60,9 +117,61 @@ pub async fn get_user_profile(pool: &PgPool, auth_header: Option<String>) -> Res
 }
 
 pub async fn update_user_profile(pool: &PgPool, auth_header: Option<String>, profile_data: Value) -> Result<UserResponse> {
-    let user_id = get_user_id_from_auth_header(auth_header)?;
     let user_repository = crate::repository::UserRepositoryImpl::new();
-    let updated_user = user_repository.update_user(pool, user_id, &profile_data).await?;
+    let user_service = UserServiceImpl::new(Box::new(user_repository));
+    user_service.update_profile(pool, auth_header, profile_data).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OLD_PICTURE: &str = "http://localhost:8080/storage/avatars/old.webp";
+
+    fn bearer(user_id: Uuid) -> Option<String> {
+        Some(format!("Bearer {}", super::super::auth_service::generate_jwt_token(user_id).unwrap()))
+    }
+
+    async fn queued_deletions(pool: &PgPool) -> i64 {
+        sqlx::query_scalar("SELECT COUNT(*) FROM storage_deletions WHERE object_url = $1")
+            .bind(OLD_PICTURE)
+            .fetch_one(pool)
+            .await
+            .unwrap()
+    }
 
-    Ok(UserResponse::from(updated_user))
-}
\ No newline at end of file
+    #[sqlx::test(migrations = "./migrations")]
+    async fn null_removes_the_profile_picture_and_queues_its_deletion(pool: PgPool) {
+        let user_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO users (email, password_hash, user_type, full_name, profile_picture_url) VALUES ('user@test.muaconnect.dev', 'x', 'customer', 'Intan', $1) RETURNING id"
+        )
+        .bind(OLD_PICTURE)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+        let user = update_user_profile(&pool, bearer(user_id), serde_json::json!({ "full_name": "Intan P", "profile_picture_url": null })).await.unwrap();
+        assert_eq!(user.profile_picture_url, None);
+        assert_eq!(user.full_name, "Intan P");
+        assert_eq!(queued_deletions(&pool).await, 1);
+
+        // Already gone, so there's nothing more to delete
+        update_user_profile(&pool, bearer(user_id), serde_json::json!({ "profile_picture_url": null })).await.unwrap();
+        assert_eq!(queued_deletions(&pool).await, 1);
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    async fn leaving_out_the_profile_picture_keeps_it(pool: PgPool) {
+        let user_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO users (email, password_hash, user_type, full_name, profile_picture_url) VALUES ('user@test.muaconnect.dev', 'x', 'customer', 'Intan', $1) RETURNING id"
+        )
+        .bind(OLD_PICTURE)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+        let user = update_user_profile(&pool, bearer(user_id), serde_json::json!({ "full_name": "Intan P" })).await.unwrap();
+        assert_eq!(user.profile_picture_url.as_deref(), Some(OLD_PICTURE));
+        assert_eq!(queued_deletions(&pool).await, 0);
+    }
+}
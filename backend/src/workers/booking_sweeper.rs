@@ -0,0 +1,37 @@
+use sqlx::PgPool;
+use std::env;
+use std::time::Duration;
+
+use crate::services::booking_service;
+
+/// Moves bookings along when nobody acts on them: pending requests past their
+/// response deadline are cancelled, and confirmed bookings are completed once
+/// the event is over.
+///
+/// Runs every `BOOKING_SWEEP_INTERVAL_SECONDS` (default 60).
+pub fn spawn(pool: PgPool) {
+    let interval_seconds = env::var("BOOKING_SWEEP_INTERVAL_SECONDS")
+        .ok()
+        .and_then(|v| v.parse::<u64>().ok())
+        .unwrap_or(60);
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(interval_seconds));
+
+        loop {
+            interval.tick().await;
+
+            match booking_service::expire_overdue_bookings(&pool).await {
+                Ok(0) => {}
+                Ok(count) => println!("⌛ Expired {} unanswered booking requests", count),
+                Err(e) => eprintln!("Booking expiry sweep failed: {}", e),
+            }
+
+            match booking_service::complete_finished_bookings(&pool).await {
+                Ok(0) => {}
+                Ok(count) => println!("🏁 Completed {} finished bookings", count),
+                Err(e) => eprintln!("Booking completion sweep failed: {}", e),
+            }
+        }
+    });
+}
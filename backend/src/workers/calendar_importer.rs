@@ -0,0 +1,40 @@
+use sqlx::PgPool;
+use std::env;
+use std::time::Duration;
+
+use crate::services::calendar_import;
+
+/// Keeps MUAs' subscribed external calendars fresh, so their busy blocks
+/// follow changes made outside MUA Connect.
+///
+/// Each calendar is refreshed every `CALENDAR_IMPORT_INTERVAL_SECONDS` (default 900).
+pub fn spawn(pool: PgPool) {
+    let interval_seconds = env::var("CALENDAR_IMPORT_INTERVAL_SECONDS")
+        .ok()
+        .and_then(|v| v.parse::<u64>().ok())
+        .unwrap_or(900);
+
+    tokio::spawn(async move {
+        // Check often, the interval decides which calendars are due
+        let mut interval = tokio::time::interval(Duration::from_secs(interval_seconds.clamp(1, 60)));
+
+        loop {
+            interval.tick().await;
+
+            let due = match calendar_import::find_calendars_due(&pool, interval_seconds as i64, 20).await {
+                Ok(due) => due,
+                Err(e) => {
+                    eprintln!("Calendar import sweep failed: {}", e);
+                    continue;
+                }
+            };
+
+            for calendar_id in due {
+                match calendar_import::import_external_calendar(&pool, calendar_id).await {
+                    Ok(count) => println!("📅 Imported {} busy blocks from calendar {}", count, calendar_id),
+                    Err(e) => eprintln!("Importing calendar {} failed: {}", calendar_id, e),
+                }
+            }
+        }
+    });
+}
@@ -0,0 +1,71 @@
+use anyhow::Result;
+use sqlx::PgPool;
+use std::env;
+use std::time::Duration;
+
+use crate::services::cancellation_service;
+use crate::services::job_queue::{self, Job};
+use crate::services::notification_service;
+use crate::services::notifier::{self, Notifier};
+use crate::services::payment_provider::{self, PaymentProvider};
+
+const JOB_BATCH_SIZE: i64 = 20;
+
+/// Runs due jobs from the `jobs` table.
+///
+/// Polls every `JOB_POLL_INTERVAL_SECONDS` (default 10). Rows are locked with
+/// `FOR UPDATE SKIP LOCKED`, so several backend instances can run side by side.
+pub fn spawn(pool: PgPool) {
+    let poll_seconds = env::var("JOB_POLL_INTERVAL_SECONDS")
+        .ok()
+        .and_then(|v| v.parse::<u64>().ok())
+        .unwrap_or(10);
+
+    let notifiers = notifier::notifiers_from_env()
+        .expect("Invalid notification configuration");
+    println!(
+        "🔔 Notification channels: {}",
+        notifiers.iter().map(|n| n.channel()).collect::<Vec<_>>().join(", ")
+    );
+
+    let payment_provider = payment_provider::payment_provider_from_env()
+        .expect("Invalid payment provider configuration");
+    println!("💳 Payment provider: {}", payment_provider.name());
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(poll_seconds));
+
+        loop {
+            interval.tick().await;
+
+            if let Err(e) = run_due_jobs(&pool, &notifiers, payment_provider.as_ref()).await {
+                eprintln!("Job runner failed: {}", e);
+            }
+        }
+    });
+}
+
+async fn run_due_jobs(pool: &PgPool, notifiers: &[Box<dyn Notifier>], payment_provider: &dyn PaymentProvider) -> Result<()> {
+    let mut tx = pool.begin().await?;
+
+    let queued_jobs = job_queue::lock_due_jobs(&mut tx, JOB_BATCH_SIZE).await?;
+
+    for queued in queued_jobs {
+        let result = match serde_json::from_value::<Job>(queued.payload.clone()) {
+            Ok(Job::IssueRefund { booking_id }) => cancellation_service::issue_refund(pool, payment_provider, booking_id).await,
+            Ok(job) => notification_service::handle_job(pool, notifiers, queued.id, &job).await,
+            Err(e) => Err(anyhow::anyhow!("Invalid {} payload: {}", queued.job_type, e)),
+        };
+
+        match result {
+            Ok(()) => job_queue::complete_job(&mut tx, queued.id).await?,
+            Err(e) => {
+                eprintln!("Job {} ({}) failed on attempt {}: {}", queued.id, queued.job_type, queued.attempts + 1, e);
+                job_queue::fail_job(&mut tx, &queued, &e.to_string()).await?;
+            }
+        }
+    }
+
+    tx.commit().await?;
+    Ok(())
+}
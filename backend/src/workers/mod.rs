@@ -0,0 +1,5 @@
+pub mod upload_sweeper;
+pub mod storage_deletions;
+pub mod job_runner;
+pub mod booking_sweeper;
+pub mod calendar_importer;
@@ -0,0 +1,70 @@
+use anyhow::Result;
+use sqlx::PgPool;
+use std::env;
+use std::time::Duration;
+
+use crate::services::object_storage;
+use crate::services::storage_outbox;
+
+const DELETION_BATCH_SIZE: i64 = 50;
+
+/// Drains the `storage_deletions` outbox, retrying failed deletes with backoff.
+///
+/// Runs every `STORAGE_DELETION_INTERVAL_SECONDS` (default 60).
+pub fn spawn(pool: PgPool) {
+    let interval_seconds = env::var("STORAGE_DELETION_INTERVAL_SECONDS")
+        .ok()
+        .and_then(|v| v.parse::<u64>().ok())
+        .unwrap_or(60);
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(interval_seconds));
+
+        loop {
+            interval.tick().await;
+
+            if let Err(e) = process_batch(&pool).await {
+                eprintln!("Storage deletion run failed: {}", e);
+            }
+        }
+    });
+}
+
+async fn process_batch(pool: &PgPool) -> Result<()> {
+    let mut tx = pool.begin().await?;
+
+    let deletions = storage_outbox::lock_due_deletions(&mut tx, DELETION_BATCH_SIZE).await?;
+    if deletions.is_empty() {
+        return Ok(());
+    }
+
+    let storage = object_storage::from_env().await?;
+
+    for deletion in deletions {
+        // Re-attached somewhere since it was queued, nothing to delete
+        if storage_outbox::is_still_referenced(&mut tx, &deletion.object_url).await? {
+            storage_outbox::complete_deletion(&mut tx, deletion.id).await?;
+            continue;
+        }
+
+        let Some(object_key) = storage.key_from_url(&deletion.object_url) else {
+            // Not one of ours (placeholder or external image)
+            storage_outbox::complete_deletion(&mut tx, deletion.id).await?;
+            continue;
+        };
+
+        match storage.delete_object(&object_key).await {
+            Ok(()) => {
+                println!("🗑️ Deleted stored object {}", object_key);
+                storage_outbox::complete_deletion(&mut tx, deletion.id).await?;
+            }
+            Err(e) => {
+                eprintln!("Failed to delete stored object {} (attempt {}): {}", object_key, deletion.attempts + 1, e);
+                storage_outbox::fail_deletion(&mut tx, deletion.id, deletion.attempts, &e.to_string()).await?;
+            }
+        }
+    }
+
+    tx.commit().await?;
+    Ok(())
+}
@@ -0,0 +1,68 @@
+use anyhow::Result;
+use sqlx::PgPool;
+use std::env;
+use std::time::Duration;
+
+use crate::services::object_storage;
+use crate::services::upload_session;
+
+const SWEEP_BATCH_SIZE: i64 = 100;
+
+/// Periodically deletes presigned uploads that were never attached to a portfolio item.
+///
+/// `UPLOAD_ORPHAN_TTL_HOURS` (default 24) is how long an upload may stay unattached,
+/// `UPLOAD_SWEEP_INTERVAL_MINUTES` (default 60) how often the sweep runs.
+pub fn spawn(pool: PgPool) {
+    let ttl_hours = env::var("UPLOAD_ORPHAN_TTL_HOURS")
+        .ok()
+        .and_then(|v| v.parse::<i64>().ok())
+        .unwrap_or(24);
+    let interval_minutes = env::var("UPLOAD_SWEEP_INTERVAL_MINUTES")
+        .ok()
+        .and_then(|v| v.parse::<u64>().ok())
+        .unwrap_or(60);
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(interval_minutes * 60));
+
+        loop {
+            interval.tick().await;
+
+            match sweep(&pool, ttl_hours).await {
+                Ok(0) => {}
+                Ok(count) => println!("🧹 Removed {} orphaned uploads", count),
+                Err(e) => eprintln!("Upload sweep failed: {}", e),
+            }
+        }
+    });
+}
+
+async fn sweep(pool: &PgPool, ttl_hours: i64) -> Result<usize> {
+    let object_keys = upload_session::find_orphaned_uploads(pool, ttl_hours, SWEEP_BATCH_SIZE).await?;
+    if object_keys.is_empty() {
+        return Ok(0);
+    }
+
+    let storage = object_storage::from_env().await?;
+    let mut removed = 0;
+
+    for object_key in object_keys {
+        let mut tx = pool.begin().await?;
+
+        if !upload_session::delete_orphaned_session(&mut tx, &object_key).await? {
+            // Attached since we looked, leave it alone
+            continue;
+        }
+
+        // Only forget the session once the object is really gone, so failures are retried next sweep
+        match storage.delete_object(&object_key).await {
+            Ok(()) => {
+                tx.commit().await?;
+                removed += 1;
+            }
+            Err(e) => eprintln!("Failed to delete orphaned upload {}: {}", object_key, e),
+        }
+    }
+
+    Ok(removed)
+}
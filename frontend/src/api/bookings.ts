@@ -1,4 +1,16 @@
 import apiClient from './client';
+import type { Message, Page, PageParams, Review } from '../types';
+
+export interface BookingListParams extends PageParams {
+  // Comma separated, e.g. 'pending,confirmed'
+  status?: string;
+  // Event dates on the MUA's calendar, YYYY-MM-DD, inclusive
+  from?: string;
+  to?: string;
+  when?: 'upcoming' | 'past';
+  service_type?: string;
+  sort?: 'newest' | 'event_date_asc' | 'event_date_desc';
+}
 
 export interface CreateBookingRequest {
   mua_id: string;
@@ -29,14 +41,97 @@ export interface Booking {
   updated_at: string;
 }
 
+// One booking as seen by one of its participants
+export interface BookingDetail extends Booking {
+  counterpart: {
+    user_id: string;
+    user_type: 'customer' | 'mua';
+    // Set when the counterpart is the MUA
+    mua_id?: string;
+    full_name: string;
+    profile_picture_url?: string;
+    // Only shared once the booking is confirmed
+    phone_number?: string;
+  };
+  payment: {
+    state: 'unpaid' | 'deposit_paid' | 'paid' | 'refund_pending' | 'refunded';
+    amount_paid: string;
+    balance_due: string;
+  };
+  // Oldest first; changed_by is null for automatic transitions
+  status_history: {
+    from_status?: string;
+    to_status: string;
+    changed_by?: string;
+    reason?: string;
+    created_at?: string;
+  }[];
+  reviews: {
+    window_closes_at?: string;
+    can_review: boolean;
+    my_review?: Review;
+    counterpart_review?: Review;
+  };
+}
+
+export interface RescheduleRequest {
+  id: string;
+  booking_id: string;
+  requested_by: string;
+  proposed_event_date: string;
+  proposed_duration_hours: number;
+  previous_event_date: string;
+  previous_duration_hours: number;
+  reason?: string;
+  status: 'pending' | 'accepted' | 'declined' | 'withdrawn';
+  responded_by?: string;
+  responded_at?: string;
+  created_at: string;
+}
+
+export interface CreateRescheduleRequest {
+  event_date: string;
+  duration_hours?: number;
+  reason?: string;
+}
+
+export interface BookingQuote {
+  id: string;
+  booking_id: string;
+  proposed_by: string;
+  price: string;
+  deposit_amount?: string;
+  notes?: string;
+  status: 'open' | 'accepted' | 'countered' | 'closed';
+  responded_at?: string;
+  created_at: string;
+}
+
+export interface CreateQuoteRequest {
+  price: string;
+  deposit_amount?: string;
+  notes?: string;
+}
+
 export const bookingsApi = {
   createBooking: async (bookingData: CreateBookingRequest): Promise<Booking> => {
     const response = await apiClient.post('/bookings', bookingData);
     return response.data;
   },
 
-  getBookings: async (): Promise<Booking[]> => {
-    const response = await apiClient.get('/bookings');
+  // Pass the page's next_cursor back as cursor, with the same filters and sort, for the next page
+  getBookings: async (params?: BookingListParams): Promise<Page<Booking>> => {
+    const response = await apiClient.get('/bookings', { params });
+    return response.data;
+  },
+
+  getBooking: async (bookingId: string): Promise<BookingDetail> => {
+    const response = await apiClient.get(`/bookings/${bookingId}`);
+    return response.data;
+  },
+
+  getMessages: async (bookingId: string, params?: PageParams): Promise<Page<Message>> => {
+    const response = await apiClient.get(`/bookings/${bookingId}/messages`, { params });
     return response.data;
   },
 
@@ -44,4 +139,39 @@ export const bookingsApi = {
     const response = await apiClient.put(`/bookings/${bookingId}/status`, { status });
     return response.data;
   },
+
+  getRescheduleRequests: async (bookingId: string, params?: PageParams): Promise<Page<RescheduleRequest>> => {
+    const response = await apiClient.get(`/bookings/${bookingId}/reschedule-requests`, { params });
+    return response.data;
+  },
+
+  requestReschedule: async (bookingId: string, request: CreateRescheduleRequest): Promise<RescheduleRequest> => {
+    const response = await apiClient.post(`/bookings/${bookingId}/reschedule-requests`, request);
+    return response.data;
+  },
+
+  acceptReschedule: async (bookingId: string, requestId: string): Promise<RescheduleRequest> => {
+    const response = await apiClient.post(`/bookings/${bookingId}/reschedule-requests/${requestId}/accept`);
+    return response.data;
+  },
+
+  declineReschedule: async (bookingId: string, requestId: string): Promise<RescheduleRequest> => {
+    const response = await apiClient.post(`/bookings/${bookingId}/reschedule-requests/${requestId}/decline`);
+    return response.data;
+  },
+
+  getQuotes: async (bookingId: string, params?: PageParams): Promise<Page<BookingQuote>> => {
+    const response = await apiClient.get(`/bookings/${bookingId}/quotes`, { params });
+    return response.data;
+  },
+
+  createQuote: async (bookingId: string, quote: CreateQuoteRequest): Promise<BookingQuote> => {
+    const response = await apiClient.post(`/bookings/${bookingId}/quotes`, quote);
+    return response.data;
+  },
+
+  acceptQuote: async (bookingId: string, quoteId: string): Promise<BookingQuote> => {
+    const response = await apiClient.post(`/bookings/${bookingId}/quotes/${quoteId}/accept`);
+    return response.data;
+  },
 };
\ No newline at end of file
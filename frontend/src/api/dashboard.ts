@@ -1,4 +1,5 @@
 import apiClient from './client';
+import type { BookingLineItem, Page, PageNumberParams, PageParams, SaveServiceOfferingRequest, ServiceOffering } from '../types';
 
 export interface DashboardStats {
   total_bookings: number;
@@ -18,6 +19,7 @@ export interface RecentBooking {
   event_date: string;
   status: 'pending' | 'confirmed' | 'completed' | 'cancelled';
   price: number;
+  line_items: BookingLineItem[];
 }
 
 export interface DashboardResponse {
@@ -34,7 +36,10 @@ export interface TimeSlot {
   is_available: boolean;
   recurring?: boolean;
   day_of_week?: number;
+  // YYYY-MM-DD
   specific_date?: string;
+  // Times and dates are on this zone's wall clock
+  timezone: string;
 }
 
 export interface CreateAvailabilityRequest {
@@ -45,6 +50,24 @@ export interface CreateAvailabilityRequest {
   specific_date?: string;
 }
 
+export interface TimeRange {
+  // HH:MM in the MUA's timezone
+  start_time: string;
+  end_time: string;
+}
+
+export interface WeeklyScheduleDay {
+  // 0 = Sunday
+  day_of_week: number;
+  ranges: TimeRange[];
+}
+
+export interface WeeklySchedule {
+  timezone: string;
+  // All seven days, Sunday first
+  days: WeeklyScheduleDay[];
+}
+
 export interface BookingCalendar {
   id: string;
   customer_name: string;
@@ -55,6 +78,63 @@ export interface BookingCalendar {
   customer_phone?: string;
   location?: string;
   notes?: string;
+  price: number;
+  line_items: BookingLineItem[];
+  // Including the preparation and travel buffers around the booking
+  blocked_from: string;
+  blocked_until: string;
+}
+
+export interface BookingBuffers {
+  prep_minutes: number;
+  travel_minutes: number;
+  travel_minutes_per_km: number;
+}
+
+export interface TimeOff {
+  id: string;
+  mua_id: string;
+  // YYYY-MM-DD, both inclusive, in the MUA's timezone
+  start_date: string;
+  end_date: string;
+  reason?: string;
+  created_at: string;
+}
+
+export interface TimeOffConflict {
+  booking_id: string;
+  customer_name: string;
+  service_type: string;
+  event_date: string;
+  duration_hours: number;
+}
+
+export interface TimeOffCreated extends TimeOff {
+  // Confirmed bookings inside the new time off; they still need moving or cancelling
+  conflicting_bookings: TimeOffConflict[];
+}
+
+export interface MuaTimezone {
+  timezone: string;
+  utc_offset: string;
+}
+
+export interface CalendarFeed {
+  token: string;
+  // Subscribe to this from Google or Apple Calendar; add ?include_pending=true for requests too
+  feed_url: string;
+}
+
+export interface ExternalCalendar {
+  id: string;
+  mua_id: string;
+  name: string;
+  // Missing for calendars uploaded as a file
+  source_url?: string;
+  last_synced_at?: string;
+  last_error?: string;
+  busy_block_count: number;
+  created_at: string;
 }
 
 export const dashboardApi = {
@@ -80,6 +160,17 @@ export const dashboardApi = {
     return response.data;
   },
 
+  getWeeklySchedule: async (): Promise<WeeklySchedule> => {
+    const response = await apiClient.get('/dashboard/availability/weekly');
+    return response.data;
+  },
+
+  // Replaces every recurring slot; days left out become unavailable
+  replaceWeeklySchedule: async (days: WeeklyScheduleDay[]): Promise<WeeklySchedule> => {
+    const response = await apiClient.put('/dashboard/availability/weekly', { days });
+    return response.data;
+  },
+
   updateAvailabilitySlot: async (id: string, data: Partial<CreateAvailabilityRequest>): Promise<TimeSlot> => {
     const response = await apiClient.put(`/dashboard/availability/slots/${id}`, data);
     return response.data;
@@ -101,4 +192,84 @@ export const dashboardApi = {
     const response = await apiClient.put(`/dashboard/bookings/${bookingId}/status`, { status });
     return response.data;
   },
-};
\ No newline at end of file
+
+  getTimeOff: async (params?: PageNumberParams): Promise<Page<TimeOff>> => {
+    const response = await apiClient.get('/dashboard/time-off', { params });
+    return response.data;
+  },
+
+  createTimeOff: async (data: { start_date: string; end_date: string; reason?: string }): Promise<TimeOffCreated> => {
+    const response = await apiClient.post('/dashboard/time-off', data);
+    return response.data;
+  },
+
+  deleteTimeOff: async (id: string): Promise<void> => {
+    await apiClient.delete(`/dashboard/time-off/${id}`);
+  },
+
+  getServices: async (params?: PageNumberParams): Promise<Page<ServiceOffering>> => {
+    const response = await apiClient.get('/dashboard/services', { params });
+    return response.data;
+  },
+
+  createService: async (data: SaveServiceOfferingRequest): Promise<ServiceOffering> => {
+    const response = await apiClient.post('/dashboard/services', data);
+    return response.data;
+  },
+
+  updateService: async (id: string, data: SaveServiceOfferingRequest): Promise<ServiceOffering> => {
+    const response = await apiClient.put(`/dashboard/services/${id}`, data);
+    return response.data;
+  },
+
+  deleteService: async (id: string): Promise<void> => {
+    await apiClient.delete(`/dashboard/services/${id}`);
+  },
+
+  getTimezone: async (): Promise<MuaTimezone> => {
+    const response = await apiClient.get('/dashboard/timezone');
+    return response.data;
+  },
+
+  updateTimezone: async (timezone: string): Promise<MuaTimezone> => {
+    const response = await apiClient.put('/dashboard/timezone', { timezone });
+    return response.data;
+  },
+
+  getCalendarFeed: async (): Promise<CalendarFeed> => {
+    const response = await apiClient.get('/dashboard/calendar-feed');
+    return response.data;
+  },
+
+  rotateCalendarFeed: async (): Promise<CalendarFeed> => {
+    const response = await apiClient.post('/dashboard/calendar-feed/rotate');
+    return response.data;
+  },
+
+  getExternalCalendars: async (params?: PageParams): Promise<Page<ExternalCalendar>> => {
+    const response = await apiClient.get('/dashboard/external-calendars', { params });
+    return response.data;
+  },
+
+  addExternalCalendar: async (url: string, name?: string): Promise<ExternalCalendar> => {
+    const response = await apiClient.post('/dashboard/external-calendars', { url, name });
+    return response.data;
+  },
+
+  uploadExternalCalendar: async (file: File, name?: string): Promise<ExternalCalendar> => {
+    const response = await apiClient.post('/dashboard/external-calendars/upload', file, {
+      params: { name },
+      headers: { 'Content-Type': 'text/calendar' }
+    });
+    return response.data;
+  },
+
+  syncExternalCalendar: async (calendarId: string): Promise<ExternalCalendar> => {
+    const response = await apiClient.post(`/dashboard/external-calendars/${calendarId}/sync`);
+    return response.data;
+  },
+
+  deleteExternalCalendar: async (calendarId: string): Promise<void> => {
+    await apiClient.delete(`/dashboard/external-calendars/${calendarId}`);
+  },
+};
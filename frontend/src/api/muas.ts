@@ -1,22 +1,10 @@
 import apiClient from './client';
-import type { MuaProfile, SearchMuasParams } from '../types';
+import type { Favorite, FavoriteMua, MuaProfile, Page, PageNumberParams, PageParams, SearchMuasParams, ServiceOffering } from '../types';
 import { compressImage } from '../utils/imageCompression';
 
-interface PortfolioResponse {
-  data: any[];
-  pagination: {
-    current_page: number;
-    per_page: number;
-    total_items: number;
-    total_pages: number;
-    has_next_page: boolean;
-    has_prev_page: boolean;
-  };
-}
-
 export const muasApi = {
-  searchMuas: async (params: SearchMuasParams): Promise<MuaProfile[]> => {
-    const response = await apiClient.get<MuaProfile[]>('/muas/search', { params });
+  searchMuas: async (params: SearchMuasParams): Promise<Page<MuaProfile>> => {
+    const response = await apiClient.get<Page<MuaProfile>>('/muas/search', { params });
     return response.data;
   },
 
@@ -25,14 +13,34 @@ export const muasApi = {
     return response.data;
   },
 
-  getMuaPortfolio: async (id: string): Promise<any[]> => {
-    const response = await apiClient.get<any[]>(`/muas/${id}/portfolio`);
+  getMuaServices: async (id: string, params?: PageNumberParams): Promise<Page<ServiceOffering>> => {
+    const response = await apiClient.get<Page<ServiceOffering>>(`/muas/${id}/services`, { params });
+    return response.data;
+  },
+
+  // Passing an event date also asks to be notified when the MUA has openings that day
+  saveFavorite: async (id: string, eventDate?: string): Promise<Favorite> => {
+    const response = await apiClient.post<Favorite>(`/muas/${id}/favorite`, { event_date: eventDate });
+    return response.data;
+  },
+
+  removeFavorite: async (id: string): Promise<void> => {
+    await apiClient.delete(`/muas/${id}/favorite`);
+  },
+
+  getFavorites: async (params?: PageParams): Promise<Page<FavoriteMua>> => {
+    const response = await apiClient.get<Page<FavoriteMua>>('/users/favorites', { params });
+    return response.data;
+  },
+
+  getMuaPortfolio: async (id: string, params?: PageNumberParams): Promise<Page<any>> => {
+    const response = await apiClient.get<Page<any>>(`/muas/${id}/portfolio`, { params });
     return response.data;
   },
 
   // New endpoint for getting current MUA's portfolio with pagination
-  getCurrentMuaPortfolio: async (page: number = 1, limit: number = 10): Promise<PortfolioResponse> => {
-    const response = await apiClient.get<PortfolioResponse>('/muas/portfolio', {
+  getCurrentMuaPortfolio: async (page: number = 1, limit: number = 10): Promise<Page<any>> => {
+    const response = await apiClient.get<Page<any>>('/muas/portfolio', {
       params: { page, limit }
     });
     return response.data;
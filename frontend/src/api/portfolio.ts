@@ -1,4 +1,5 @@
 import apiClient from './client';
+import type { Page, PageNumberParams } from '../types';
 
 export interface PortfolioItem {
   id: string;
@@ -6,6 +7,8 @@ export interface PortfolioItem {
   title: string;
   description?: string;
   image_url: string;
+  thumbnail_url?: string;
+  medium_url?: string;
   service_type?: string;
   created_at: string;
 }
@@ -25,8 +28,8 @@ export interface UpdatePortfolioRequest {
 }
 
 export const portfolioApi = {
-  getPortfolioItems: async (): Promise<PortfolioItem[]> => {
-    const response = await apiClient.get('/portfolio');
+  getPortfolioItems: async (params?: PageNumberParams): Promise<Page<PortfolioItem>> => {
+    const response = await apiClient.get('/portfolio', { params });
     return response.data;
   },
 
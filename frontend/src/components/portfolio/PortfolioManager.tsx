@@ -10,6 +10,8 @@ interface PortfolioItem {
   title: string;
   description?: string;
   image_url: string;
+  thumbnail_url?: string;
+  medium_url?: string;
   service_type?: string;
   created_at: string;
 }
@@ -58,7 +60,7 @@ const PortfolioManager = () => {
         // Use the new endpoint to get current MUA's portfolio with pagination
         const response = await muasApi.getCurrentMuaPortfolio(1, 50); // Get first 50 items
         console.log('DEBUG: Portfolio response fetched:', response);
-        setPortfolioItems(response.data);
+        setPortfolioItems(response.items);
       } else {
         console.log('DEBUG: No user ID found in auth store');
         setPortfolioItems([]);
@@ -268,7 +270,7 @@ const PortfolioManager = () => {
             <div key={item.id} className="bg-white rounded-lg shadow-sm overflow-hidden">
               <div className="aspect-w-16 aspect-h-12 bg-gray-100">
                 <img
-                  src={item.image_url}
+                  src={item.medium_url || item.image_url}
                   alt={item.title}
                   className="w-full h-48 object-cover"
                   onError={(e) => {
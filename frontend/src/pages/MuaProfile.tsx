@@ -226,7 +226,7 @@ const MuaProfile = () => {
                 <h2 className="text-xl font-semibold text-gray-900">Portfolio</h2>
                 <div className="flex items-center text-sm text-gray-600">
                   <Camera className="w-4 h-4 mr-1" />
-                  <span>{portfolio?.length || 0} karya</span>
+                  <span>{portfolio?.total || 0} karya</span>
                 </div>
               </div>
 
@@ -235,13 +235,13 @@ const MuaProfile = () => {
                   <div className="inline-block animate-spin rounded-full h-6 w-6 border-b-2 border-pink-600"></div>
                   <p className="mt-2 text-gray-600">Memuat portfolio...</p>
                 </div>
-              ) : portfolio && portfolio.length > 0 ? (
+              ) : portfolio && portfolio.items.length > 0 ? (
                 <div className="grid grid-cols-1 md:grid-cols-2 lg:grid-cols-3 gap-4">
-                  {portfolio.map((item) => (
+                  {portfolio.items.map((item) => (
                     <div key={item.id} className="group relative overflow-hidden rounded-lg border border-gray-200 hover:shadow-md transition-shadow">
                       <div className="aspect-w-16 aspect-h-12 bg-gray-100 relative">
                         <img
-                          src={item.image_url}
+                          src={item.thumbnail_url || item.image_url}
                           alt={item.title}
                           className="w-full h-full object-cover group-hover:scale-105 transition-transform duration-300"
                           onError={(e) => {
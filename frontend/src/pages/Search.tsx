@@ -16,10 +16,11 @@ const Search = () => {
     limit: 12,
   });
 
-  const { data: muas, isLoading, error } = useQuery({
+  const { data: results, isLoading, error } = useQuery({
     queryKey: ['muas', searchParams],
     queryFn: () => muasApi.searchMuas(searchParams),
   });
+  const muas = results?.items;
 
   const handleSearch = (e: React.FormEvent) => {
     e.preventDefault();
@@ -118,7 +119,7 @@ const Search = () => {
         {/* Results Count */}
         {muas && muas.length > 0 && (
           <div className="mb-6 text-gray-600">
-            <p>Menemukan {muas.length} MUA</p>
+            <p>Menemukan {results?.total} MUA</p>
           </div>
         )}
 
@@ -21,16 +21,60 @@ export interface MuaProfile {
   is_available: boolean;
   average_rating?: number;
   total_reviews?: number;
+  // IANA zone, e.g. Asia/Jakarta
+  timezone: string;
+  // Cheapest service per person, as a decimal string; null when the MUA has no known prices
+  starting_price: string | null;
+  // Only present when the request was signed in
+  is_favorite?: boolean;
+  // Only present when searching with q; escaped HTML with matches in <mark>
+  highlights?: string[];
   created_at: string;
 }
 
+export interface ServiceOffering {
+  id: string;
+  mua_id: string;
+  category: string;
+  name: string;
+  description?: string;
+  // Per person, as a decimal string
+  price: string;
+  duration_minutes: number;
+  created_at: string;
+  updated_at: string;
+}
+
+export interface SaveServiceOfferingRequest {
+  category: string;
+  name: string;
+  description?: string;
+  price: string;
+  duration_minutes: number;
+}
+
+export interface Favorite {
+  mua_id: string;
+  // YYYY-MM-DD the customer wants to hear about openings on
+  event_date?: string;
+  created_at: string;
+}
+
+export interface FavoriteMua extends MuaProfile {
+  event_date?: string;
+  saved_at: string;
+}
+
 export interface Booking {
   id: string;
   customer_id: string;
   mua_id: string;
   service_type: string;
   description?: string;
+  // RFC 3339 with the MUA's UTC offset
   event_date: string;
+  // The MUA's IANA zone
+  timezone: string;
   event_location: string;
   duration_hours: number;
   price: number;
@@ -38,16 +82,59 @@ export interface Booking {
   deposit_amount?: number;
   deposit_paid: boolean;
   final_payment_paid: boolean;
+  response_deadline?: string;
+  cancellation_reason?: string;
+  completed_at?: string;
+  review_window_closes_at?: string;
+  cancellation_policy: CancellationPolicy;
+  refund_amount?: string;
+  refunded_at?: string;
+  event_latitude?: number;
+  event_longitude?: number;
+  travel_distance_km?: number;
+  // Included in price
+  travel_fee: string;
+  // Included in price: what an accepted quote changed from the line items and travel fee
+  price_adjustment: string;
+  line_items: BookingLineItem[];
   created_at: string;
   updated_at: string;
 }
 
+export interface BookingLineItem {
+  id: string;
+  booking_id: string;
+  service_type: string;
+  person_count: number;
+  unit_price: string;
+  minutes_per_person: number;
+  position: number;
+  created_at: string;
+}
+
+export interface CancellationTier {
+  min_hours_before: number;
+  refund_percent: number;
+}
+
+export interface CancellationPolicy {
+  tiers: CancellationTier[];
+}
+
+export interface TravelPricing {
+  free_radius_km: number;
+  fee_per_km: string;
+  max_distance_km?: number | null;
+}
+
 export interface PortfolioItem {
   id: string;
   mua_id: string;
   title: string;
   description?: string;
   image_url: string;
+  thumbnail_url?: string;
+  medium_url?: string;
   service_type?: string;
   created_at: string;
 }
@@ -87,16 +174,30 @@ export interface RegisterRequest {
 
 export interface CreateBookingRequest {
   mua_id: string;
-  service_type: string;
+  // Derived from line_items when those are given
+  service_type?: string;
   description?: string;
   event_date: string;
   event_location: string;
-  duration_hours: number;
-  price: number;
+  event_latitude?: number;
+  event_longitude?: number;
+  duration_hours?: number;
+  price?: number;
   deposit_amount?: number;
+  line_items?: CreateBookingLineItem[];
+  response_deadline?: string;
+}
+
+export interface CreateBookingLineItem {
+  service_type: string;
+  person_count: number;
+  unit_price: string;
+  minutes_per_person: number;
 }
 
 export interface SearchMuasParams {
+  // Free text over name, specialization, bio and portfolio
+  q?: string;
   location?: string;
   latitude?: number;
   longitude?: number;
@@ -104,9 +205,45 @@ export interface SearchMuasParams {
   date?: string;
   specialization?: string;
   min_rating?: number;
+  min_price?: number;
+  max_price?: number;
+  service_category?: string;
+  min_experience_years?: number;
+  sort?: 'relevance' | 'rating' | 'price_asc' | 'price_desc';
+  page?: number;
+  limit?: number;
+}
+
+// One page of a list endpoint: numbered lists fill in `page`, keyset lists fill in
+// `next_cursor`, which goes back as `cursor` for the next page (null on the last one)
+export interface Page<T> {
+  items: T[];
+  total: number;
+  page: number | null;
+  limit: number;
+  next_cursor: string | null;
+}
+
+export interface PageParams {
+  cursor?: string;
+  limit?: number;
+}
+
+// For lists paged by number rather than by cursor
+export interface PageNumberParams {
   page?: number;
   limit?: number;
 }
 
+export interface Message {
+  id: string;
+  booking_id?: string;
+  sender_id: string;
+  receiver_id: string;
+  content: string;
+  is_read?: boolean;
+  created_at: string;
+}
+
 // Export all request/response types
 export type { LoginRequest, RegisterRequest, CreateBookingRequest, SearchMuasParams };
\ No newline at end of file